@@ -1,8 +1,26 @@
-use anyhow::{bail, Result};
-use clap::{Parser, Subcommand};
+use anyhow::{anyhow, bail, Result};
+use clap::{Args, Parser, Subcommand};
 use std::path::PathBuf;
-
-const DEFAULT_URL: &str = "https://www.uio.no/studier/emner/ledige-plasser/";
+use std::time::Duration;
+
+use crate::config_file::{ConfigFile, DEFAULT_PROFILE};
+use crate::db::Debounce;
+use crate::exclude::{ExclusionRule, Exclusions};
+use crate::filter::FilterExpr;
+use crate::guard::SanityGuard;
+use crate::models::{normalize_course_code, Course};
+use crate::notifier::NOTIFIER_NAMES;
+use crate::profile::ParserProfile;
+use crate::route::{Route, RouteRule};
+use crate::schedule::{HotPolicy, Schedule};
+use crate::source::{HttpSettings, RetryPolicy, DEFAULT_USER_AGENT};
+use crate::watchlist::Watchlist;
+
+/// Course vacancy page monitored when no --url or config file says otherwise
+pub const DEFAULT_URL: &str = "https://www.uio.no/studier/emner/ledige-plasser/";
+
+/// Name of the single source used when no config file defines any
+pub const DEFAULT_SOURCE: &str = "default";
 
 #[derive(Parser, Debug, Clone)]
 #[command(name = "uiobot")]
@@ -25,9 +43,17 @@ pub enum Command {
         #[command(flatten)]
         config: Config,
 
-        /// Scrape interval in seconds (minimum 10)
-        #[arg(short, long, default_value = "60")]
-        interval: u64,
+        #[command(flatten)]
+        schedule: ScheduleArgs,
+    },
+    /// Print the upcoming scrape times of the configured schedule
+    NextRuns {
+        #[command(flatten)]
+        schedule: ScheduleArgs,
+
+        /// Number of runs to show
+        #[arg(short = 'n', long, default_value = "10")]
+        count: usize,
     },
     /// Send a test email notification to verify email configuration
     TestEmail {
@@ -51,12 +77,80 @@ pub enum Command {
     },
 }
 
+/// When `start` scrapes; times are in Europe/Oslo
+#[derive(Args, Debug, Clone)]
+pub struct ScheduleArgs {
+    /// Scrape interval in seconds (minimum 10); outside --active-hours windows when those are set
+    #[arg(short, long, default_value = "60")]
+    pub interval: u64,
+
+    /// Scrape at the times matched by a cron expression instead of a fixed interval
+    /// Example: --cron "*/5 7-22 * * Mon-Fri" (minute hour day month weekday)
+    #[arg(long, env = "UIOBOT_CRON", value_name = "EXPR", conflicts_with = "active_hours")]
+    pub cron: Option<String>,
+
+    /// Intervals by weekday and time of day, first match wins; --interval applies outside them
+    /// Example: --active-hours "mon-fri 08:00-16:00=30s; 07:00-23:00=5m"
+    #[arg(long, env = "UIOBOT_ACTIVE_HOURS", value_name = "WINDOWS")]
+    pub active_hours: Option<String>,
+
+    /// Date ranges with tighter polling, e.g. around registration deadlines
+    /// Example: --hot-periods "08-01..08-25; 2027-01-05..2027-01-20" (MM-DD repeats every year)
+    #[arg(long, env = "UIOBOT_HOT_PERIODS", value_name = "PERIODS")]
+    pub hot_periods: Option<String>,
+
+    /// Also poll tightly while a source has had at least this many changes in the last 24 hours
+    #[arg(long, env = "UIOBOT_HOT_CHANGE_THRESHOLD", value_name = "CHANGES")]
+    pub hot_change_threshold: Option<usize>,
+
+    /// Scrape interval in seconds while hot (minimum 10)
+    #[arg(long, env = "UIOBOT_HOT_INTERVAL", default_value = "20", value_name = "SECONDS")]
+    pub hot_interval: u64,
+}
+
+impl ScheduleArgs {
+    /// Schedule for a source; `interval_secs` replaces --interval (e.g. a per-source interval)
+    pub fn schedule(&self, interval_secs: u64) -> Result<Schedule> {
+        validate_interval(interval_secs)?;
+        let interval = Duration::from_secs(interval_secs);
+
+        if let Some(ref cron) = self.cron {
+            return Schedule::parse_cron(cron);
+        }
+        if let Some(ref windows) = self.active_hours {
+            return Schedule::parse_windows(windows, interval);
+        }
+        Ok(Schedule::Interval(interval))
+    }
+
+    /// When to poll faster than the schedule
+    pub fn hot_policy(&self) -> Result<HotPolicy> {
+        validate_interval(self.hot_interval)?;
+        HotPolicy::new(
+            self.hot_periods.as_deref(),
+            Duration::from_secs(self.hot_interval),
+            self.hot_change_threshold,
+        )
+    }
+}
+
 #[derive(Parser, Debug, Clone)]
 pub struct Config {
     /// URL to scrape
     #[arg(short, long, default_value = DEFAULT_URL)]
     pub url: String,
 
+    /// Read the page from somewhere other than --url (overrides --url for fetching)
+    /// Formats: "file:///path/page.html" (single file), "file:///path/dir/" (replay snapshots),
+    /// "https://..." (live HTTP)
+    #[arg(long, env = "UIOBOT_SOURCE", value_name = "SOURCE")]
+    pub source: Option<String>,
+
+    /// TOML file with named [[sources]] to monitor in one process
+    /// (replaces --url/--source as the list of pages to scrape)
+    #[arg(long, env = "UIOBOT_CONFIG_FILE", value_name = "PATH")]
+    pub config_file: Option<PathBuf>,
+
     /// Database file path (for local SQLite, ignored if --database-url is set)
     #[arg(short, long, default_value = "uiobot.db")]
     pub db: PathBuf,
@@ -83,10 +177,124 @@ pub struct Config {
     pub points_min: Option<f32>,
 
     /// Filter: points filter expression (alternative to individual flags)
-    /// Formats: "2.5" (exact), ">=5" (min), "<=10" (max), "5-10" (range)
+    /// Formats: "2.5" (exact), ">=5" (min), "<=10" (max), ">5"/"<10" (strict), "5-10" (range),
+    /// "2.5,5,7.5" or "<=5 | 20" (any of several)
     #[arg(long, env = "UIOBOT_POINTS_FILTER", value_name = "FILTER")]
     pub points_filter_expr: Option<String>,
 
+    /// Filter: only courses from these departments (comma-separated code prefixes)
+    /// Example: --departments "IN,MAT-INF"
+    #[arg(long, env = "UIOBOT_DEPARTMENTS", value_name = "PREFIXES", value_delimiter = ',')]
+    pub departments: Vec<String>,
+
+    /// Filter: only courses at these levels (comma-separated)
+    /// Example: --levels "1000,2000"
+    #[arg(long, env = "UIOBOT_LEVELS", value_name = "LEVELS", value_delimiter = ',')]
+    pub levels: Vec<u16>,
+
+    /// Filter: expression over course fields, combined with the filters above
+    /// Example: --filter 'points <= 5 and faculty ~ "matematisk" and not code ^= "EXPH"'
+    #[arg(long = "filter", env = "UIOBOT_FILTER", value_name = "EXPRESSION")]
+    pub filter_expr: Option<String>,
+
+    /// Course codes or patterns (* and ?) that are always notified, whatever the filters
+    /// Example: --watch "IN1000,MAT-INF1*"; more can be added from the web UI
+    #[arg(long = "watch", env = "UIOBOT_WATCHLIST", value_name = "CODES", value_delimiter = ',')]
+    pub watchlist: Vec<String>,
+
+    /// Never notify these courses (watchlist courses excepted): code:PATTERN, faculty:TEXT or level:N
+    /// Example: --exclude "level:9000,code:EVU*"
+    #[arg(long = "exclude", env = "UIOBOT_EXCLUDE", value_name = "RULES", value_delimiter = ',')]
+    pub exclude: Vec<String>,
+
+    /// Notifiers that only receive watchlist hits (console, email, sms, slack)
+    /// Example: --urgent-channels sms
+    #[arg(long, env = "UIOBOT_URGENT_CHANNELS", value_name = "NOTIFIERS", value_delimiter = ',')]
+    pub urgent_channels: Vec<String>,
+
+    /// Which change types a notifier receives: NOTIFIER=EVENTS [where EXPRESSION], rules separated by ';'
+    /// Events: added, removed, modified, reappeared, watched (watchlist hits) or *
+    /// Example: --route "sms=added,reappeared,watched; email=*"
+    #[arg(long = "route", env = "UIOBOT_ROUTES", value_name = "RULES", value_delimiter = ';')]
+    pub routes: Vec<String>,
+
+    /// Retries after a transient fetch failure (timeout, DNS, connection, 5xx, 408/429)
+    #[arg(long, env = "UIOBOT_FETCH_RETRIES", default_value = "3", value_name = "COUNT")]
+    pub fetch_retries: u32,
+
+    /// Base delay before the first retry in milliseconds (doubles per retry, with jitter)
+    #[arg(long, env = "UIOBOT_RETRY_BACKOFF_MS", default_value = "1000", value_name = "MS")]
+    pub retry_backoff_ms: u64,
+
+    /// User-Agent header for requests to uio.no
+    #[arg(long, env = "UIOBOT_USER_AGENT", default_value = DEFAULT_USER_AGENT)]
+    pub user_agent: String,
+
+    /// Seconds allowed for establishing a connection
+    #[arg(long, env = "UIOBOT_HTTP_CONNECT_TIMEOUT", default_value = "10", value_name = "SECONDS")]
+    pub http_connect_timeout: u64,
+
+    /// Seconds allowed for a whole request, including reading the page (also used for Slack webhooks)
+    #[arg(long, env = "UIOBOT_HTTP_TIMEOUT", default_value = "30", value_name = "SECONDS")]
+    pub http_timeout: u64,
+
+    /// Proxy for requests to uio.no (e.g., http://proxy.example.org:3128)
+    /// Without it the HTTP_PROXY/HTTPS_PROXY environment variables are used
+    #[arg(long, env = "UIOBOT_HTTP_PROXY", value_name = "URL")]
+    pub http_proxy: Option<String>,
+
+    /// Extra request header as "Name: value" (repeatable; ';'-separated in the environment)
+    #[arg(long = "http-header", env = "UIOBOT_HTTP_HEADERS", value_name = "HEADER", value_delimiter = ';')]
+    pub http_headers: Vec<String>,
+
+    /// PEM file with additional trusted root certificates (repeatable)
+    #[arg(long = "http-ca-cert", env = "UIOBOT_HTTP_CA_CERTS", value_name = "PATH", value_delimiter = ',')]
+    pub http_ca_certs: Vec<PathBuf>,
+
+    /// Archive the raw HTML of fetched pages, keeping at most this many snapshots
+    /// (disabled when not set)
+    #[arg(long, env = "UIOBOT_SNAPSHOT_RETENTION", value_name = "COUNT")]
+    pub snapshot_retention: Option<usize>,
+
+    /// Reject a scrape when the course count drops by more than this percentage
+    /// compared to the stored courses (guards against maintenance pages)
+    #[arg(long, env = "UIOBOT_MAX_DROP_PERCENT", default_value = "50", value_name = "PERCENT")]
+    pub max_drop_percent: f64,
+
+    /// Accept a course count drop as the new baseline once the same count has been
+    /// rejected this many cycles in a row (0 keeps rejecting it)
+    #[arg(long, env = "UIOBOT_ACCEPT_DROP_AFTER", default_value = "3", value_name = "CYCLES")]
+    pub accept_drop_after: usize,
+
+    /// Disable the sanity guard and sync every parsed page, even if it looks broken
+    #[arg(long, env = "UIOBOT_DISABLE_SANITY_GUARD")]
+    pub disable_sanity_guard: bool,
+
+    /// Only report a course as removed after it has been absent for this many consecutive cycles
+    #[arg(long, env = "UIOBOT_REMOVAL_CYCLES", value_name = "N")]
+    pub removal_cycles: Option<u32>,
+
+    /// Only report a course as removed after it has been absent for this many minutes
+    /// (whichever of --removal-cycles and --removal-minutes is reached first)
+    #[arg(long, env = "UIOBOT_REMOVAL_MINUTES", value_name = "MINUTES")]
+    pub removal_minutes: Option<u32>,
+
+    /// Only report a course as added after it has been present for this many consecutive cycles
+    #[arg(long, env = "UIOBOT_ADDITION_CYCLES", default_value = "1", value_name = "N")]
+    pub addition_cycles: u32,
+
+    /// Fetch the detail page of every new course for language, semester, exam form, etc.
+    #[arg(long, env = "UIOBOT_ENRICH")]
+    pub enrich: bool,
+
+    /// Maximum number of course detail pages fetched at the same time
+    #[arg(long, env = "UIOBOT_ENRICH_CONCURRENCY", default_value = "4", value_name = "N")]
+    pub enrich_concurrency: usize,
+
+    /// Reuse cached course details for this many days before fetching them again
+    #[arg(long, env = "UIOBOT_ENRICH_CACHE_DAYS", default_value = "30", value_name = "DAYS")]
+    pub enrich_cache_days: u32,
+
     /// Enable verbose logging
     #[arg(short, long)]
     pub verbose: bool,
@@ -113,6 +321,11 @@ pub struct Config {
     /// Twilio phone number to send SMS from
     #[arg(long, env = "TWILIO_FROM_NUMBER")]
     pub sms_from: Option<String>,
+
+    /// Slack incoming webhook to post notifications to
+    /// Example: --slack-webhook-url "https://hooks.slack.com/services/T000/B000/XXXX"
+    #[arg(long, env = "UIOBOT_SLACK_WEBHOOK_URL", value_name = "URL")]
+    pub slack_webhook_url: Option<String>,
 }
 
 impl Cli {
@@ -162,6 +375,11 @@ impl Config {
         self.sms_to.is_some() && !self.sms_recipients().is_empty()
     }
 
+    /// Check if Slack notifications are enabled
+    pub fn slack_enabled(&self) -> bool {
+        self.slack_webhook_url.as_deref().is_some_and(|url| !url.trim().is_empty())
+    }
+
     /// Validate the configuration and return errors if invalid
     pub fn validate(&self) -> Result<()> {
         // Validate URL
@@ -172,6 +390,30 @@ impl Config {
             );
         }
 
+        // Validate page source override
+        if let Some(ref source) = self.source {
+            let source = source.trim();
+            if !source.starts_with("file://")
+                && !source.starts_with("http://")
+                && !source.starts_with("https://")
+            {
+                bail!(
+                    "Invalid source '{}': must start with file://, http:// or https://\n\
+                     Example: --source file:///tmp/ledige-plasser.html",
+                    source
+                );
+            }
+        }
+
+        // Validate the config file, if any
+        self.sources()?;
+
+        // Validate HTTP client settings (headers, proxy, certificates)
+        if self.http_timeout == 0 || self.http_connect_timeout == 0 {
+            bail!("Invalid HTTP timeout: --http-timeout and --http-connect-timeout must be at least 1 second.");
+        }
+        self.http_settings()?.build_client()?;
+
         // Validate database configuration
         if let Some(ref db_url) = self.database_url {
             // Validate database URL format
@@ -193,6 +435,45 @@ impl Config {
             }
         }
 
+        self.points_filter()?;
+        if let Some(ref expr) = self.filter_expr {
+            FilterExpr::parse(expr).map_err(|e| anyhow!("Invalid --filter expression: {}", e))?;
+        }
+
+        Watchlist::new(&self.watchlist)?;
+        self.exclusions()?;
+        for channel in &self.urgent_channels {
+            if !NOTIFIER_NAMES.contains(&channel.trim()) {
+                bail!(
+                    "Invalid --urgent-channels entry '{}': must be one of {}",
+                    channel,
+                    NOTIFIER_NAMES.join(", ")
+                );
+            }
+        }
+
+        let mut routed = Vec::new();
+        for rule in self.route_rules()? {
+            if !NOTIFIER_NAMES.contains(&rule.notifier.as_str()) {
+                bail!(
+                    "Invalid --route for '{}': notifier must be one of {}",
+                    rule.notifier,
+                    NOTIFIER_NAMES.join(", ")
+                );
+            }
+            if routed.contains(&rule.notifier) {
+                bail!("Invalid --route: more than one rule for '{}'", rule.notifier);
+            }
+            if self.urgent_channels.iter().any(|c| c.trim() == rule.notifier) {
+                bail!(
+                    "'{}' is in both --route and --urgent-channels (use --route {}=watched instead)",
+                    rule.notifier,
+                    rule.notifier
+                );
+            }
+            routed.push(rule.notifier);
+        }
+
         // Validate points filter
         if let (Some(min), Some(max)) = (self.points_min, self.points_max) {
             if min > max {
@@ -210,6 +491,44 @@ impl Config {
             }
         }
 
+        // Validate level filter
+        for level in &self.levels {
+            if !(1000..=9000).contains(level) || level % 1000 != 0 {
+                bail!(
+                    "Invalid level '{}' in --levels: must be one of 1000, 2000, ..., 9000",
+                    level
+                );
+            }
+        }
+
+        if self.snapshot_retention == Some(0) {
+            bail!(
+                "Invalid --snapshot-retention: must be at least 1.\n\
+                 Leave it unset to disable the snapshot archive."
+            );
+        }
+
+        if !(0.0..=100.0).contains(&self.max_drop_percent) {
+            bail!(
+                "Invalid --max-drop-percent {}: must be between 0 and 100.",
+                self.max_drop_percent
+            );
+        }
+
+        if self.removal_cycles == Some(0) || self.removal_minutes == Some(0) || self.addition_cycles == 0 {
+            bail!(
+                "Invalid debounce settings: --removal-cycles, --removal-minutes and \
+                 --addition-cycles must be at least 1."
+            );
+        }
+
+        if self.enrich && !(1..=16).contains(&self.enrich_concurrency) {
+            bail!(
+                "Invalid --enrich-concurrency {}: must be between 1 and 16.",
+                self.enrich_concurrency
+            );
+        }
+
         // Validate email configuration
         if self.email_enabled() {
             // Validate email_from is set
@@ -283,27 +602,185 @@ impl Config {
             }
         }
 
+        if let Some(ref url) = self.slack_webhook_url {
+            if self.slack_enabled() && !url.starts_with("https://") && !url.starts_with("http://") {
+                bail!(
+                    "Invalid --slack-webhook-url: must be an http(s) URL\n\
+                     Expected format: https://hooks.slack.com/services/..."
+                );
+            }
+        }
+
         Ok(())
     }
 
-    pub fn points_filter(&self) -> PointsFilter {
+    /// Pages to monitor: the config file's [[sources]], or a single source from --url/--source
+    pub fn sources(&self) -> Result<Vec<SourceSettings>> {
+        let file = match self.config_file {
+            Some(ref path) => ConfigFile::load(path)?,
+            None => ConfigFile::default(),
+        };
+
+        if file.sources.is_empty() {
+            return Ok(vec![SourceSettings {
+                name: DEFAULT_SOURCE.to_string(),
+                location: self.source.clone().unwrap_or_else(|| self.url.clone()),
+                page_url: self.url.clone(),
+                profile: DEFAULT_PROFILE.to_string(),
+                parser: file.parser_profile(DEFAULT_PROFILE).unwrap_or_default(),
+                interval_secs: None,
+            }]);
+        }
+
+        Ok(file
+            .sources
+            .iter()
+            .cloned()
+            .map(|entry| SourceSettings {
+                // Validated when the file was loaded
+                parser: file.parser_profile(&entry.profile).unwrap_or_default(),
+                // Links on file:// pages are still resolved against the live page
+                page_url: if entry.url.starts_with("file://") {
+                    self.url.clone()
+                } else {
+                    entry.url.clone()
+                },
+                name: entry.name,
+                location: entry.url,
+                profile: entry.profile,
+                interval_secs: entry.interval,
+            })
+            .collect())
+    }
+
+    /// Retry policy for live HTTP fetches
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_retries: self.fetch_retries,
+            base_delay: Duration::from_millis(self.retry_backoff_ms),
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// HTTP client settings for the course page and course detail pages
+    pub fn http_settings(&self) -> Result<HttpSettings> {
+        let headers = self
+            .http_headers
+            .iter()
+            .filter(|header| !header.trim().is_empty())
+            .map(|header| match header.split_once(':') {
+                Some((name, value)) if !name.trim().is_empty() => {
+                    Ok((name.trim().to_string(), value.trim().to_string()))
+                }
+                _ => bail!(
+                    "Invalid --http-header '{}'\n\
+                     Expected format: \"Name: value\"",
+                    header
+                ),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(HttpSettings {
+            user_agent: self.user_agent.clone(),
+            connect_timeout: Duration::from_secs(self.http_connect_timeout),
+            timeout: Duration::from_secs(self.http_timeout),
+            proxy: self.http_proxy.clone(),
+            headers,
+            ca_certs: self.http_ca_certs.clone(),
+        })
+    }
+
+    /// Sanity guard applied to parsed pages before sync
+    pub fn sanity_guard(&self) -> SanityGuard {
+        if self.disable_sanity_guard {
+            SanityGuard::disabled()
+        } else {
+            SanityGuard::new(self.max_drop_percent, self.accept_drop_after)
+        }
+    }
+
+    /// Flap suppression rules for sync
+    pub fn debounce(&self) -> Debounce {
+        Debounce {
+            removal_cycles: self.removal_cycles,
+            removal_after: self
+                .removal_minutes
+                .map(|minutes| chrono::Duration::minutes(minutes.into())),
+            addition_cycles: self.addition_cycles,
+        }
+    }
+
+    /// Watchlist from the command line and environment (entries from the web UI are added per cycle)
+    pub fn watchlist(&self) -> Result<Watchlist> {
+        Watchlist::new(&self.watchlist)
+    }
+
+    /// Parsed --exclude rules
+    pub fn exclusions(&self) -> Result<Exclusions> {
+        Exclusions::new(&self.exclude)
+    }
+
+    /// Parsed --route rules
+    pub fn route_rules(&self) -> Result<Vec<RouteRule>> {
+        self.routes
+            .iter()
+            .filter(|rule| !rule.trim().is_empty())
+            .map(|rule| RouteRule::parse(rule).map_err(|e| anyhow!("Invalid --route: {:#}", e)))
+            .collect()
+    }
+
+    /// What a notifier receives: its --route rule, only watchlist hits when it is an
+    /// urgent channel, or everything (None)
+    pub fn route_for(&self, notifier: &str) -> Result<Option<Route>> {
+        if let Some(rule) = self.route_rules()?.into_iter().find(|r| r.notifier == notifier) {
+            return Ok(Some(rule.route));
+        }
+        if self.urgent_channels.iter().any(|c| c.trim() == notifier) {
+            return Ok(Some(Route::watched_only()));
+        }
+        Ok(None)
+    }
+
+    /// Points, department and level filters combined
+    pub fn course_filter(&self) -> Result<CourseFilter> {
+        let expression = match self.filter_expr {
+            Some(ref expr) => Some(
+                FilterExpr::parse(expr).map_err(|e| anyhow!("Invalid --filter expression: {}", e))?,
+            ),
+            None => None,
+        };
+
+        Ok(CourseFilter {
+            points: self.points_filter()?,
+            departments: self
+                .departments
+                .iter()
+                .map(|d| normalize_course_code(d))
+                .filter(|d| !d.is_empty())
+                .collect(),
+            levels: self.levels.clone(),
+            expression,
+            exclusions: self.exclusions()?,
+        })
+    }
+
+    pub fn points_filter(&self) -> Result<PointsFilter> {
         // First check if points_filter_expr is set (takes precedence)
         if let Some(ref expr) = self.points_filter_expr {
-            if let Some(filter) = parse_points_filter_expr(expr) {
-                return filter;
-            }
+            return parse_points_filter_expr(expr)
+                .map_err(|e| anyhow!("Invalid --points-filter-expr '{}': {}", expr.trim(), e));
         }
 
         // Fall back to individual flags
         if let Some(exact) = self.points_exact {
-            PointsFilter::Exact(exact)
+            Ok(PointsFilter::Exact(exact))
         } else if self.points_min.is_some() || self.points_max.is_some() {
-            PointsFilter::Range {
+            Ok(PointsFilter::Range {
                 min: self.points_min,
                 max: self.points_max,
-            }
+            })
         } else {
-            PointsFilter::None
+            Ok(PointsFilter::None)
         }
     }
 }
@@ -311,78 +788,79 @@ impl Config {
 /// Parse a points filter expression string
 /// Formats:
 /// - "2.5" -> exact match
-/// - ">=5" or "5+" -> minimum
-/// - "<=10" or "10-" -> maximum
+/// - ">=5" or "5+" -> minimum, ">5" -> strictly more
+/// - "<=10" or "10-" -> maximum, "<10" -> strictly less
 /// - "5-10" -> range (min-max)
-fn parse_points_filter_expr(expr: &str) -> Option<PointsFilter> {
+/// - "2.5,5,7.5" or "<=5 | 20" -> any of the terms (',' and '|' both separate them)
+fn parse_points_filter_expr(expr: &str) -> Result<PointsFilter> {
     let expr = expr.trim();
 
     if expr.is_empty() {
-        return Some(PointsFilter::None);
+        return Ok(PointsFilter::None);
     }
 
-    // Range format: "5-10" or "5.0-10.0"
-    if let Some(dash_pos) = expr.find('-') {
-        // Make sure it's not just a negative number or suffix like "10-"
-        if dash_pos > 0 && dash_pos < expr.len() - 1 {
-            let left = expr[..dash_pos].trim();
-            let right = expr[dash_pos + 1..].trim();
-
-            if let (Ok(min), Ok(max)) = (left.parse::<f32>(), right.parse::<f32>()) {
-                return Some(PointsFilter::Range {
-                    min: Some(min),
-                    max: Some(max),
-                });
-            }
-        }
+    let mut terms = expr
+        .split([',', '|'])
+        .map(parse_points_term)
+        .collect::<Result<Vec<_>>>()?;
+    if terms.len() == 1 {
+        return Ok(terms.remove(0));
     }
+    Ok(PointsFilter::AnyOf(terms))
+}
 
-    // Minimum format: ">=5" or ">5"
-    if expr.starts_with(">=") {
-        if let Ok(min) = expr[2..].trim().parse::<f32>() {
-            return Some(PointsFilter::Range { min: Some(min), max: None });
-        }
+/// Parse one term of a points filter expression
+fn parse_points_term(term: &str) -> Result<PointsFilter> {
+    let term = term.trim();
+    let number = |text: &str| {
+        text.trim()
+            .parse::<f32>()
+            .ok()
+            .filter(|points| points.is_finite() && *points >= 0.0)
+            .ok_or_else(|| anyhow!("'{}' is not a number of points", text.trim()))
+    };
+
+    if term.is_empty() {
+        bail!("empty term (check for a doubled or trailing ',' or '|')");
     }
-    if expr.starts_with('>') && !expr.starts_with(">=") {
-        if let Ok(min) = expr[1..].trim().parse::<f32>() {
-            // For ">5", we use 5.01 as a slight offset (not strictly greater, but close)
-            return Some(PointsFilter::Range { min: Some(min), max: None });
-        }
+
+    if let Some(rest) = term.strip_prefix(">=") {
+        return Ok(PointsFilter::Range { min: Some(number(rest)?), max: None });
+    }
+    if let Some(rest) = term.strip_prefix('>') {
+        return Ok(PointsFilter::Above(number(rest)?));
+    }
+    if let Some(rest) = term.strip_prefix("<=") {
+        return Ok(PointsFilter::Range { min: None, max: Some(number(rest)?) });
+    }
+    if let Some(rest) = term.strip_prefix('<') {
+        return Ok(PointsFilter::Below(number(rest)?));
     }
 
     // Minimum format: "5+"
-    if expr.ends_with('+') {
-        if let Ok(min) = expr[..expr.len() - 1].trim().parse::<f32>() {
-            return Some(PointsFilter::Range { min: Some(min), max: None });
-        }
+    if let Some(rest) = term.strip_suffix('+') {
+        return Ok(PointsFilter::Range { min: Some(number(rest)?), max: None });
     }
 
-    // Maximum format: "<=10" or "<10"
-    if expr.starts_with("<=") {
-        if let Ok(max) = expr[2..].trim().parse::<f32>() {
-            return Some(PointsFilter::Range { min: None, max: Some(max) });
-        }
-    }
-    if expr.starts_with('<') && !expr.starts_with("<=") {
-        if let Ok(max) = expr[1..].trim().parse::<f32>() {
-            return Some(PointsFilter::Range { min: None, max: Some(max) });
-        }
+    // Maximum format: "10-" (trailing dash)
+    if let Some(rest) = term.strip_suffix('-') {
+        return Ok(PointsFilter::Range { min: None, max: Some(number(rest)?) });
     }
 
-    // Maximum format: "10-" (trailing dash)
-    if expr.ends_with('-') && expr.len() > 1 {
-        if let Ok(max) = expr[..expr.len() - 1].trim().parse::<f32>() {
-            return Some(PointsFilter::Range { min: None, max: Some(max) });
+    // Range format: "5-10" or "5.0-10.0"
+    if let Some((left, right)) = term.split_once('-') {
+        let (min, max) = (number(left)?, number(right)?);
+        if min > max {
+            bail!("range '{}' has its minimum above its maximum", term);
         }
+        return Ok(PointsFilter::Range {
+            min: Some(min),
+            max: Some(max),
+        });
     }
 
     // Exact format: just a number
-    if let Ok(exact) = expr.parse::<f32>() {
-        return Some(PointsFilter::Exact(exact));
-    }
-
-    // Couldn't parse
-    None
+    Ok(PointsFilter::Exact(number(term)?))
 }
 
 /// Simple email validation (not RFC 5322 compliant but good enough)
@@ -470,10 +948,9 @@ pub fn normalize_norwegian_phone(phone: &str) -> Option<String> {
     let cleaned: String = phone.chars().filter(|c| !c.is_whitespace() && *c != '-').collect();
 
     // Extract digits and optional leading +
-    let (has_plus, digits): (bool, String) = if cleaned.starts_with('+') {
-        (true, cleaned[1..].to_string())
-    } else {
-        (false, cleaned)
+    let (has_plus, digits): (bool, String) = match cleaned.strip_prefix('+') {
+        Some(rest) => (true, rest.to_string()),
+        None => (false, cleaned),
     };
 
     // Must be all digits after optional +
@@ -511,6 +988,21 @@ pub fn normalize_norwegian_phone(phone: &str) -> Option<String> {
     Some(format!("+47{}", eight_digits))
 }
 
+/// One page to monitor, resolved from the config file or the --url/--source flags
+#[derive(Debug, Clone)]
+pub struct SourceSettings {
+    pub name: String,
+    /// http(s):// URL or file:// spec the page is read from
+    pub location: String,
+    /// URL relative course links are resolved against
+    pub page_url: String,
+    /// Name of the parser profile, and the profile itself
+    pub profile: String,
+    pub parser: ParserProfile,
+    /// Seconds between scrapes in `start` (None uses --interval)
+    pub interval_secs: Option<u64>,
+}
+
 /// Validate the interval for the start command
 pub fn validate_interval(interval: u64) -> Result<()> {
     if interval < 10 {
@@ -527,7 +1019,74 @@ pub fn validate_interval(interval: u64) -> Result<()> {
 pub enum PointsFilter {
     None,
     Exact(f32),
+    /// Inclusive bounds
     Range { min: Option<f32>, max: Option<f32> },
+    /// Strictly more than
+    Above(f32),
+    /// Strictly less than
+    Below(f32),
+    /// Matches when any of the filters does, e.g. "<=5 | 20"
+    AnyOf(Vec<PointsFilter>),
+}
+
+/// Which courses changes are notified for
+#[derive(Debug, Clone)]
+pub struct CourseFilter {
+    pub points: PointsFilter,
+    /// Department prefixes to include (empty includes all)
+    pub departments: Vec<String>,
+    /// Course levels to include (empty includes all)
+    pub levels: Vec<u16>,
+    /// Expression the course must also match (see `filter::FilterExpr`)
+    pub expression: Option<FilterExpr>,
+    /// Applied after the criteria above; see `CourseFilter::exclusion`
+    pub exclusions: Exclusions,
+}
+
+impl From<PointsFilter> for CourseFilter {
+    fn from(points: PointsFilter) -> Self {
+        Self {
+            points,
+            departments: Vec::new(),
+            levels: Vec::new(),
+            expression: None,
+            exclusions: Exclusions::default(),
+        }
+    }
+}
+
+impl CourseFilter {
+    pub fn matches(&self, course: &Course) -> bool {
+        self.points.matches(course.points)
+            && (self.departments.is_empty()
+                || self.departments.iter().any(|d| d == course.department()))
+            && (self.levels.is_empty()
+                || course.level().is_some_and(|level| self.levels.contains(&level)))
+            && self.expression.as_ref().is_none_or(|expr| expr.matches(course))
+    }
+
+    pub fn description(&self) -> String {
+        let mut description = self.points.description();
+        if !self.departments.is_empty() {
+            description.push_str(&format!(", departments {}", self.departments.join("/")));
+        }
+        if !self.levels.is_empty() {
+            let levels: Vec<String> = self.levels.iter().map(|l| l.to_string()).collect();
+            description.push_str(&format!(", levels {}", levels.join("/")));
+        }
+        if let Some(ref expr) = self.expression {
+            description.push_str(&format!(", where {}", expr));
+        }
+        if !self.exclusions.is_empty() {
+            description.push_str(&format!(", excluding {}", self.exclusions.description()));
+        }
+        description
+    }
+
+    /// The exclusion rule dropping a course that `matches`, if any
+    pub fn exclusion(&self, course: &Course) -> Option<&ExclusionRule> {
+        self.exclusions.matching(course)
+    }
 }
 
 impl PointsFilter {
@@ -536,23 +1095,41 @@ impl PointsFilter {
             PointsFilter::None => true,
             PointsFilter::Exact(exact) => (points - exact).abs() < 0.01,
             PointsFilter::Range { min, max } => {
-                let above_min = min.map_or(true, |m| points >= m);
-                let below_max = max.map_or(true, |m| points <= m);
+                let above_min = min.is_none_or(|m| points >= m);
+                let below_max = max.is_none_or(|m| points <= m);
                 above_min && below_max
             }
+            PointsFilter::Above(min) => points > *min,
+            PointsFilter::Below(max) => points < *max,
+            PointsFilter::AnyOf(filters) => filters.iter().any(|f| f.matches(points)),
         }
     }
 
     pub fn description(&self) -> String {
         match self {
-            PointsFilter::None => "all courses".to_string(),
+            PointsFilter::None | PointsFilter::Range { min: None, max: None } => "all courses".to_string(),
             PointsFilter::Exact(v) => format!("courses with exactly {} points", v),
-            PointsFilter::Range { min, max } => match (min, max) {
-                (Some(min), Some(max)) => format!("courses with {}-{} points", min, max),
-                (Some(min), None) => format!("courses with >= {} points", min),
-                (None, Some(max)) => format!("courses with <= {} points", max),
-                (None, None) => "all courses".to_string(),
-            },
+            _ => format!("courses with {} points", self.condition()),
+        }
+    }
+
+    /// The points condition alone, e.g. "<= 5 or 20" or "2.5, 5 or 7.5"
+    fn condition(&self) -> String {
+        match self {
+            PointsFilter::None | PointsFilter::Range { min: None, max: None } => "any".to_string(),
+            PointsFilter::Exact(v) => v.to_string(),
+            PointsFilter::Range { min: Some(min), max: Some(max) } => format!("{}-{}", min, max),
+            PointsFilter::Range { min: Some(min), max: None } => format!(">= {}", min),
+            PointsFilter::Range { min: None, max: Some(max) } => format!("<= {}", max),
+            PointsFilter::Above(min) => format!("> {}", min),
+            PointsFilter::Below(max) => format!("< {}", max),
+            PointsFilter::AnyOf(filters) => {
+                let terms: Vec<String> = filters.iter().map(PointsFilter::condition).collect();
+                match terms.split_last() {
+                    Some((last, rest)) if !rest.is_empty() => format!("{} or {}", rest.join(", "), last),
+                    _ => terms.join(""),
+                }
+            }
         }
     }
 }
@@ -593,6 +1170,8 @@ mod tests {
     fn test_email_recipients() {
         let config = Config {
             url: "https://example.com".to_string(),
+            source: None,
+            config_file: None,
             db: PathBuf::from("test.db"),
             database_url: None,
             database_auth_token: None,
@@ -600,12 +1179,38 @@ mod tests {
             points_max: None,
             points_min: None,
             points_filter_expr: None,
+            departments: Vec::new(),
+            levels: Vec::new(),
+            filter_expr: None,
+            watchlist: Vec::new(),
+            exclude: Vec::new(),
+            urgent_channels: Vec::new(),
+            routes: Vec::new(),
+            fetch_retries: 3,
+            retry_backoff_ms: 1000,
+            snapshot_retention: None,
+            max_drop_percent: 50.0,
+            accept_drop_after: 3,
+            disable_sanity_guard: false,
+            removal_cycles: None,
+            removal_minutes: None,
+            addition_cycles: 1,
+            enrich: false,
+            enrich_concurrency: 4,
+            enrich_cache_days: 30,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            http_connect_timeout: 10,
+            http_timeout: 30,
+            http_proxy: None,
+            http_headers: Vec::new(),
+            http_ca_certs: Vec::new(),
             verbose: false,
             email_to: Some("a@b.com, c@d.com, e@f.com".to_string()),
             email_from: None,
             port: 3000,
             sms_to: None,
             sms_from: None,
+            slack_webhook_url: None,
         };
 
         let recipients = config.email_recipients();
@@ -633,7 +1238,9 @@ mod tests {
         assert!(matches!(filter, PointsFilter::Range { min: Some(v), max: None } if (v - 5.0).abs() < 0.01));
 
         let filter = parse_points_filter_expr(">5").unwrap();
-        assert!(matches!(filter, PointsFilter::Range { min: Some(v), max: None } if (v - 5.0).abs() < 0.01));
+        assert!(matches!(filter, PointsFilter::Above(v) if (v - 5.0).abs() < 0.01));
+        assert!(!filter.matches(5.0));
+        assert!(filter.matches(7.5));
     }
 
     #[test]
@@ -642,7 +1249,9 @@ mod tests {
         assert!(matches!(filter, PointsFilter::Range { min: None, max: Some(v) } if (v - 10.0).abs() < 0.01));
 
         let filter = parse_points_filter_expr("<10").unwrap();
-        assert!(matches!(filter, PointsFilter::Range { min: None, max: Some(v) } if (v - 10.0).abs() < 0.01));
+        assert!(matches!(filter, PointsFilter::Below(v) if (v - 10.0).abs() < 0.01));
+        assert!(!filter.matches(10.0));
+        assert!(filter.matches(7.5));
 
         let filter = parse_points_filter_expr("10-").unwrap();
         assert!(matches!(filter, PointsFilter::Range { min: None, max: Some(v) } if (v - 10.0).abs() < 0.01));
@@ -674,10 +1283,38 @@ mod tests {
         assert!(matches!(filter, PointsFilter::None));
     }
 
+    #[test]
+    fn test_parse_points_filter_expr_sets_and_unions() {
+        let filter = parse_points_filter_expr("2.5,5, 7.5").unwrap();
+        assert!(filter.matches(5.0));
+        assert!(!filter.matches(10.0));
+        assert_eq!(filter.description(), "courses with 2.5, 5 or 7.5 points");
+
+        let filter = parse_points_filter_expr("<=5 | 20").unwrap();
+        assert!(filter.matches(2.5));
+        assert!(filter.matches(20.0));
+        assert!(!filter.matches(10.0));
+        assert_eq!(filter.description(), "courses with <= 5 or 20 points");
+
+        let filter = parse_points_filter_expr(">5,<2.5").unwrap();
+        assert!(!filter.matches(5.0));
+        assert!(!filter.matches(2.5));
+        assert_eq!(filter.description(), "courses with > 5 or < 2.5 points");
+    }
+
+    #[test]
+    fn test_parse_points_filter_expr_errors() {
+        for invalid in ["five", ">=", "5,", "5||10", "10-5", "5-x", "-5", ">= -1", "2.5 points"] {
+            assert!(parse_points_filter_expr(invalid).is_err(), "{} should not parse", invalid);
+        }
+    }
+
     #[test]
     fn test_points_filter_expr_takes_precedence() {
         let config = Config {
             url: "https://example.com".to_string(),
+            source: None,
+            config_file: None,
             db: PathBuf::from("test.db"),
             database_url: None,
             database_auth_token: None,
@@ -685,16 +1322,50 @@ mod tests {
             points_max: None,
             points_min: None,
             points_filter_expr: Some("2.5".to_string()), // This takes precedence
+            departments: Vec::new(),
+            levels: Vec::new(),
+            filter_expr: None,
+            watchlist: Vec::new(),
+            exclude: Vec::new(),
+            urgent_channels: Vec::new(),
+            routes: Vec::new(),
+            fetch_retries: 3,
+            retry_backoff_ms: 1000,
+            snapshot_retention: None,
+            max_drop_percent: 50.0,
+            accept_drop_after: 3,
+            disable_sanity_guard: false,
+            removal_cycles: None,
+            removal_minutes: None,
+            addition_cycles: 1,
+            enrich: false,
+            enrich_concurrency: 4,
+            enrich_cache_days: 30,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            http_connect_timeout: 10,
+            http_timeout: 30,
+            http_proxy: None,
+            http_headers: Vec::new(),
+            http_ca_certs: Vec::new(),
             verbose: false,
             email_to: None,
             email_from: None,
             port: 3000,
             sms_to: None,
             sms_from: None,
+            slack_webhook_url: None,
         };
 
-        let filter = config.points_filter();
+        let filter = config.points_filter().unwrap();
         assert!(matches!(filter, PointsFilter::Exact(v) if (v - 2.5).abs() < 0.01));
+
+        // An unparseable expression is an error, not a fallback to the individual flags
+        let config = Config {
+            points_filter_expr: Some(">= five".to_string()),
+            ..config
+        };
+        assert!(config.points_filter().is_err());
+        assert!(config.validate().is_err());
     }
 
     #[test]
@@ -752,4 +1423,214 @@ mod tests {
         assert_eq!(normalize_twilio_phone("+442071234567"), None); // UK
         assert_eq!(normalize_twilio_phone("+33123456789"), None); // France
     }
+
+    #[test]
+    fn test_http_settings_from_flags() {
+        let cli = Cli::try_parse_from([
+            "uiobot",
+            "check",
+            "--http-header",
+            "X-Campus-Token: abc:def",
+            "--http-timeout",
+            "5",
+            "--http-proxy",
+            "http://proxy.example.org:3128",
+        ])
+        .unwrap();
+        let Command::Check { config } = cli.command else {
+            panic!("expected check command");
+        };
+
+        let http = config.http_settings().unwrap();
+        assert_eq!(http.user_agent, DEFAULT_USER_AGENT);
+        assert_eq!(http.timeout, Duration::from_secs(5));
+        assert_eq!(http.connect_timeout, Duration::from_secs(10));
+        assert_eq!(http.headers, vec![("X-Campus-Token".to_string(), "abc:def".to_string())]);
+        assert!(http.build_client().is_ok());
+
+        let config = Config {
+            http_headers: vec!["no colon".to_string()],
+            ..config
+        };
+        assert!(config.http_settings().is_err());
+    }
+
+    #[test]
+    fn test_course_filter_by_department_and_level() {
+        let filter = CourseFilter {
+            points: PointsFilter::Range {
+                min: Some(5.0),
+                max: None,
+            },
+            departments: vec!["IN".to_string(), "MAT-INF".to_string()],
+            levels: vec![1000, 2000],
+            expression: None,
+            exclusions: Exclusions::default(),
+        };
+        let course = |code: &str, points: f32| {
+            Course::new(code.to_string(), String::new(), points, String::new(), String::new())
+        };
+
+        assert!(filter.matches(&course("IN1000", 10.0)));
+        assert!(filter.matches(&course("MAT-INF1100", 10.0)));
+        // Prefixes match whole departments, not the start of a longer one
+        assert!(!filter.matches(&course("INF1000", 10.0)));
+        assert!(!filter.matches(&course("IN4000", 10.0)));
+        assert!(!filter.matches(&course("IN1020", 2.5)));
+        assert_eq!(
+            filter.description(),
+            "courses with >= 5 points, departments IN/MAT-INF, levels 1000/2000"
+        );
+    }
+
+    #[test]
+    fn test_filter_expression_flag() {
+        let cli = Cli::try_parse_from([
+            "uiobot",
+            "check",
+            "--points-filter-expr",
+            "<=10",
+            "--filter",
+            "department = IN and not level = 1000",
+        ])
+        .unwrap();
+        let Command::Check { config } = cli.command else {
+            panic!("expected check command");
+        };
+
+        let filter = config.course_filter().unwrap();
+        let course = |code: &str| {
+            Course::new(code.to_string(), String::new(), 10.0, String::new(), String::new())
+        };
+        assert!(filter.matches(&course("IN2010")));
+        assert!(!filter.matches(&course("IN1000")));
+        assert!(!filter.matches(&course("MAT2000")));
+        assert_eq!(
+            filter.description(),
+            "courses with <= 10 points, where department = IN and not level = 1000"
+        );
+
+        let config = Config {
+            filter_expr: Some("department = IN and".to_string()),
+            ..config
+        };
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.starts_with("Invalid --filter expression: expected a field name"), "{}", err);
+    }
+
+    #[test]
+    fn test_route_rules() {
+        let cli = Cli::try_parse_from([
+            "uiobot",
+            "check",
+            "--route",
+            "sms=added,reappeared where points <= 10; email=*",
+        ])
+        .unwrap();
+        let Command::Check { config } = cli.command else {
+            panic!("expected check command");
+        };
+        config.validate().unwrap();
+        assert_eq!(
+            config.route_for("sms").unwrap().unwrap().to_string(),
+            "added,reappeared where points <= 10"
+        );
+        assert_eq!(config.route_for("email").unwrap().unwrap().to_string(), "*");
+        assert!(config.route_for("console").unwrap().is_none());
+
+        let invalid = |routes: &[&str], urgent: &[&str]| {
+            Config {
+                routes: routes.iter().map(|r| r.to_string()).collect(),
+                urgent_channels: urgent.iter().map(|c| c.to_string()).collect(),
+                ..config.clone()
+            }
+            .validate()
+            .is_err()
+        };
+        assert!(invalid(&["pager=added"], &[]));
+        assert!(invalid(&["sms=added", "sms=removed"], &[]));
+        assert!(invalid(&["sms=added"], &["sms"]));
+        assert!(invalid(&["sms=seats"], &[]));
+    }
+
+    #[test]
+    fn test_watchlist_and_urgent_channels() {
+        let cli = Cli::try_parse_from([
+            "uiobot",
+            "check",
+            "--watch",
+            "in1000,MAT-INF1*",
+            "--urgent-channels",
+            "sms",
+        ])
+        .unwrap();
+        let Command::Check { config } = cli.command else {
+            panic!("expected check command");
+        };
+
+        let watchlist = config.watchlist().unwrap();
+        assert_eq!(watchlist.patterns().collect::<Vec<_>>(), vec!["IN1000", "MAT-INF1*"]);
+        assert_eq!(config.route_for("sms").unwrap().unwrap().to_string(), "watched");
+        assert!(config.route_for("email").unwrap().is_none());
+
+        let config = Config {
+            urgent_channels: vec!["pager".to_string()],
+            ..config
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_exclusions_flag() {
+        let cli = Cli::try_parse_from([
+            "uiobot",
+            "check",
+            "--points-filter-expr",
+            ">=5",
+            "--exclude",
+            "level:9000,code:EVU*",
+        ])
+        .unwrap();
+        let Command::Check { config } = cli.command else {
+            panic!("expected check command");
+        };
+
+        let filter = config.course_filter().unwrap();
+        assert_eq!(filter.description(), "courses with >= 5 points, excluding level:9000, code:EVU*");
+        let course = |code: &str| Course::new(code.to_string(), String::new(), 10.0, String::new(), String::new());
+        assert!(filter.matches(&course("IN9000")));
+        assert!(filter.exclusion(&course("IN9000")).is_some());
+        assert!(filter.exclusion(&course("IN1000")).is_none());
+
+        let config = Config {
+            exclude: vec!["semester:autumn".to_string()],
+            ..config
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_slack_webhook_url() {
+        let cli = Cli::try_parse_from([
+            "uiobot",
+            "check",
+            "--slack-webhook-url",
+            "https://hooks.slack.com/services/T000/B000/XXXX",
+            "--route",
+            "slack=added,watched",
+        ])
+        .unwrap();
+        let Command::Check { config } = cli.command else {
+            panic!("expected check command");
+        };
+        assert!(config.slack_enabled());
+        assert!(config.validate().is_ok());
+        assert_eq!(config.route_for("slack").unwrap().unwrap().to_string(), "added,watched");
+
+        let config = Config {
+            slack_webhook_url: Some("hooks.slack.com/services/T000".to_string()),
+            ..config
+        };
+        assert!(config.validate().is_err());
+    }
 }
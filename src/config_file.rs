@@ -0,0 +1,217 @@
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use crate::config::validate_interval;
+use crate::profile::ParserProfile;
+
+/// Settings loaded from the optional TOML file given with --config-file
+///
+/// ```toml
+/// [[sources]]
+/// name = "regular"
+/// url = "https://www.uio.no/studier/emner/ledige-plasser/"
+///
+/// [[sources]]
+/// name = "english"
+/// url = "https://www.uio.no/english/studies/courses/vacant-places/"
+/// interval = 300
+/// profile = "vortex-h3"
+///
+/// [profiles.vortex-h3]
+/// heading_selector = "h3[id]"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    #[serde(default)]
+    pub sources: Vec<SourceEntry>,
+    /// Named parser profiles; `[profiles.default]` replaces the built-in layout
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ParserProfile>,
+}
+
+/// One `[[sources]]` entry
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SourceEntry {
+    /// Short name shown in the dashboard and notifications, and stored with courses and runs
+    pub name: String,
+    /// Page to scrape: http(s):// URL, or file:// path for offline testing
+    pub url: String,
+    /// Parser profile used for this page
+    #[serde(default = "default_profile")]
+    pub profile: String,
+    /// Seconds between scrapes in `start` (defaults to --interval)
+    pub interval: Option<u64>,
+}
+
+/// Name of the parser profile used when a source names none
+pub const DEFAULT_PROFILE: &str = "default";
+
+fn default_profile() -> String {
+    DEFAULT_PROFILE.to_string()
+}
+
+impl ConfigFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let file: ConfigFile = toml::from_str(text)?;
+        file.validate()?;
+        Ok(file)
+    }
+
+    /// Parser profile by name; "default" falls back to the built-in layout
+    pub fn parser_profile(&self, name: &str) -> Option<ParserProfile> {
+        match self.profiles.get(name) {
+            Some(profile) => Some(profile.clone()),
+            None if name == DEFAULT_PROFILE => Some(ParserProfile::default()),
+            None => None,
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        for (name, profile) in &self.profiles {
+            profile
+                .compile()
+                .with_context(|| format!("Invalid parser profile '{}'", name))?;
+        }
+
+        let mut names = HashSet::new();
+        for source in &self.sources {
+            if source.name.is_empty()
+                || !source
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                bail!(
+                    "Invalid source name '{}': use letters, digits, '-' and '_' only",
+                    source.name
+                );
+            }
+
+            if !names.insert(source.name.as_str()) {
+                bail!("Duplicate source name '{}'", source.name);
+            }
+
+            if !source.url.starts_with("file://")
+                && !source.url.starts_with("http://")
+                && !source.url.starts_with("https://")
+            {
+                bail!(
+                    "Invalid url '{}' for source '{}': must start with file://, http:// or https://",
+                    source.url,
+                    source.name
+                );
+            }
+
+            if self.parser_profile(&source.profile).is_none() {
+                bail!(
+                    "Unknown parser profile '{}' for source '{}'",
+                    source.profile,
+                    source.name
+                );
+            }
+
+            if let Some(interval) = source.interval {
+                validate_interval(interval)
+                    .with_context(|| format!("Invalid interval for source '{}'", source.name))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sources() {
+        let file = ConfigFile::parse(
+            r#"
+            [[sources]]
+            name = "regular"
+            url = "https://www.uio.no/studier/emner/ledige-plasser/"
+
+            [[sources]]
+            name = "english"
+            url = "https://www.uio.no/english/studies/courses/vacant-places/"
+            interval = 300
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(file.sources.len(), 2);
+        assert_eq!(file.sources[0].profile, DEFAULT_PROFILE);
+        assert_eq!(file.sources[0].interval, None);
+        assert_eq!(file.sources[1].name, "english");
+        assert_eq!(file.sources[1].interval, Some(300));
+    }
+
+    #[test]
+    fn test_rejects_invalid_sources() {
+        let duplicate = r#"
+            [[sources]]
+            name = "a"
+            url = "https://example.com/"
+            [[sources]]
+            name = "a"
+            url = "https://example.org/"
+        "#;
+        assert!(ConfigFile::parse(duplicate).is_err());
+
+        let bad_name = "[[sources]]\nname = \"with space\"\nurl = \"https://example.com/\"";
+        assert!(ConfigFile::parse(bad_name).is_err());
+
+        let too_fast = "[[sources]]\nname = \"a\"\nurl = \"https://example.com/\"\ninterval = 1";
+        assert!(ConfigFile::parse(too_fast).is_err());
+
+        let typo = "[[sources]]\nname = \"a\"\nurll = \"https://example.com/\"";
+        assert!(ConfigFile::parse(typo).is_err());
+    }
+
+    #[test]
+    fn test_parser_profiles() {
+        let file = ConfigFile::parse(
+            r#"
+            [[sources]]
+            name = "english"
+            url = "https://www.uio.no/english/studies/courses/vacant-places/"
+            profile = "english"
+
+            [profiles.english]
+            heading_selector = "h3[id]"
+
+            [profiles.english.columns]
+            points = ["Credits (ECTS)"]
+            "#,
+        )
+        .unwrap();
+
+        let english = file.parser_profile("english").unwrap();
+        assert_eq!(english.heading_selector, "h3[id]");
+        assert_eq!(english.table_selector, ParserProfile::default().table_selector);
+        assert_eq!(file.parser_profile(DEFAULT_PROFILE), Some(ParserProfile::default()));
+
+        let unknown = "[[sources]]\nname = \"a\"\nurl = \"https://example.com/\"\nprofile = \"nope\"";
+        assert!(ConfigFile::parse(unknown).is_err());
+
+        // The shipped example must stay loadable
+        let example = ConfigFile::parse(include_str!("../uiobot.example.toml")).unwrap();
+        assert_eq!(example.sources.len(), 3);
+        assert!(example.parser_profile("english").is_some());
+
+        let bad_selector = "[profiles.broken]\ntable_selector = \"table[\"";
+        let err = ConfigFile::parse(bad_selector).unwrap_err();
+        assert!(format!("{:#}", err).contains("Invalid parser profile 'broken'"));
+    }
+}
@@ -1,224 +1,418 @@
-use anyhow::{Context, Result};
-use scraper::{ElementRef, Html, Selector};
+use anyhow::{bail, Result};
+use reqwest::Url;
+use scraper::{ElementRef, Html};
+use sha2::{Digest, Sha256};
+use std::sync::Mutex;
 use std::time::Instant;
 use tracing::{debug, info, instrument, warn};
 
-use crate::models::Course;
+use crate::error::ScrapeError;
+use crate::models::{normalize_course_code, Course};
+use crate::profile::CompiledProfile;
+use crate::source::{CourseSource, FetchedPage};
+
+/// Faculty recorded for courses whose table has no preceding faculty heading
+const UNKNOWN_FACULTY: &str = "Unknown Faculty";
 
 pub struct CourseScraper {
-    client: reqwest::Client,
-    url: String,
+    /// Configured source name, stored with courses and runs
+    name: String,
+    source: Box<dyn CourseSource>,
+    /// Selectors and column names for the page layout
+    profile: CompiledProfile,
+    /// Page URL that course links are resolved against (links are kept as written without it)
+    page_url: Option<Url>,
+    /// Hash and course count of the last page that made it through a full cycle
+    last_processed: Mutex<Option<ProcessedPage>>,
+}
+
+#[derive(Debug, Clone)]
+struct ProcessedPage {
+    content_hash: String,
+    course_count: usize,
+}
+
+/// Result of fetching and parsing the course page once
+#[derive(Debug)]
+pub struct ScrapedPage {
+    /// Raw HTML exactly as served by the source
+    pub html: String,
+    /// Hex-encoded SHA-256 of the raw HTML
+    pub content_hash: String,
+    pub courses: Vec<Course>,
+    pub structure: PageStructure,
+}
+
+/// Structural counts seen while parsing, used to spot maintenance pages and markup changes
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PageStructure {
+    /// Tables found in the content area
+    pub tables: usize,
+    /// Faculty section headings found in the content area
+    pub faculty_headings: usize,
+}
+
+/// A page identical to the last processed one; parsing was skipped
+#[derive(Debug)]
+pub struct UnchangedPage {
+    pub content_hash: String,
+    /// Course count of the last processed page
+    pub course_count: usize,
+    /// True when the server answered 304, false when the body hash matched
+    pub not_modified: bool,
+}
+
+/// A fetched page that could not be parsed, kept so it can be archived and inspected
+#[derive(Debug, thiserror::Error)]
+#[error("page {content_hash} could not be parsed")]
+pub struct UnparsedPage {
+    /// Raw HTML exactly as served by the source
+    pub html: String,
+    pub content_hash: String,
+    #[source]
+    pub error: ScrapeError,
+}
+
+#[derive(Debug)]
+pub enum ScrapeOutcome {
+    Changed(ScrapedPage),
+    Unchanged(UnchangedPage),
 }
 
 impl CourseScraper {
-    pub fn new(url: String) -> Self {
-        let client = reqwest::Client::builder()
-            .user_agent("UiOBot/1.0 (Course Availability Monitor)")
-            .build()
-            .expect("Failed to create HTTP client");
+    pub fn new(name: impl Into<String>, source: Box<dyn CourseSource>) -> Self {
+        let name = name.into();
+        info!(name = %name, source = %source.describe(), "Scraper initialized");
+        Self {
+            name,
+            source,
+            profile: CompiledProfile::default(),
+            page_url: None,
+            last_processed: Mutex::new(None),
+        }
+    }
+
+    /// Parse pages with the given profile instead of the built-in UiO layout
+    pub fn with_profile(mut self, profile: CompiledProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Resolve course links against the given page URL
+    pub fn with_page_url(mut self, page_url: Url) -> Self {
+        self.page_url = Some(page_url);
+        self
+    }
+
+    /// Configured source name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 
-        info!(url = %url, "Scraper initialized");
-        Self { client, url }
+    /// Description of the underlying page source
+    pub fn source_description(&self) -> String {
+        self.source.describe()
     }
 
-    #[instrument(skip(self), fields(url = %self.url))]
-    pub async fn fetch_courses(&self) -> Result<Vec<Course>> {
+    /// Fetch the page and parse it, unless it is unchanged since the last processed page
+    #[instrument(skip(self), fields(name = %self.name, source = %self.source.describe()))]
+    pub async fn fetch_courses(&self) -> Result<ScrapeOutcome> {
         let start = Instant::now();
-        info!(url = %self.url, "Starting HTTP fetch");
-
-        let response = self
-            .client
-            .get(&self.url)
-            .send()
-            .await
-            .context("Failed to fetch URL")?;
-
-        let status = response.status();
-        let status_code = status.as_u16();
-
-        if !status.is_success() {
-            warn!(
-                status_code = status_code,
-                status_text = %status,
-                url = %self.url,
-                "HTTP request failed"
-            );
-            anyhow::bail!("HTTP error: {}", status);
-        }
+        let last = self.last_processed.lock().unwrap().clone();
+
+        let html = match self.source.fetch_page().await? {
+            FetchedPage::Content(html) => html,
+            FetchedPage::NotModified => match last {
+                Some(last) => {
+                    return Ok(ScrapeOutcome::Unchanged(UnchangedPage {
+                        content_hash: last.content_hash,
+                        course_count: last.course_count,
+                        not_modified: true,
+                    }))
+                }
+                None => {
+                    // Nothing to compare against; force a full fetch next time
+                    self.source.invalidate();
+                    bail!("Source reported page not modified, but no page has been processed yet");
+                }
+            },
+        };
 
-        let content_length = response.content_length();
-        let html = response.text().await.context("Failed to read response body")?;
-        let fetch_duration_ms = start.elapsed().as_millis();
+        let content_hash = content_hash(&html);
 
-        info!(
-            status_code = status_code,
-            content_length_header = ?content_length,
-            body_bytes = html.len(),
-            fetch_duration_ms = fetch_duration_ms,
-            "HTTP fetch completed"
-        );
+        if let Some(last) = last.filter(|last| last.content_hash == content_hash) {
+            debug!(
+                content_hash = %content_hash,
+                "Page content hash unchanged, skipping parse"
+            );
+            return Ok(ScrapeOutcome::Unchanged(UnchangedPage {
+                content_hash,
+                course_count: last.course_count,
+                not_modified: false,
+            }));
+        }
 
         let parse_start = Instant::now();
-        let courses = self.parse_courses(&html)?;
+        let (courses, structure) = match self.parse_courses(&html) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                // The source already stored this response's validators; without forgetting
+                // them the next poll gets a 304 and reports the broken page as unchanged
+                self.source.invalidate();
+                return Err(UnparsedPage {
+                    html,
+                    content_hash,
+                    error,
+                }
+                .into());
+            }
+        };
         let parse_duration_ms = parse_start.elapsed().as_millis();
 
         info!(
             courses_parsed = courses.len(),
+            tables = structure.tables,
+            faculty_headings = structure.faculty_headings,
+            content_hash = %content_hash,
             parse_duration_ms = parse_duration_ms,
             total_duration_ms = start.elapsed().as_millis(),
             "Fetch and parse completed"
         );
 
-        Ok(courses)
+        Ok(ScrapeOutcome::Changed(ScrapedPage {
+            html,
+            content_hash,
+            courses,
+            structure,
+        }))
+    }
+
+    /// Remember a page as fully processed, so identical fetches can be skipped
+    pub fn mark_processed(&self, page: &ScrapedPage) {
+        *self.last_processed.lock().unwrap() = Some(ProcessedPage {
+            content_hash: page.content_hash.clone(),
+            course_count: page.courses.len(),
+        });
     }
 
-    fn parse_courses(&self, html: &str) -> Result<Vec<Course>> {
+    /// Forget the last processed page so the next fetch is fully parsed and synced
+    pub fn invalidate(&self) {
+        *self.last_processed.lock().unwrap() = None;
+        self.source.invalidate();
+    }
+
+    fn parse_courses(&self, html: &str) -> Result<(Vec<Course>, PageStructure), ScrapeError> {
         let document = Html::parse_document(html);
         let mut courses = Vec::new();
 
-        // Find the main content area
-        let content_selector = Selector::parse("#vrtx-content, main, article, .vrtx-content, body")
-            .expect("Invalid content selector");
+        let profile = &self.profile;
 
-        let content = document.select(&content_selector).next();
+        // Find the main content area
+        let content = document.select(&profile.content).next();
         let content_element = match content {
             Some(el) => el,
             None => {
                 warn!("Could not find main content area in HTML document");
-                return Ok(courses);
+                return Err(ScrapeError::Parse(
+                    "could not find main content area in HTML document".to_string(),
+                ));
             }
         };
 
-        // Build a map of h2 IDs to their text (faculty names)
-        // The structure is: h2 with id like "det-humanistiske-fakultet" followed by table
-        let h2_selector = Selector::parse("h2[id]").expect("Invalid h2 selector");
-        let table_selector = Selector::parse("table").expect("Invalid table selector");
-
-        // Collect all h2 elements with their positions
-        let mut faculty_map: Vec<(usize, String)> = Vec::new();
-        for h2 in content_element.select(&h2_selector) {
-            if let Some(id) = h2.value().attr("id") {
-                // Skip navigation-related h2s
-                if id.contains("sporsmal") || id.contains("kontakt") {
-                    debug!(h2_id = %id, "Skipping navigation h2 element");
+        // Faculty sections are an h2 with an id like "det-humanistiske-fakultet" followed by
+        // its table. Walk headings and tables in document order so every table belongs to
+        // its nearest preceding heading, regardless of missing, extra or empty sections.
+
+        let mut faculties: Vec<String> = Vec::new();
+        let mut current_faculty: Option<String> = None;
+        let mut tables_found = 0;
+        let mut courses_by_faculty: Vec<(String, usize)> = Vec::new();
+
+        for element in content_element.select(&profile.section) {
+            if profile.heading.matches(&element) {
+                let id = element.value().attr("id").unwrap_or("");
+                // Navigation headings end the faculty listing
+                if profile.skips_heading(id) {
+                    debug!(h2_id = %id, "Skipping navigation heading");
+                    current_faculty = None;
                     continue;
                 }
-                let faculty_name = h2.text().collect::<String>().trim().to_string();
+                let faculty_name = element.text().collect::<String>().trim().to_string();
                 if !faculty_name.is_empty() {
                     debug!(
-                        faculty_index = faculty_map.len(),
+                        faculty_index = faculties.len(),
                         faculty_name = %faculty_name,
                         h2_id = %id,
                         "Found faculty section"
                     );
-                    faculty_map.push((faculty_map.len(), faculty_name));
+                    faculties.push(faculty_name.clone());
+                    current_faculty = Some(faculty_name);
                 }
+                continue;
             }
-        }
 
-        info!(
-            faculty_count = faculty_map.len(),
-            faculties = ?faculty_map.iter().map(|(_, name)| name.as_str()).collect::<Vec<_>>(),
-            "Identified faculty sections"
-        );
+            // Tables nested in a course table are part of that table
+            if self.is_nested_table(element) {
+                continue;
+            }
+            tables_found += 1;
 
-        // Now process tables - each table corresponds to a faculty in order
-        let mut table_idx = 0;
-        let mut courses_by_faculty: Vec<(String, usize)> = Vec::new();
+            let table_courses = self.parse_table(
+                element,
+                current_faculty.as_deref().unwrap_or(UNKNOWN_FACULTY),
+            )?;
+            if table_courses.is_empty() {
+                continue;
+            }
 
-        for table in content_element.select(&table_selector) {
-            let faculty = if table_idx < faculty_map.len() {
-                faculty_map[table_idx].1.clone()
-            } else {
-                "Unknown Faculty".to_string()
+            let faculty = match current_faculty {
+                Some(ref faculty) => faculty.clone(),
+                None => {
+                    warn!(
+                        table_index = tables_found - 1,
+                        courses_in_table = table_courses.len(),
+                        "Course table has no preceding faculty heading"
+                    );
+                    UNKNOWN_FACULTY.to_string()
+                }
             };
-
-            let table_courses = self.parse_table(table, &faculty);
-            if !table_courses.is_empty() {
-                debug!(
-                    faculty = %faculty,
-                    courses_in_table = table_courses.len(),
-                    table_index = table_idx,
-                    "Parsed faculty table"
-                );
-                courses_by_faculty.push((faculty.clone(), table_courses.len()));
-                courses.extend(table_courses);
-                table_idx += 1;
-            }
+            debug!(
+                faculty = %faculty,
+                courses_in_table = table_courses.len(),
+                table_index = tables_found - 1,
+                "Parsed faculty table"
+            );
+            courses_by_faculty.push((faculty, table_courses.len()));
+            courses.extend(table_courses);
         }
 
+        info!(
+            faculty_count = faculties.len(),
+            faculties = ?faculties,
+            "Identified faculty sections"
+        );
+
         info!(
             total_courses = courses.len(),
-            tables_processed = table_idx,
+            tables_processed = courses_by_faculty.len(),
             courses_by_faculty = ?courses_by_faculty,
             "HTML parsing completed"
         );
 
-        Ok(courses)
+        let structure = PageStructure {
+            tables: tables_found,
+            faculty_headings: faculties.len(),
+        };
+
+        Ok((courses, structure))
     }
 
-    fn parse_table(&self, table: ElementRef, faculty: &str) -> Vec<Course> {
+    fn parse_table(&self, table: ElementRef, faculty: &str) -> Result<Vec<Course>, ScrapeError> {
         let mut courses = Vec::new();
-        let tr_selector = Selector::parse("tr").expect("Invalid tr selector");
-        let td_selector = Selector::parse("td").expect("Invalid td selector");
-        let a_selector = Selector::parse("a").expect("Invalid a selector");
+        let profile = &self.profile;
+
+        let columns = match self.header_row(table) {
+            Some(headers) => {
+                let columns = ColumnMap::from_headers(&headers, profile)?;
+                debug!(
+                    faculty = %faculty,
+                    headers = ?headers,
+                    columns = ?columns,
+                    "Mapped table columns from header row"
+                );
+                columns
+            }
+            None => {
+                debug!(
+                    faculty = %faculty,
+                    "Table has no header row, assuming course and points in the first two columns"
+                );
+                ColumnMap::positional()
+            }
+        };
 
         let mut rows_processed = 0;
         let mut rows_skipped = 0;
         let mut parse_errors = 0;
 
-        for row in table.select(&tr_selector) {
-            let tds: Vec<_> = row.select(&td_selector).collect();
-            if tds.len() < 2 {
+        for row in table.select(&profile.row) {
+            let cells: Vec<_> = row.select(&profile.cell).collect();
+            // Header rows only contain <th> cells
+            if cells.iter().all(|cell| cell.value().name() == "th")
+                || cells.len() <= columns.last_required()
+            {
                 rows_skipped += 1;
                 continue;
             }
             rows_processed += 1;
 
-            // First td contains the link with course code and name
-            let first_td = &tds[0];
-            let link = first_td.select(&a_selector).next();
+            // The code cell holds the link with course code and (usually) the name
+            let code_cell = &cells[columns.code];
+            let link = code_cell.select(&profile.link).next();
 
-            let (url, code, name) = if let Some(a) = link {
+            let (href, code, mut name) = if let Some(a) = link {
                 let href = a.value().attr("href").unwrap_or("").to_string();
                 let text = a.text().collect::<String>();
                 let (code, name) = parse_course_text(&text);
                 (href, code, name)
             } else {
                 // No link, try to get text directly
-                let text = first_td.text().collect::<String>();
+                let text = code_cell.text().collect::<String>();
                 let (code, name) = parse_course_text(&text);
                 (String::new(), code, name)
             };
+            let code = normalize_course_code(&code);
+            let mut url = self.resolve_url(&href);
 
             if code.is_empty() {
                 debug!(
                     faculty = %faculty,
-                    raw_text = %first_td.text().collect::<String>().trim(),
+                    raw_text = %code_cell.text().collect::<String>().trim(),
                     "Skipping row with empty course code"
                 );
                 rows_skipped += 1;
                 continue;
             }
 
-            // Second td contains points
-            let points_text = tds[1].text().collect::<String>();
+            // A separate name column wins over the name in the code cell
+            if let Some(name_cell) = columns.name.and_then(|idx| cells.get(idx)) {
+                let text = cell_text(name_cell);
+                if !text.is_empty() {
+                    name = text;
+                }
+                if url.is_empty() {
+                    if let Some(a) = name_cell.select(&profile.link).next() {
+                        url = self.resolve_url(a.value().attr("href").unwrap_or(""));
+                    }
+                }
+            }
+
+            let points_text = cells[columns.points].text().collect::<String>();
             let points = parse_points(&points_text);
 
             if let Some(points) = points {
-                let course = Course::new(
+                let mut course = Course::new(
                     code.clone(),
                     name.clone(),
                     points,
                     url.clone(),
                     faculty.to_string(),
                 );
+                for (idx, key) in &columns.extra {
+                    if let Some(cell) = cells.get(*idx) {
+                        let value = cell_text(cell);
+                        if !value.is_empty() {
+                            course.attributes.insert(key.clone(), value);
+                        }
+                    }
+                }
                 debug!(
                     course_code = %code,
                     course_name = %name,
                     points = points,
                     faculty = %faculty,
                     has_url = !url.is_empty(),
+                    attributes = ?course.attributes,
                     "Parsed course"
                 );
                 courses.push(course);
@@ -242,10 +436,168 @@ impl CourseScraper {
             "Table parsing completed"
         );
 
-        courses
+        Ok(courses)
+    }
+
+    /// Absolute form of a course link, without fragment; empty links stay empty
+    fn resolve_url(&self, href: &str) -> String {
+        let href = href.trim();
+        let Some(ref page_url) = self.page_url else {
+            return href.to_string();
+        };
+        if href.is_empty() {
+            return String::new();
+        }
+
+        match page_url.join(href) {
+            Ok(mut url) => {
+                url.set_fragment(None);
+                url.to_string()
+            }
+            Err(e) => {
+                debug!(href = %href, error = %e, "Could not resolve course link, keeping it as written");
+                href.to_string()
+            }
+        }
+    }
+
+    /// Whether a course table sits inside another course table
+    fn is_nested_table(&self, table: ElementRef) -> bool {
+        table
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .any(|ancestor| self.profile.table.matches(&ancestor))
+    }
+
+    /// Normalized header texts of the table's first row, if it consists of `<th>` cells only
+    fn header_row(&self, table: ElementRef) -> Option<Vec<String>> {
+        let first_row = table.select(&self.profile.row).next()?;
+        let cells: Vec<_> = first_row.select(&self.profile.cell).collect();
+        if cells.is_empty() || cells.iter().any(|cell| cell.value().name() != "th") {
+            return None;
+        }
+
+        Some(cells.iter().map(|cell| normalize_header(&cell_text(cell))).collect())
+    }
+}
+
+/// Which cell holds which course field, derived from a table's header row
+#[derive(Debug, Clone, PartialEq)]
+struct ColumnMap {
+    code: usize,
+    name: Option<usize>,
+    points: usize,
+    /// Unrecognised columns and their normalized header text, captured into `Course::attributes`
+    extra: Vec<(usize, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Column {
+    Code,
+    Name,
+    Points,
+}
+
+impl ColumnMap {
+    /// Layout of header-less tables: course link first, then points
+    fn positional() -> Self {
+        Self {
+            code: 0,
+            name: None,
+            points: 1,
+            extra: Vec::new(),
+        }
+    }
+
+    fn from_headers(headers: &[String], profile: &CompiledProfile) -> Result<Self, ScrapeError> {
+        let mut code = None;
+        let mut name = None;
+        let mut points = None;
+        let mut extra = Vec::new();
+
+        for (idx, header) in headers.iter().enumerate() {
+            let slot = match classify_header(header, profile) {
+                Some(Column::Code) => &mut code,
+                Some(Column::Name) => &mut name,
+                Some(Column::Points) => &mut points,
+                None => {
+                    let key = if header.is_empty() {
+                        format!("column {}", idx + 1)
+                    } else {
+                        header.clone()
+                    };
+                    extra.push((idx, key));
+                    continue;
+                }
+            };
+            // Keep the first match; a repeated column is kept as an attribute
+            match slot {
+                Some(_) => extra.push((idx, header.clone())),
+                None => *slot = Some(idx),
+            }
+        }
+
+        match (code, points) {
+            (Some(code), Some(points)) => Ok(Self {
+                code,
+                name,
+                points,
+                extra,
+            }),
+            _ => Err(ScrapeError::Layout(format!(
+                "expected course and credits columns, found headers {:?}",
+                headers
+            ))),
+        }
+    }
+
+    /// Highest column index a row needs to be parsed
+    fn last_required(&self) -> usize {
+        self.code.max(self.points)
     }
 }
 
+/// Recognise a normalized header by the profile's column names
+fn classify_header(header: &str, profile: &CompiledProfile) -> Option<Column> {
+    let columns = &profile.columns;
+    let is = |names: &[String]| names.iter().any(|name| name == header);
+    if is(&columns.code) {
+        Some(Column::Code)
+    } else if is(&columns.name) {
+        Some(Column::Name)
+    } else if is(&columns.points) {
+        Some(Column::Points)
+    } else {
+        None
+    }
+}
+
+/// Lowercase, collapse whitespace and drop a trailing colon
+fn normalize_header(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches(':')
+        .to_lowercase()
+}
+
+/// Trimmed text content of a cell with inner whitespace collapsed
+fn cell_text(cell: &ElementRef) -> String {
+    cell.text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Hex-encoded SHA-256 of a page body, used to identify snapshots
+pub fn content_hash(body: &str) -> String {
+    Sha256::digest(body.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
 /// Parse course code and name from link text
 /// Format: "CODE - Course Name" or just "CODE"
 fn parse_course_text(text: &str) -> (String, String) {
@@ -268,6 +620,199 @@ fn parse_points(text: &str) -> Option<f32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::source::FileSource;
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/ledige-plasser.html");
+
+    fn fixture_scraper() -> CourseScraper {
+        CourseScraper::new("test", Box::new(FileSource::new("unused.html".into())))
+    }
+
+    #[test]
+    fn test_parse_fixture_page() {
+        let (courses, structure) = fixture_scraper().parse_courses(FIXTURE).unwrap();
+        assert_eq!(courses.len(), 8);
+        assert_eq!(
+            structure,
+            PageStructure {
+                tables: 3,
+                faculty_headings: 3
+            }
+        );
+
+        let hfleser = courses.iter().find(|c| c.code == "HFLESER1031").unwrap();
+        assert_eq!(hfleser.points, 2.5);
+        assert_eq!(hfleser.faculty, "Det humanistiske fakultet");
+
+        let econ = courses.iter().find(|c| c.code == "ECON1100").unwrap();
+        assert_eq!(econ.faculty, "Det samfunnsvitenskapelige fakultet");
+    }
+
+    /// Faculty of every course in a parsed fixture, keyed by course code
+    fn faculties_by_code(html: &str) -> std::collections::HashMap<String, String> {
+        let (courses, _) = fixture_scraper().parse_courses(html).unwrap();
+        courses.into_iter().map(|c| (c.code, c.faculty)).collect()
+    }
+
+    #[test]
+    fn test_faculty_from_nearest_heading_with_missing_heading() {
+        let html = include_str!("../tests/fixtures/ledige-plasser-missing-heading.html");
+        let faculties = faculties_by_code(html);
+        assert_eq!(faculties.len(), 8);
+
+        // Only the section that lost its heading is affected
+        assert_eq!(faculties["HFLESER1031"], UNKNOWN_FACULTY);
+        assert_eq!(faculties["IN1000"], "Det matematisk-naturvitenskapelige fakultet");
+        assert_eq!(faculties["ECON1100"], "Det samfunnsvitenskapelige fakultet");
+    }
+
+    #[test]
+    fn test_faculty_from_nearest_heading_with_extra_headings() {
+        let html = include_str!("../tests/fixtures/ledige-plasser-extra-headings.html");
+        let (_, structure) = fixture_scraper().parse_courses(html).unwrap();
+        assert_eq!(
+            structure,
+            PageStructure {
+                tables: 4,
+                faculty_headings: 5
+            }
+        );
+
+        // A heading without a table and an empty table don't shift later sections
+        let faculties = faculties_by_code(html);
+        assert_eq!(faculties.len(), 8);
+        assert_eq!(faculties["FIL1000"], "Det humanistiske fakultet");
+        assert_eq!(faculties["MAT1100"], "Det matematisk-naturvitenskapelige fakultet");
+        assert_eq!(faculties["PSY2301"], "Det samfunnsvitenskapelige fakultet");
+    }
+
+    /// Wrap a single faculty table in the page's content area
+    fn faculty_page(table: &str) -> String {
+        format!(
+            r#"<html><body><div id="vrtx-content">
+            <h2 id="mn">Det matematisk-naturvitenskapelige fakultet</h2>{}
+            </div></body></html>"#,
+            table
+        )
+    }
+
+    #[test]
+    fn test_parse_reordered_columns_into_attributes() {
+        let html = faculty_page(
+            r#"<table>
+              <tr><th>Studiepoeng</th><th>Ledige plasser</th><th>Emne</th></tr>
+              <tr><td>5</td><td>12</td><td><a href="/in2010">IN2010 - Algoritmer</a></td></tr>
+            </table>"#,
+        );
+        let (courses, _) = fixture_scraper().parse_courses(&html).unwrap();
+        assert_eq!(courses.len(), 1);
+        assert_eq!(courses[0].code, "IN2010");
+        assert_eq!(courses[0].name, "Algoritmer");
+        assert_eq!(courses[0].points, 5.0);
+        assert_eq!(courses[0].url, "/in2010");
+        assert_eq!(courses[0].attributes.get("ledige plasser").map(String::as_str), Some("12"));
+    }
+
+    #[test]
+    fn test_parse_english_headers_and_headerless_tables() {
+        let html = faculty_page(
+            r#"<table>
+              <thead><tr><th>Course code</th><th>Course name</th><th>Credits</th></tr></thead>
+              <tbody><tr><td>IN1010</td><td><a href="/in1010">Object-oriented programming</a></td><td>10</td></tr></tbody>
+            </table>"#,
+        );
+        let (courses, _) = fixture_scraper().parse_courses(&html).unwrap();
+        assert_eq!(courses[0].code, "IN1010");
+        assert_eq!(courses[0].name, "Object-oriented programming");
+        assert_eq!(courses[0].url, "/in1010");
+        assert!(courses[0].attributes.is_empty());
+
+        // Without a header row the original code-then-points layout is assumed
+        let html = faculty_page(r#"<table><tr><td>IN1020 - Maskinvare</td><td>2,5</td></tr></table>"#);
+        let (courses, _) = fixture_scraper().parse_courses(&html).unwrap();
+        assert_eq!(courses[0].code, "IN1020");
+        assert_eq!(courses[0].points, 2.5);
+    }
+
+    #[test]
+    fn test_unrecognised_layout_is_reported() {
+        let html = faculty_page(
+            r#"<table>
+              <tr><th>Dato</th><th>Hendelse</th></tr>
+              <tr><td>IN1000</td><td>10</td></tr>
+            </table>"#,
+        );
+        let err = fixture_scraper().parse_courses(&html).unwrap_err();
+        assert_eq!(err.kind(), "layout");
+    }
+
+    #[test]
+    fn test_custom_profile_parses_alternative_markup() {
+        let profile: crate::profile::ParserProfile = toml::from_str(
+            r#"
+            content_selector = "section.listing"
+            heading_selector = "h3.faculty"
+            table_selector = "table.courses"
+            skip_heading_ids = ["om-siden"]
+
+            [columns]
+            points = ["Vekt"]
+            "#,
+        )
+        .unwrap();
+        let scraper = fixture_scraper().with_profile(profile.compile().unwrap());
+
+        let html = r#"<html><body><section class="listing">
+            <h3 class="faculty">Det teologiske fakultet</h3>
+            <table class="courses">
+              <tr><th>Emne</th><th>Vekt</th></tr>
+              <tr><td><a href="/teol1010">TEOL1010 - Bibelvitenskap</a></td><td>10</td></tr>
+            </table>
+            <table class="layout"><tr><td>IGNORED1000</td><td>10</td></tr></table>
+            <h3 class="faculty" id="om-siden">Om siden</h3>
+            </section></body></html>"#;
+        let (courses, structure) = scraper.parse_courses(html).unwrap();
+        assert_eq!(
+            structure,
+            PageStructure {
+                tables: 1,
+                faculty_headings: 1
+            }
+        );
+        assert_eq!(courses.len(), 1);
+        assert_eq!(courses[0].code, "TEOL1010");
+        assert_eq!(courses[0].faculty, "Det teologiske fakultet");
+
+        // The built-in profile doesn't recognise the custom credits column
+        let err = fixture_scraper().parse_courses(html).unwrap_err();
+        assert_eq!(err.kind(), "layout");
+    }
+
+    #[test]
+    fn test_links_resolved_and_codes_normalized() {
+        let scraper = fixture_scraper()
+            .with_page_url(Url::parse("https://www.uio.no/studier/emner/ledige-plasser/").unwrap());
+        let html = faculty_page(
+            r#"<table>
+              <tr><th>Emne</th><th>Studiepoeng</th></tr>
+              <tr><td><a href="/studier/emner/matnat/ifi/IN1000/index.html#a">in 1000 - Programmering</a></td><td>10</td></tr>
+              <tr><td><a href="../../emner/hf/ifikk/EXPHIL03/"> exphil03 </a></td><td>10</td></tr>
+              <tr><td><a href="https://example.org/ECON1100">ECON1100</a></td><td>10</td></tr>
+              <tr><td>STK1000</td><td>10</td></tr>
+            </table>"#,
+        );
+        let (courses, _) = scraper.parse_courses(&html).unwrap();
+        let urls: Vec<_> = courses.iter().map(|c| (c.code.as_str(), c.url.as_str())).collect();
+        assert_eq!(
+            urls,
+            vec![
+                ("IN1000", "https://www.uio.no/studier/emner/matnat/ifi/IN1000/index.html"),
+                ("EXPHIL03", "https://www.uio.no/studier/emner/hf/ifikk/EXPHIL03/"),
+                ("ECON1100", "https://example.org/ECON1100"),
+                ("STK1000", ""),
+            ]
+        );
+    }
 
     #[test]
     fn test_parse_course_text() {
@@ -280,6 +825,63 @@ mod tests {
         assert_eq!(name, "");
     }
 
+    /// A page that fails to parse must be fetched in full again, not answered with a 304
+    #[tokio::test]
+    async fn test_parse_failure_is_not_reported_unchanged_after_304() {
+        use crate::source::{HttpSettings, HttpSource, RetryPolicy};
+        use axum::http::{HeaderMap, StatusCode};
+        use axum::{routing::get, Router};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // First the fixture (ETag v1), then a page with an unknown table layout (ETag v2)
+        // that a 304 confirms
+        static HITS: AtomicUsize = AtomicUsize::new(0);
+        async fn page(headers: HeaderMap) -> (StatusCode, HeaderMap, &'static str) {
+            let mut response_headers = HeaderMap::new();
+            if HITS.fetch_add(1, Ordering::SeqCst) == 0 {
+                response_headers.insert("etag", "\"v1\"".parse().unwrap());
+                return (StatusCode::OK, response_headers, FIXTURE);
+            }
+            if headers.get("if-none-match").and_then(|v| v.to_str().ok()) == Some("\"v2\"") {
+                return (StatusCode::NOT_MODIFIED, response_headers, "");
+            }
+            response_headers.insert("etag", "\"v2\"".parse().unwrap());
+            let broken = r#"<html><body><div id="vrtx-content"><h2 id="mn">MatNat</h2>
+                <table><tr><th>Dato</th><th>Hendelse</th></tr><tr><td>IN1000</td><td>10</td></tr></table>
+                </div></body></html>"#;
+            (StatusCode::OK, response_headers, broken)
+        }
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, Router::new().route("/", get(page))).await.unwrap();
+        });
+
+        let source = HttpSource::new(format!("http://{}/", addr), RetryPolicy::default(), &HttpSettings::default())
+            .unwrap();
+        let scraper = CourseScraper::new("test", Box::new(source));
+
+        let ScrapeOutcome::Changed(good) = scraper.fetch_courses().await.unwrap() else {
+            panic!("expected the fixture page");
+        };
+        scraper.mark_processed(&good);
+
+        // The broken page fails, and keeps failing instead of turning into "unchanged"
+        assert!(scraper.fetch_courses().await.is_err());
+        let err = scraper.fetch_courses().await.unwrap_err();
+        assert_eq!(ScrapeError::find(&err).unwrap().kind(), "layout");
+    }
+
+    #[test]
+    fn test_content_hash() {
+        assert_eq!(
+            content_hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_ne!(content_hash("abc"), content_hash("abd"));
+    }
+
     #[test]
     fn test_parse_points() {
         assert_eq!(parse_points("10"), Some(10.0));
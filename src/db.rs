@@ -1,13 +1,25 @@
 use anyhow::{Context, Result};
 use chrono::Utc;
 use libsql::{Builder, Connection};
+use reqwest::Url;
 use std::collections::HashMap;
 use std::path::Path;
 use tracing::{debug, info, instrument};
 
-use crate::models::Course;
+use crate::config::DEFAULT_URL;
+use crate::models::{Course, CourseChange, CourseDetails, Reappearance, ScrapeDiff};
 
-const SCHEMA_VERSION: i32 = 2;
+const SCHEMA_VERSION: i32 = 16;
+
+/// Columns selected for run log display, shared by list and detail queries
+const RUN_LOG_COLUMNS: &str = "id, timestamp, total_courses_fetched, raw_added_count, raw_removed_count,
+    filtered_added_count, filtered_removed_count, filter_used,
+    notification_sent, is_first_run, added_courses, removed_courses, duration_ms,
+    content_hash,
+    EXISTS(SELECT 1 FROM page_snapshots s WHERE s.content_hash = run_log.content_hash),
+    status, error_kind, error_message, error_transient, source,
+    raw_changed_count, filtered_changed_count, changed_courses, reappeared_courses,
+    excluded_courses";
 
 pub struct Database {
     conn: Connection,
@@ -57,7 +69,7 @@ impl Database {
 
         db.run_migrations().await?;
 
-        let count = db.get_course_count().await.unwrap_or(0);
+        let count = db.total_course_count().await.unwrap_or(0);
         info!(
             db_path = %path_str,
             db_type = "sqlite",
@@ -94,7 +106,7 @@ impl Database {
 
         db.run_migrations().await?;
 
-        let count = db.get_course_count().await.unwrap_or(0);
+        let count = db.total_course_count().await.unwrap_or(0);
         info!(
             db_url = %url,
             db_type = "turso",
@@ -209,6 +221,76 @@ impl Database {
             self.migrate_v2().await?;
         }
 
+        if current_version < 3 {
+            info!(migration = 3, "Running migration: add page snapshots");
+            self.migrate_v3().await?;
+        }
+
+        if current_version < 4 {
+            info!(migration = 4, "Running migration: add run status");
+            self.migrate_v4().await?;
+        }
+
+        if current_version < 5 {
+            info!(migration = 5, "Running migration: add run errors");
+            self.migrate_v5().await?;
+        }
+
+        if current_version < 6 {
+            info!(migration = 6, "Running migration: add course attributes");
+            self.migrate_v6().await?;
+        }
+
+        if current_version < 7 {
+            info!(migration = 7, "Running migration: add course details cache");
+            self.migrate_v7().await?;
+        }
+
+        if current_version < 8 {
+            info!(migration = 8, "Running migration: tag courses and runs by source");
+            self.migrate_v8().await?;
+        }
+
+        if current_version < 9 {
+            info!(migration = 9, "Running migration: record modified courses per run");
+            self.migrate_v9().await?;
+        }
+
+        if current_version < 10 {
+            info!(migration = 10, "Running migration: add pending changes for flap suppression");
+            self.migrate_v10().await?;
+        }
+
+        if current_version < 11 {
+            info!(migration = 11, "Running migration: keep removed courses with availability history");
+            self.migrate_v11().await?;
+        }
+
+        if current_version < 12 {
+            info!(migration = 12, "Running migration: add watchlist");
+            self.migrate_v12().await?;
+        }
+
+        if current_version < 13 {
+            info!(migration = 13, "Running migration: add subscribers");
+            self.migrate_v13().await?;
+        }
+
+        if current_version < 14 {
+            info!(migration = 14, "Running migration: add excluded courses to run log");
+            self.migrate_v14().await?;
+        }
+
+        if current_version < 15 {
+            info!(migration = 15, "Running migration: hold subscriber changes during quiet hours");
+            self.migrate_v15().await?;
+        }
+
+        if current_version < 16 {
+            info!(migration = 16, "Running migration: resolve relative course links");
+            self.migrate_v16().await?;
+        }
+
         info!(
             from_version = current_version,
             to_version = SCHEMA_VERSION,
@@ -315,10 +397,287 @@ impl Database {
         Ok(())
     }
 
-    pub async fn get_all_courses(&self) -> Result<HashMap<String, Course>> {
+    /// Migration v3: Add content hashes to run_log and a table of raw page snapshots
+    async fn migrate_v3(&mut self) -> Result<()> {
+        // Raw HTML keyed by SHA-256; runs link here through run_log.content_hash
+        self.conn
+            .execute_batch(
+                "ALTER TABLE run_log ADD COLUMN content_hash TEXT;
+                 CREATE TABLE IF NOT EXISTS page_snapshots (
+                    content_hash TEXT PRIMARY KEY,
+                    html TEXT NOT NULL,
+                    size_bytes INTEGER NOT NULL,
+                    first_seen_at TEXT NOT NULL,
+                    last_seen_at TEXT NOT NULL
+                 );
+                 CREATE INDEX IF NOT EXISTS idx_page_snapshots_last_seen ON page_snapshots(last_seen_at);
+                 INSERT INTO schema_version (version) VALUES (3);",
+            )
+            .await?;
+
+        debug!("Migration v3 completed: page snapshots added");
+        Ok(())
+    }
+
+    /// Migration v4: Record the outcome of each run (existing rows were full syncs)
+    async fn migrate_v4(&mut self) -> Result<()> {
+        self.conn
+            .execute_batch(
+                "ALTER TABLE run_log ADD COLUMN status TEXT NOT NULL DEFAULT 'ok';
+                 INSERT INTO schema_version (version) VALUES (4);",
+            )
+            .await?;
+
+        debug!("Migration v4 completed: run status added");
+        Ok(())
+    }
+
+    /// Migration v5: Record classified errors for failed runs
+    async fn migrate_v5(&mut self) -> Result<()> {
+        self.conn
+            .execute_batch(
+                "ALTER TABLE run_log ADD COLUMN error_kind TEXT;
+                 ALTER TABLE run_log ADD COLUMN error_message TEXT;
+                 ALTER TABLE run_log ADD COLUMN error_transient INTEGER;
+                 INSERT INTO schema_version (version) VALUES (5);",
+            )
+            .await?;
+
+        debug!("Migration v5 completed: run errors added");
+        Ok(())
+    }
+
+    /// Migration v6: Store extra table columns as a JSON object per course
+    async fn migrate_v6(&mut self) -> Result<()> {
+        self.conn
+            .execute_batch(
+                "ALTER TABLE courses ADD COLUMN attributes TEXT NOT NULL DEFAULT '{}';
+                 INSERT INTO schema_version (version) VALUES (6);",
+            )
+            .await?;
+
+        debug!("Migration v6 completed: course attributes added");
+        Ok(())
+    }
+
+    /// Migration v7: Cache of course detail pages, kept independently of availability
+    async fn migrate_v7(&mut self) -> Result<()> {
+        self.conn
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS course_details (
+                    source TEXT NOT NULL DEFAULT 'default',
+                    code TEXT NOT NULL,
+                    details TEXT NOT NULL,
+                    fetched_at TEXT NOT NULL,
+                    PRIMARY KEY (source, code)
+                 );
+                 INSERT INTO schema_version (version) VALUES (7);",
+            )
+            .await?;
+
+        debug!("Migration v7 completed: course details cache added");
+        Ok(())
+    }
+
+    /// Migration v8: Key courses by (source, code) and tag run and change logs with their source.
+    /// Existing rows belong to the implicit 'default' source.
+    async fn migrate_v8(&mut self) -> Result<()> {
+        self.conn
+            .execute_batch(
+                "BEGIN;
+                 CREATE TABLE courses_v8 (
+                    source TEXT NOT NULL DEFAULT 'default',
+                    code TEXT NOT NULL,
+                    name TEXT NOT NULL,
+                    points REAL NOT NULL,
+                    url TEXT NOT NULL,
+                    faculty TEXT NOT NULL,
+                    attributes TEXT NOT NULL DEFAULT '{}',
+                    first_seen_at TEXT NOT NULL,
+                    last_seen_at TEXT NOT NULL,
+                    PRIMARY KEY (source, code)
+                 );
+                 INSERT INTO courses_v8 (code, name, points, url, faculty, attributes, first_seen_at, last_seen_at)
+                    SELECT code, name, points, url, faculty, attributes, first_seen_at, last_seen_at FROM courses;
+                 DROP TABLE courses;
+                 ALTER TABLE courses_v8 RENAME TO courses;
+                 ALTER TABLE run_log ADD COLUMN source TEXT NOT NULL DEFAULT 'default';
+                 ALTER TABLE change_log ADD COLUMN source TEXT NOT NULL DEFAULT 'default';
+                 CREATE INDEX IF NOT EXISTS idx_run_log_source ON run_log(source);
+                 INSERT INTO schema_version (version) VALUES (8);
+                 COMMIT;",
+            )
+            .await?;
+
+        debug!("Migration v8 completed: courses and runs tagged by source");
+        Ok(())
+    }
+
+    /// Migration v9: Track courses whose points, name, faculty or URL changed
+    async fn migrate_v9(&mut self) -> Result<()> {
+        self.conn
+            .execute_batch(
+                "ALTER TABLE run_log ADD COLUMN raw_changed_count INTEGER NOT NULL DEFAULT 0;
+                 ALTER TABLE run_log ADD COLUMN filtered_changed_count INTEGER NOT NULL DEFAULT 0;
+                 ALTER TABLE run_log ADD COLUMN changed_courses TEXT NOT NULL DEFAULT '[]';
+                 INSERT INTO schema_version (version) VALUES (9);",
+            )
+            .await?;
+
+        debug!("Migration v9 completed: modified courses added to run log");
+        Ok(())
+    }
+
+    /// Migration v10: Additions and removals waiting to be confirmed by later cycles
+    async fn migrate_v10(&mut self) -> Result<()> {
+        self.conn
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS pending_changes (
+                    source TEXT NOT NULL,
+                    code TEXT NOT NULL,
+                    kind TEXT NOT NULL,
+                    since TEXT NOT NULL,
+                    cycles INTEGER NOT NULL,
+                    PRIMARY KEY (source, code)
+                 );
+                 INSERT INTO schema_version (version) VALUES (10);",
+            )
+            .await?;
+
+        debug!("Migration v10 completed: pending changes table added");
+        Ok(())
+    }
+
+    /// Migration v11: Keep removed courses as unavailable rows so returns can be recognized
+    async fn migrate_v11(&mut self) -> Result<()> {
+        self.conn
+            .execute_batch(
+                "ALTER TABLE courses ADD COLUMN available INTEGER NOT NULL DEFAULT 1;
+                 ALTER TABLE courses ADD COLUMN removed_at TEXT;
+                 ALTER TABLE courses ADD COLUMN appearances INTEGER NOT NULL DEFAULT 1;
+                 ALTER TABLE courses ADD COLUMN last_absence_seconds INTEGER;
+                 ALTER TABLE run_log ADD COLUMN reappeared_courses TEXT NOT NULL DEFAULT '[]';
+                 INSERT INTO schema_version (version) VALUES (11);",
+            )
+            .await?;
+
+        debug!("Migration v11 completed: course availability history added");
+        Ok(())
+    }
+
+    /// Migration v12: Watchlist patterns managed from the web UI
+    async fn migrate_v12(&mut self) -> Result<()> {
+        self.conn
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS watchlist (
+                    pattern TEXT PRIMARY KEY,
+                    added_at TEXT NOT NULL
+                 );
+                 INSERT INTO schema_version (version) VALUES (12);",
+            )
+            .await?;
+
+        debug!("Migration v12 completed: watchlist table added");
+        Ok(())
+    }
+
+    /// Migration v13: Subscribers with their own filter, channels, language and quiet hours
+    async fn migrate_v13(&mut self) -> Result<()> {
+        self.conn
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS subscribers (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    name TEXT NOT NULL UNIQUE,
+                    filter TEXT,
+                    email TEXT,
+                    phone TEXT,
+                    language TEXT NOT NULL DEFAULT 'no',
+                    quiet_hours TEXT,
+                    created_at TEXT NOT NULL
+                 );
+                 INSERT INTO schema_version (version) VALUES (13);",
+            )
+            .await?;
+
+        debug!("Migration v13 completed: subscribers table added");
+        Ok(())
+    }
+
+    /// Migration v14: Codes of changes suppressed by exclusion rules, per run
+    async fn migrate_v14(&mut self) -> Result<()> {
+        self.conn
+            .execute_batch(
+                "ALTER TABLE run_log ADD COLUMN excluded_courses TEXT NOT NULL DEFAULT '[]';
+                 INSERT INTO schema_version (version) VALUES (14);",
+            )
+            .await?;
+
+        debug!("Migration v14 completed: excluded courses added to run log");
+        Ok(())
+    }
+
+    /// Migration v15: Changes held back from subscribers during their quiet hours
+    async fn migrate_v15(&mut self) -> Result<()> {
+        self.conn
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS held_changes (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    subscriber_id INTEGER NOT NULL,
+                    source TEXT NOT NULL,
+                    diff TEXT NOT NULL,
+                    held_at TEXT NOT NULL
+                 );
+                 CREATE INDEX IF NOT EXISTS idx_held_changes_subscriber ON held_changes(subscriber_id, source);
+                 INSERT INTO schema_version (version) VALUES (15);",
+            )
+            .await?;
+
+        debug!("Migration v15 completed: held changes table added");
+        Ok(())
+    }
+
+    /// Migration v16: Resolve course links stored as written on the page (before v8 every
+    /// course came from the UiO page), so comparing them with resolved links finds no change
+    async fn migrate_v16(&mut self) -> Result<()> {
+        let page_url = Url::parse(DEFAULT_URL)?;
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT code, url FROM courses WHERE source = 'default' AND url != '' AND url NOT LIKE '%://%'",
+                (),
+            )
+            .await?;
+
+        let mut batch_sql = String::from("BEGIN;");
+        while let Some(row) = rows.next().await? {
+            let code = row.get::<String>(0)?;
+            let Ok(mut url) = page_url.join(row.get::<String>(1)?.trim()) else {
+                continue;
+            };
+            url.set_fragment(None);
+            batch_sql.push_str(&format!(
+                "UPDATE courses SET url = '{}' WHERE source = 'default' AND code = '{}';",
+                escape_sql(url.as_str()),
+                escape_sql(&code)
+            ));
+        }
+        batch_sql.push_str("INSERT INTO schema_version (version) VALUES (16); COMMIT;");
+        self.conn.execute_batch(&batch_sql).await?;
+
+        debug!("Migration v16 completed: relative course links resolved");
+        Ok(())
+    }
+
+    /// All stored courses of one source, keyed by code
+    pub async fn get_all_courses(&self, source: &str) -> Result<HashMap<String, Course>> {
         let mut rows = self
             .conn
-            .query("SELECT code, name, points, url, faculty FROM courses", ())
+            .query(
+                "SELECT c.code, c.name, c.points, c.url, c.faculty, c.attributes, d.details
+                 FROM courses c LEFT JOIN course_details d ON d.source = c.source AND d.code = c.code
+                 WHERE c.source = ? AND c.available = 1",
+                libsql::params![source],
+            )
             .await?;
 
         let mut courses = HashMap::new();
@@ -329,6 +688,8 @@ impl Database {
                 points: row.get::<f64>(2)? as f32,
                 url: row.get::<String>(3)?,
                 faculty: row.get::<String>(4)?,
+                attributes: serde_json::from_str(&row.get::<String>(5)?).unwrap_or_default(),
+                details: parse_details_json(row.get::<Option<String>>(6)?),
             };
             courses.insert(course.code.clone(), course);
         }
@@ -336,8 +697,102 @@ impl Database {
         Ok(courses)
     }
 
-    pub async fn get_course_count(&self) -> Result<usize> {
-        let mut rows = self.conn.query("SELECT COUNT(*) FROM courses", ()).await?;
+    /// Additions and removals of one source still waiting for confirmation, keyed by code
+    pub async fn get_pending_changes(&self, source: &str) -> Result<HashMap<String, PendingChange>> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT code, kind, since, cycles FROM pending_changes WHERE source = ?",
+                libsql::params![source],
+            )
+            .await?;
+
+        let mut pending = HashMap::new();
+        while let Some(row) = rows.next().await? {
+            let kind = match row.get::<String>(1)?.as_str() {
+                "added" => PendingKind::Added,
+                _ => PendingKind::Removed,
+            };
+            let since = chrono::DateTime::parse_from_rfc3339(&row.get::<String>(2)?)
+                .map(|t| t.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now());
+            pending.insert(
+                row.get::<String>(0)?,
+                PendingChange {
+                    kind,
+                    since,
+                    cycles: row.get::<i64>(3)? as u32,
+                },
+            );
+        }
+
+        Ok(pending)
+    }
+
+    pub async fn get_course_count(&self, source: &str) -> Result<usize> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT COUNT(*) FROM courses WHERE source = ? AND available = 1",
+                libsql::params![source],
+            )
+            .await?;
+        let count = rows
+            .next()
+            .await?
+            .map(|row| row.get::<i64>(0).unwrap_or(0))
+            .unwrap_or(0);
+        Ok(count as usize)
+    }
+
+    /// Changes logged for a source since the given time
+    pub async fn count_changes_since(&self, source: &str, since: chrono::DateTime<Utc>) -> Result<usize> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT COUNT(*) FROM change_log WHERE source = ? AND timestamp >= ?",
+                libsql::params![source, since.to_rfc3339()],
+            )
+            .await?;
+        let count = rows
+            .next()
+            .await?
+            .map(|row| row.get::<i64>(0).unwrap_or(0))
+            .unwrap_or(0);
+        Ok(count as usize)
+    }
+
+    /// How many of the latest runs of a source were rejected for dropping to this
+    /// course count, counted back to the first run that was not (failed fetches are skipped)
+    pub async fn count_repeated_drops(&self, source: &str, course_count: usize) -> Result<usize> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT status, error_kind, total_courses_fetched FROM run_log
+                 WHERE source = ? AND status != 'failed' ORDER BY id DESC",
+                libsql::params![source],
+            )
+            .await?;
+
+        let mut count = 0;
+        while let Some(row) = rows.next().await? {
+            let status = RunStatus::from_db(&row.get::<String>(0)?);
+            let kind = row.get::<Option<String>>(1)?;
+            let fetched = row.get::<i64>(2)? as usize;
+            if status != RunStatus::Anomalous || kind.as_deref() != Some("course_drop") || fetched != course_count {
+                break;
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Course count across all sources
+    async fn total_course_count(&self) -> Result<usize> {
+        let mut rows = self
+            .conn
+            .query("SELECT COUNT(*) FROM courses WHERE available = 1", ())
+            .await?;
         let count = rows
             .next()
             .await?
@@ -346,8 +801,42 @@ impl Database {
         Ok(count as usize)
     }
 
-    pub async fn is_first_run(&self) -> Result<bool> {
-        Ok(self.get_course_count().await? == 0)
+    /// Whether nothing has been stored for this source yet (removed courses count as stored)
+    pub async fn is_first_run(&self, source: &str) -> Result<bool> {
+        let mut rows = self
+            .conn
+            .query("SELECT 1 FROM courses WHERE source = ? LIMIT 1", libsql::params![source])
+            .await?;
+        Ok(rows.next().await?.is_none())
+    }
+
+    /// Courses of one source that are no longer available, keyed by code
+    pub async fn get_removed_courses(&self, source: &str) -> Result<HashMap<String, RemovedCourse>> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT code, removed_at, appearances FROM courses WHERE source = ? AND available = 0",
+                libsql::params![source],
+            )
+            .await?;
+
+        let mut removed = HashMap::new();
+        while let Some(row) = rows.next().await? {
+            let removed_at = row
+                .get::<Option<String>>(1)?
+                .and_then(|ts| chrono::DateTime::parse_from_rfc3339(&ts).ok())
+                .map(|t| t.with_timezone(&Utc))
+                .unwrap_or_else(Utc::now);
+            removed.insert(
+                row.get::<String>(0)?,
+                RemovedCourse {
+                    removed_at,
+                    appearances: row.get::<i64>(2)? as u32,
+                },
+            );
+        }
+
+        Ok(removed)
     }
 
     /// Log a complete run with all delta information
@@ -362,6 +851,9 @@ impl Database {
         // Serialize course code lists as JSON
         let added_json = serde_json::to_string(&run_log.added_courses)?;
         let removed_json = serde_json::to_string(&run_log.removed_courses)?;
+        let changed_json = serde_json::to_string(&run_log.changed_courses)?;
+        let reappeared_json = serde_json::to_string(&run_log.reappeared_courses)?;
+        let excluded_json = serde_json::to_string(&run_log.excluded_courses)?;
 
         self.conn
             .execute(
@@ -370,8 +862,11 @@ impl Database {
                     raw_added_count, raw_removed_count,
                     filtered_added_count, filtered_removed_count,
                     filter_used, notification_sent, is_first_run,
-                    added_courses, removed_courses, duration_ms
-                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                    added_courses, removed_courses, duration_ms, content_hash, status,
+                    error_kind, error_message, error_transient, source,
+                    raw_changed_count, filtered_changed_count, changed_courses, reappeared_courses,
+                    excluded_courses
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
                 libsql::params![
                     now.clone(),
                     run_log.total_courses_fetched as i64,
@@ -385,6 +880,17 @@ impl Database {
                     added_json,
                     removed_json,
                     run_log.duration_ms as i64,
+                    run_log.content_hash.clone(),
+                    run_log.status.as_str(),
+                    run_log.error.as_ref().map(|e| e.kind.clone()),
+                    run_log.error.as_ref().map(|e| e.message.clone()),
+                    run_log.error.as_ref().map(|e| e.transient as i64),
+                    run_log.source.clone(),
+                    run_log.raw_changed_count as i64,
+                    run_log.filtered_changed_count as i64,
+                    changed_json,
+                    reappeared_json,
+                    excluded_json,
                 ],
             )
             .await?;
@@ -399,17 +905,24 @@ impl Database {
 
         info!(
             run_id = run_id,
+            source = %run_log.source,
+            status = run_log.status.as_str(),
             timestamp = %now,
             total_courses_fetched = run_log.total_courses_fetched,
             raw_added = run_log.raw_added_count,
             raw_removed = run_log.raw_removed_count,
             filtered_added = run_log.filtered_added_count,
             filtered_removed = run_log.filtered_removed_count,
+            raw_changed = run_log.raw_changed_count,
+            filtered_changed = run_log.filtered_changed_count,
             filter = %run_log.filter_used,
             notification_sent = run_log.notification_sent,
             is_first_run = run_log.is_first_run,
             added_codes = ?run_log.added_courses.iter().map(|c| &c.code).collect::<Vec<_>>(),
             removed_codes = ?run_log.removed_courses.iter().map(|c| &c.code).collect::<Vec<_>>(),
+            changed_codes = ?run_log.changed_courses.iter().map(|c| c.code()).collect::<Vec<_>>(),
+            reappeared_codes = ?run_log.reappeared_courses.iter().map(|r| &r.course.code).collect::<Vec<_>>(),
+            excluded_codes = ?run_log.excluded_courses,
             duration_ms = run_log.duration_ms,
             "Run logged to database"
         );
@@ -422,8 +935,11 @@ impl Database {
         let mut rows = self
             .conn
             .query(
-                "SELECT code, name, points, url, faculty, first_seen_at
-                 FROM courses ORDER BY code",
+                "SELECT c.code, c.name, c.points, c.url, c.faculty, c.first_seen_at, d.details, c.source,
+                        c.appearances, c.last_absence_seconds
+                 FROM courses c LEFT JOIN course_details d ON d.source = c.source AND d.code = c.code
+                 WHERE c.available = 1
+                 ORDER BY c.code, c.source",
                 (),
             )
             .await?;
@@ -437,104 +953,343 @@ impl Database {
                 url: row.get::<String>(3)?,
                 faculty: row.get::<String>(4)?,
                 first_seen_at: row.get::<String>(5)?,
+                details: parse_details_json(row.get::<Option<String>>(6)?),
+                source: row.get::<String>(7)?,
+                appearances: row.get::<i64>(8)? as u32,
+                last_absence_seconds: row.get::<Option<i64>>(9)?,
             });
         }
 
         Ok(courses)
     }
 
-    /// Get recent run logs for web display
-    pub async fn get_run_logs(&self, limit: usize) -> Result<Vec<RunLogEntry>> {
+    /// Watchlist patterns added from the web UI, in the order they were added
+    pub async fn get_watchlist(&self) -> Result<Vec<String>> {
         let mut rows = self
             .conn
-            .query(
-                "SELECT id, timestamp, total_courses_fetched, raw_added_count, raw_removed_count,
-                        filtered_added_count, filtered_removed_count, filter_used,
-                        notification_sent, is_first_run, added_courses, removed_courses, duration_ms
-                 FROM run_log ORDER BY id DESC LIMIT ?",
-                libsql::params![limit as i64],
-            )
+            .query("SELECT pattern FROM watchlist ORDER BY added_at, pattern", ())
             .await?;
 
-        let mut entries = Vec::new();
+        let mut patterns = Vec::new();
         while let Some(row) = rows.next().await? {
-            let added_json: String = row.get(10)?;
-            let removed_json: String = row.get(11)?;
-
-            entries.push(RunLogEntry {
-                id: row.get(0)?,
-                timestamp: row.get(1)?,
-                total_courses_fetched: row.get(2)?,
-                raw_added_count: row.get(3)?,
-                raw_removed_count: row.get(4)?,
-                filtered_added_count: row.get(5)?,
-                filtered_removed_count: row.get(6)?,
-                filter_used: row.get(7)?,
-                notification_sent: row.get::<i64>(8)? != 0,
-                is_first_run: row.get::<i64>(9)? != 0,
-                added_courses: parse_courses_json(&added_json),
-                removed_courses: parse_courses_json(&removed_json),
-                duration_ms: row.get(12)?,
-            });
+            patterns.push(row.get::<String>(0)?);
         }
+        Ok(patterns)
+    }
 
-        Ok(entries)
+    /// Add a (normalized) watchlist pattern; adding an existing one is a no-op
+    pub async fn add_watch(&self, pattern: &str) -> Result<()> {
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO watchlist (pattern, added_at) VALUES (?, ?)",
+                libsql::params![pattern, Utc::now().to_rfc3339()],
+            )
+            .await?;
+        info!(pattern = %pattern, "Watchlist pattern added");
+        Ok(())
     }
 
-    /// Get a single run log by ID
-    pub async fn get_run_log(&self, id: i64) -> Result<Option<RunLogEntry>> {
+    pub async fn remove_watch(&self, pattern: &str) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM watchlist WHERE pattern = ?", libsql::params![pattern])
+            .await?;
+        info!(pattern = %pattern, "Watchlist pattern removed");
+        Ok(())
+    }
+
+    /// All subscribers, oldest first
+    pub async fn get_subscribers(&self) -> Result<Vec<SubscriberRecord>> {
         let mut rows = self
             .conn
             .query(
-                "SELECT id, timestamp, total_courses_fetched, raw_added_count, raw_removed_count,
-                        filtered_added_count, filtered_removed_count, filter_used,
-                        notification_sent, is_first_run, added_courses, removed_courses, duration_ms
-                 FROM run_log WHERE id = ?",
-                libsql::params![id],
+                "SELECT id, name, filter, email, phone, language, quiet_hours
+                 FROM subscribers ORDER BY id",
+                (),
             )
             .await?;
 
-        if let Some(row) = rows.next().await? {
-            let added_json: String = row.get(10)?;
-            let removed_json: String = row.get(11)?;
-
-            Ok(Some(RunLogEntry {
-                id: row.get(0)?,
-                timestamp: row.get(1)?,
-                total_courses_fetched: row.get(2)?,
-                raw_added_count: row.get(3)?,
-                raw_removed_count: row.get(4)?,
-                filtered_added_count: row.get(5)?,
-                filtered_removed_count: row.get(6)?,
-                filter_used: row.get(7)?,
-                notification_sent: row.get::<i64>(8)? != 0,
-                is_first_run: row.get::<i64>(9)? != 0,
-                added_courses: parse_courses_json(&added_json),
-                removed_courses: parse_courses_json(&removed_json),
-                duration_ms: row.get(12)?,
-            }))
-        } else {
-            Ok(None)
+        let mut subscribers = Vec::new();
+        while let Some(row) = rows.next().await? {
+            subscribers.push(SubscriberRecord {
+                id: row.get::<i64>(0)?,
+                name: row.get::<String>(1)?,
+                filter: row.get::<Option<String>>(2)?,
+                email: row.get::<Option<String>>(3)?,
+                phone: row.get::<Option<String>>(4)?,
+                language: row.get::<String>(5)?,
+                quiet_hours: row.get::<Option<String>>(6)?,
+            });
         }
+        Ok(subscribers)
     }
 
-    #[instrument(skip(self, current_courses), fields(incoming_courses = current_courses.len()))]
-    pub async fn sync_courses(&self, current_courses: &[Course]) -> Result<SyncResult> {
-        let now = Utc::now();
-        let now_str = now.to_rfc3339();
-        let is_first_run = self.is_first_run().await?;
-
-        // Get existing courses (single query)
-        let existing = self.get_all_courses().await?;
-        let existing_count = existing.len();
-        let current_codes: std::collections::HashSet<_> =
-            current_courses.iter().map(|c| c.code.clone()).collect();
-        let existing_codes: std::collections::HashSet<_> = existing.keys().cloned().collect();
+    /// Store a new subscriber and return its id (`subscriber.id` is ignored)
+    pub async fn add_subscriber(&self, subscriber: &SubscriberRecord) -> Result<i64> {
+        self.conn
+            .execute(
+                "INSERT INTO subscribers (name, filter, email, phone, language, quiet_hours, created_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+                libsql::params![
+                    subscriber.name.clone(),
+                    subscriber.filter.clone(),
+                    subscriber.email.clone(),
+                    subscriber.phone.clone(),
+                    subscriber.language.clone(),
+                    subscriber.quiet_hours.clone(),
+                    Utc::now().to_rfc3339()
+                ],
+            )
+            .await
+            .with_context(|| format!("Failed to add subscriber '{}'", subscriber.name))?;
 
-        info!(
+        let mut rows = self.conn.query("SELECT last_insert_rowid()", ()).await?;
+        let id = rows
+            .next()
+            .await?
+            .map(|row| row.get::<i64>(0).unwrap_or(0))
+            .unwrap_or(0);
+
+        info!(subscriber_id = id, name = %subscriber.name, "Subscriber added");
+        Ok(id)
+    }
+
+    pub async fn remove_subscriber(&self, id: i64) -> Result<()> {
+        self.conn
+            .execute_batch(&format!(
+                "DELETE FROM held_changes WHERE subscriber_id = {id};
+                 DELETE FROM subscribers WHERE id = {id};"
+            ))
+            .await?;
+        info!(subscriber_id = id, "Subscriber removed");
+        Ok(())
+    }
+
+    /// Keep changes of one source for a subscriber until their quiet hours end
+    pub async fn hold_changes(&self, subscriber_id: i64, source: &str, diff: &ScrapeDiff) -> Result<()> {
+        self.conn
+            .execute(
+                "INSERT INTO held_changes (subscriber_id, source, diff, held_at) VALUES (?, ?, ?, ?)",
+                libsql::params![subscriber_id, source, serde_json::to_string(diff)?, Utc::now().to_rfc3339()],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Changes held for a subscriber from one source, oldest first
+    pub async fn get_held_changes(&self, subscriber_id: i64, source: &str) -> Result<Vec<ScrapeDiff>> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT diff FROM held_changes WHERE subscriber_id = ? AND source = ? ORDER BY id",
+                libsql::params![subscriber_id, source],
+            )
+            .await?;
+
+        let mut diffs = Vec::new();
+        while let Some(row) = rows.next().await? {
+            diffs.push(serde_json::from_str(&row.get::<String>(0)?)?);
+        }
+        Ok(diffs)
+    }
+
+    /// Forget held changes once they have been delivered
+    pub async fn clear_held_changes(&self, subscriber_id: i64, source: &str) -> Result<()> {
+        self.conn
+            .execute(
+                "DELETE FROM held_changes WHERE subscriber_id = ? AND source = ?",
+                libsql::params![subscriber_id, source],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Cached course details of one source fetched after `fetched_after`, keyed by course code
+    pub async fn get_course_details(
+        &self,
+        source: &str,
+        codes: &[String],
+        fetched_after: chrono::DateTime<Utc>,
+    ) -> Result<HashMap<String, CourseDetails>> {
+        if codes.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let code_list = codes
+            .iter()
+            .map(|code| format!("'{}'", escape_sql(code)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut rows = self
+            .conn
+            .query(
+                &format!(
+                    "SELECT code, details FROM course_details WHERE source = ? AND code IN ({}) AND fetched_at >= ?",
+                    code_list
+                ),
+                libsql::params![source, fetched_after.to_rfc3339()],
+            )
+            .await?;
+
+        let mut details = HashMap::new();
+        while let Some(row) = rows.next().await? {
+            if let Some(parsed) = parse_details_json(Some(row.get::<String>(1)?)) {
+                details.insert(row.get::<String>(0)?, parsed);
+            }
+        }
+
+        Ok(details)
+    }
+
+    /// Store freshly fetched course details, replacing older cache entries
+    pub async fn store_course_details(&self, source: &str, details: &[(String, CourseDetails)]) -> Result<()> {
+        if details.is_empty() {
+            return Ok(());
+        }
+
+        let now_str = Utc::now().to_rfc3339();
+        let mut batch_sql = String::new();
+        for (code, course_details) in details {
+            batch_sql.push_str(&format!(
+                "INSERT OR REPLACE INTO course_details (source, code, details, fetched_at) VALUES ('{}', '{}', '{}', '{}');\n",
+                escape_sql(source),
+                escape_sql(code),
+                escape_sql(&serde_json::to_string(course_details)?),
+                now_str
+            ));
+        }
+
+        self.conn
+            .execute_batch(&batch_sql)
+            .await
+            .context("Failed to store course details")?;
+
+        Ok(())
+    }
+
+    /// Get recent run logs for web display
+    pub async fn get_run_logs(&self, limit: usize) -> Result<Vec<RunLogEntry>> {
+        let mut rows = self
+            .conn
+            .query(
+                &format!("SELECT {} FROM run_log ORDER BY id DESC LIMIT ?", RUN_LOG_COLUMNS),
+                libsql::params![limit as i64],
+            )
+            .await?;
+
+        let mut entries = Vec::new();
+        while let Some(row) = rows.next().await? {
+            entries.push(run_log_entry_from_row(&row)?);
+        }
+
+        Ok(entries)
+    }
+
+    /// Get a single run log by ID
+    pub async fn get_run_log(&self, id: i64) -> Result<Option<RunLogEntry>> {
+        let mut rows = self
+            .conn
+            .query(
+                &format!("SELECT {} FROM run_log WHERE id = ?", RUN_LOG_COLUMNS),
+                libsql::params![id],
+            )
+            .await?;
+
+        match rows.next().await? {
+            Some(row) => Ok(Some(run_log_entry_from_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Archive the raw HTML of a fetched page, keeping at most `retention` snapshots.
+    /// Identical pages share one row; only their last_seen_at is refreshed.
+    #[instrument(skip(self, html), fields(content_hash = %content_hash, size_bytes = html.len()))]
+    pub async fn store_snapshot(&self, content_hash: &str, html: &str, retention: usize) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+
+        self.conn
+            .execute(
+                "INSERT INTO page_snapshots (content_hash, html, size_bytes, first_seen_at, last_seen_at)
+                 VALUES (?, ?, ?, ?, ?)
+                 ON CONFLICT(content_hash) DO UPDATE SET last_seen_at = excluded.last_seen_at",
+                libsql::params![
+                    content_hash.to_string(),
+                    html.to_string(),
+                    html.len() as i64,
+                    now.clone(),
+                    now,
+                ],
+            )
+            .await
+            .context("Failed to store page snapshot")?;
+
+        let pruned = self
+            .conn
+            .execute(
+                "DELETE FROM page_snapshots WHERE content_hash NOT IN (
+                    SELECT content_hash FROM page_snapshots ORDER BY last_seen_at DESC LIMIT ?
+                 )",
+                libsql::params![retention as i64],
+            )
+            .await
+            .context("Failed to prune page snapshots")?;
+
+        debug!(
+            retention = retention,
+            pruned_snapshots = pruned,
+            "Page snapshot archived"
+        );
+
+        Ok(())
+    }
+
+    /// Get the raw HTML of an archived snapshot by content hash
+    pub async fn get_snapshot(&self, content_hash: &str) -> Result<Option<String>> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT html FROM page_snapshots WHERE content_hash = ?",
+                libsql::params![content_hash.to_string()],
+            )
+            .await?;
+
+        match rows.next().await? {
+            Some(row) => Ok(Some(row.get::<String>(0)?)),
+            None => Ok(None),
+        }
+    }
+
+    #[instrument(skip(self, current_courses, debounce), fields(incoming_courses = current_courses.len()))]
+    pub async fn sync_courses(
+        &self,
+        source: &str,
+        current_courses: &[Course],
+        debounce: &Debounce,
+    ) -> Result<SyncResult> {
+        let now = Utc::now();
+        let now_str = now.to_rfc3339();
+        let is_first_run = self.is_first_run(source).await?;
+
+        // Get existing courses and changes awaiting confirmation (one query each)
+        let existing = self.get_all_courses(source).await?;
+        let existing_count = existing.len();
+        let (pending, previously_removed) = if is_first_run {
+            (HashMap::new(), HashMap::new())
+        } else {
+            (
+                self.get_pending_changes(source).await?,
+                self.get_removed_courses(source).await?,
+            )
+        };
+        let current_codes: std::collections::HashSet<_> =
+            current_courses.iter().map(|c| c.code.clone()).collect();
+        let existing_codes: std::collections::HashSet<_> = existing.keys().cloned().collect();
+
+        info!(
+            source = %source,
             is_first_run = is_first_run,
             existing_courses_in_db = existing_count,
             incoming_courses = current_courses.len(),
+            pending_changes = pending.len(),
             db_type = %self.db_type,
             "Starting database sync"
         );
@@ -545,75 +1300,206 @@ impl Database {
 
         let mut added: Vec<Course> = Vec::new();
         let mut removed: Vec<Course> = Vec::new();
+        let mut reappeared: Vec<Reappearance> = Vec::new();
+        // Candidates that have not yet been present/absent long enough to count
+        let mut held: HashMap<String, PendingChange> = HashMap::new();
 
         // Collect added courses
         if !is_first_run {
             for course in current_courses {
-                if added_codes.contains(&course.code) {
+                if !added_codes.contains(&course.code) {
+                    continue;
+                }
+                let (cycles, since) = match pending.get(&course.code) {
+                    Some(p) if p.kind == PendingKind::Added => (p.cycles + 1, p.since),
+                    _ => (1, now),
+                };
+                if !debounce.addition_confirmed(cycles) {
                     debug!(
                         course_code = %course.code,
-                        course_name = %course.name,
-                        points = course.points,
-                        "New course detected"
+                        cycles_present = cycles,
+                        required_cycles = debounce.addition_cycles,
+                        "New course pending confirmation"
+                    );
+                    held.insert(
+                        course.code.clone(),
+                        PendingChange {
+                            kind: PendingKind::Added,
+                            since,
+                            cycles,
+                        },
                     );
-                    added.push(course.clone());
+                    continue;
                 }
+                // A code that was available before is a return, not a new course
+                if let Some(gone) = previously_removed.get(&course.code) {
+                    let reappearance = Reappearance {
+                        course: course.clone(),
+                        absent_seconds: (now - gone.removed_at).num_seconds().max(0),
+                        previous_appearances: gone.appearances,
+                    };
+                    debug!(
+                        course_code = %course.code,
+                        absent_for = %reappearance.absence(),
+                        previous_appearances = gone.appearances,
+                        "Course available again"
+                    );
+                    reappeared.push(reappearance);
+                    continue;
+                }
+                debug!(
+                    course_code = %course.code,
+                    course_name = %course.name,
+                    points = course.points,
+                    "New course detected"
+                );
+                added.push(course.clone());
+            }
+        }
+
+        // Collect courses whose tracked fields changed
+        let mut changed: Vec<CourseChange> = Vec::new();
+        if !is_first_run {
+            for course in current_courses {
+                let Some(change) = existing
+                    .get(&course.code)
+                    .and_then(|previous| CourseChange::between(previous, course))
+                else {
+                    continue;
+                };
+                debug!(
+                    course_code = %course.code,
+                    changes = %change.summary(),
+                    "Course modified"
+                );
+                changed.push(change);
             }
         }
 
         // Collect removed courses
         if !is_first_run {
             for code in &removed_codes {
-                if let Some(course) = existing.get(code) {
+                let Some(course) = existing.get(code) else {
+                    continue;
+                };
+                let (cycles, since) = match pending.get(code) {
+                    Some(p) if p.kind == PendingKind::Removed => (p.cycles + 1, p.since),
+                    _ => (1, now),
+                };
+                if !debounce.removal_confirmed(cycles, since, now) {
                     debug!(
                         course_code = %course.code,
-                        course_name = %course.name,
-                        points = course.points,
-                        "Course removed from availability"
+                        cycles_absent = cycles,
+                        absent_since = %since.to_rfc3339(),
+                        "Course absence pending confirmation"
                     );
-                    removed.push(course.clone());
+                    held.insert(
+                        code.clone(),
+                        PendingChange {
+                            kind: PendingKind::Removed,
+                            since,
+                            cycles,
+                        },
+                    );
+                    continue;
                 }
+                debug!(
+                    course_code = %course.code,
+                    course_name = %course.name,
+                    points = course.points,
+                    "Course removed from availability"
+                );
+                removed.push(course.clone());
+            }
+        }
+
+        // Pending changes that are no longer candidates were flaps
+        for (code, change) in &pending {
+            if !held.contains_key(code) {
+                debug!(
+                    course_code = %code,
+                    kind = change.kind.as_str(),
+                    cycles = change.cycles,
+                    "Pending change reverted before confirmation"
+                );
             }
         }
 
         // Build batch SQL for all upserts (single network round trip)
         let mut batch_sql = String::new();
         for course in current_courses {
-            // Use INSERT OR REPLACE to handle both insert and update cases
+            // New courses are only stored once their addition is confirmed
+            if held.contains_key(&course.code) {
+                continue;
+            }
+            // Returning courses keep first_seen_at and count one more appearance
+            let absence = reappeared
+                .iter()
+                .find(|r| r.course.code == course.code)
+                .map(|r| format!(", last_absence_seconds = {}", r.absent_seconds))
+                .unwrap_or_default();
             let sql = format!(
-                "INSERT OR REPLACE INTO courses (code, name, points, url, faculty, first_seen_at, last_seen_at) \
-                 VALUES ('{}', '{}', {}, '{}', '{}', \
-                 COALESCE((SELECT first_seen_at FROM courses WHERE code = '{}'), '{}'), '{}');\n",
+                "INSERT INTO courses (source, code, name, points, url, faculty, attributes, first_seen_at, last_seen_at) \
+                 VALUES ('{}', '{}', '{}', {}, '{}', '{}', '{}', '{}', '{}') \
+                 ON CONFLICT(source, code) DO UPDATE SET \
+                 name = excluded.name, points = excluded.points, url = excluded.url, \
+                 faculty = excluded.faculty, attributes = excluded.attributes, \
+                 last_seen_at = excluded.last_seen_at, \
+                 appearances = courses.appearances + 1 - courses.available, \
+                 available = 1, removed_at = NULL{};\n",
+                escape_sql(source),
                 escape_sql(&course.code),
                 escape_sql(&course.name),
                 course.points as f64,
                 escape_sql(&course.url),
                 escape_sql(&course.faculty),
-                escape_sql(&course.code),
+                escape_sql(&serde_json::to_string(&course.attributes)?),
                 now_str,
-                now_str
+                now_str,
+                absence
             );
             batch_sql.push_str(&sql);
         }
 
-        // Delete removed courses
-        for code in &removed_codes {
+        // Mark removed courses unavailable, keeping their history (absent courses
+        // awaiting confirmation stay available)
+        for code in removed_codes.iter().filter(|code| !held.contains_key(*code)) {
             batch_sql.push_str(&format!(
-                "DELETE FROM courses WHERE code = '{}';\n",
+                "UPDATE courses SET available = 0, removed_at = '{}' WHERE source = '{}' AND code = '{}';\n",
+                now_str,
+                escape_sql(source),
                 escape_sql(code)
             ));
         }
 
+        // Replace the pending state with this cycle's held candidates
+        batch_sql.push_str(&format!(
+            "DELETE FROM pending_changes WHERE source = '{}';\n",
+            escape_sql(source)
+        ));
+        for (code, change) in &held {
+            batch_sql.push_str(&format!(
+                "INSERT INTO pending_changes (source, code, kind, since, cycles) \
+                 VALUES ('{}', '{}', '{}', '{}', {});\n",
+                escape_sql(source),
+                escape_sql(code),
+                change.kind.as_str(),
+                change.since.to_rfc3339(),
+                change.cycles
+            ));
+        }
+
         // Log changes (added)
         if !is_first_run {
             for course in &added {
                 let json = serde_json::to_string(course).unwrap_or_default();
                 batch_sql.push_str(&format!(
-                    "INSERT INTO change_log (timestamp, change_type, course_code, course_data) \
-                     VALUES ('{}', 'added', '{}', '{}');\n",
+                    "INSERT INTO change_log (timestamp, change_type, course_code, course_data, source) \
+                     VALUES ('{}', 'added', '{}', '{}', '{}');\n",
                     now_str,
                     escape_sql(&course.code),
-                    escape_sql(&json)
+                    escape_sql(&json),
+                    escape_sql(source)
                 ));
             }
 
@@ -621,11 +1507,38 @@ impl Database {
             for course in &removed {
                 let json = serde_json::to_string(course).unwrap_or_default();
                 batch_sql.push_str(&format!(
-                    "INSERT INTO change_log (timestamp, change_type, course_code, course_data) \
-                     VALUES ('{}', 'removed', '{}', '{}');\n",
+                    "INSERT INTO change_log (timestamp, change_type, course_code, course_data, source) \
+                     VALUES ('{}', 'removed', '{}', '{}', '{}');\n",
                     now_str,
                     escape_sql(&course.code),
-                    escape_sql(&json)
+                    escape_sql(&json),
+                    escape_sql(source)
+                ));
+            }
+
+            // Log changes (reappeared)
+            for reappearance in &reappeared {
+                let json = serde_json::to_string(reappearance).unwrap_or_default();
+                batch_sql.push_str(&format!(
+                    "INSERT INTO change_log (timestamp, change_type, course_code, course_data, source) \
+                     VALUES ('{}', 'reappeared', '{}', '{}', '{}');\n",
+                    now_str,
+                    escape_sql(&reappearance.course.code),
+                    escape_sql(&json),
+                    escape_sql(source)
+                ));
+            }
+
+            // Log changes (modified)
+            for change in &changed {
+                let json = serde_json::to_string(change).unwrap_or_default();
+                batch_sql.push_str(&format!(
+                    "INSERT INTO change_log (timestamp, change_type, course_code, course_data, source) \
+                     VALUES ('{}', 'modified', '{}', '{}', '{}');\n",
+                    now_str,
+                    escape_sql(change.code()),
+                    escape_sql(&json),
+                    escape_sql(source)
                 ));
             }
         }
@@ -638,22 +1551,32 @@ impl Database {
                 .context("Failed to execute batch sync")?;
         }
 
-        let updated_count = current_courses.len() - added.len();
+        let pending_added = held.values().filter(|p| p.kind == PendingKind::Added).count();
+        let pending_removed = held.len() - pending_added;
+        let updated_count = current_courses.len() - added.len() - reappeared.len() - pending_added;
 
         if is_first_run {
             info!(
+                source = %source,
                 courses_stored = current_courses.len(),
                 db_type = %self.db_type,
                 "First run completed - database initialized"
             );
         } else {
             info!(
+                source = %source,
                 added_count = added.len(),
                 removed_count = removed.len(),
+                changed_count = changed.len(),
+                reappeared_count = reappeared.len(),
+                pending_added = pending_added,
+                pending_removed = pending_removed,
                 updated_count = updated_count,
                 total_courses = current_courses.len(),
                 added_codes = ?added.iter().map(|c| c.code.as_str()).collect::<Vec<_>>(),
                 removed_codes = ?removed.iter().map(|c| c.code.as_str()).collect::<Vec<_>>(),
+                changed_codes = ?changed.iter().map(|c| c.code()).collect::<Vec<_>>(),
+                reappeared_codes = ?reappeared.iter().map(|r| r.course.code.as_str()).collect::<Vec<_>>(),
                 db_type = %self.db_type,
                 "Database sync completed"
             );
@@ -662,6 +1585,8 @@ impl Database {
         Ok(SyncResult {
             added,
             removed,
+            changed,
+            reappeared,
             is_first_run,
             total_courses: current_courses.len(),
         })
@@ -673,34 +1598,204 @@ fn escape_sql(s: &str) -> String {
     s.replace('\'', "''")
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct SyncResult {
     pub added: Vec<Course>,
     pub removed: Vec<Course>,
+    pub changed: Vec<CourseChange>,
+    pub reappeared: Vec<Reappearance>,
     pub is_first_run: bool,
     pub total_courses: usize,
 }
 
+/// Flap suppression: how long a course must be absent or present before it is reported.
+/// The default reports every change on the first cycle it is seen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Debounce {
+    /// Consecutive cycles a course must be absent before it counts as removed
+    pub removal_cycles: Option<u32>,
+    /// Time a course must be absent before it counts as removed
+    pub removal_after: Option<chrono::Duration>,
+    /// Consecutive cycles a course must be present before it counts as added
+    pub addition_cycles: u32,
+}
+
+impl Default for Debounce {
+    fn default() -> Self {
+        Self {
+            removal_cycles: None,
+            removal_after: None,
+            addition_cycles: 1,
+        }
+    }
+}
+
+impl Debounce {
+    pub fn is_enabled(&self) -> bool {
+        *self != Self::default()
+    }
+
+    /// Whether a course absent for `cycles` cycles since `since` counts as removed.
+    /// Either limit is enough; without limits every absence counts.
+    pub fn removal_confirmed(&self, cycles: u32, since: chrono::DateTime<Utc>, now: chrono::DateTime<Utc>) -> bool {
+        match (self.removal_cycles, self.removal_after) {
+            (None, None) => true,
+            (min_cycles, min_age) => {
+                min_cycles.is_some_and(|n| cycles >= n) || min_age.is_some_and(|age| now - since >= age)
+            }
+        }
+    }
+
+    /// Whether a course present for `cycles` consecutive cycles counts as added
+    pub fn addition_confirmed(&self, cycles: u32) -> bool {
+        cycles >= self.addition_cycles
+    }
+
+    /// Human-readable rules, e.g. "removed after 3 cycles or 15 min, added after 2 cycles"
+    pub fn description(&self) -> String {
+        let removal = match (self.removal_cycles, self.removal_after) {
+            (None, None) => "removed at once".to_string(),
+            (Some(n), None) => format!("removed after {} cycles", n),
+            (None, Some(age)) => format!("removed after {} min", age.num_minutes()),
+            (Some(n), Some(age)) => {
+                format!("removed after {} cycles or {} min", n, age.num_minutes())
+            }
+        };
+        let addition = if self.addition_cycles <= 1 {
+            "added at once".to_string()
+        } else {
+            format!("added after {} cycles", self.addition_cycles)
+        };
+        format!("{}, {}", removal, addition)
+    }
+}
+
+/// Whether a pending change is an unconfirmed addition or removal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PendingKind {
+    Added,
+    Removed,
+}
+
+impl PendingKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PendingKind::Added => "added",
+            PendingKind::Removed => "removed",
+        }
+    }
+}
+
+/// A subscriber as stored; see `subscriber::Subscriber` for the parsed form
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SubscriberRecord {
+    pub id: i64,
+    pub name: String,
+    /// Filter expression (None notifies every change)
+    pub filter: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    /// Language code, "no" or "en"
+    pub language: String,
+    /// Quiet hours, e.g. "22:00-07:00"
+    pub quiet_hours: Option<String>,
+}
+
+/// History of a course that is no longer available
+#[derive(Debug, Clone)]
+pub struct RemovedCourse {
+    pub removed_at: chrono::DateTime<Utc>,
+    /// How many separate times the course has been available
+    pub appearances: u32,
+}
+
+/// A course that appeared or disappeared, but not for long enough to be reported yet
+#[derive(Debug, Clone)]
+pub struct PendingChange {
+    pub kind: PendingKind,
+    /// When the course was first seen present (added) or absent (removed)
+    pub since: chrono::DateTime<Utc>,
+    /// Consecutive cycles the course has been present/absent
+    pub cycles: u32,
+}
+
 impl SyncResult {
     pub fn has_changes(&self) -> bool {
-        !self.added.is_empty() || !self.removed.is_empty()
+        !self.added.is_empty()
+            || !self.removed.is_empty()
+            || !self.changed.is_empty()
+            || !self.reappeared.is_empty()
+    }
+}
+
+/// Outcome of a scrape run as recorded in run_log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunStatus {
+    /// Page was parsed and synced
+    Ok,
+    /// Page matched the last processed one; parse, sync and notifications were skipped
+    Unchanged,
+    /// Fetch or parse failed; see the run's error
+    Failed,
+    /// Page failed the sanity guard; sync and notifications were skipped
+    Anomalous,
+}
+
+impl RunStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RunStatus::Ok => "ok",
+            RunStatus::Unchanged => "unchanged",
+            RunStatus::Failed => "failed",
+            RunStatus::Anomalous => "anomalous",
+        }
+    }
+
+    fn from_db(s: &str) -> Self {
+        match s {
+            "unchanged" => RunStatus::Unchanged,
+            "failed" => RunStatus::Failed,
+            "anomalous" => RunStatus::Anomalous,
+            _ => RunStatus::Ok,
+        }
     }
 }
 
 /// Record of a single scrape run for logging
 #[derive(Debug)]
 pub struct RunLog {
+    /// Name of the configured source this run scraped
+    pub source: String,
+    pub status: RunStatus,
     pub total_courses_fetched: usize,
     pub raw_added_count: usize,
     pub raw_removed_count: usize,
     pub filtered_added_count: usize,
     pub filtered_removed_count: usize,
+    pub raw_changed_count: usize,
+    pub filtered_changed_count: usize,
     pub filter_used: String,
     pub notification_sent: bool,
     pub is_first_run: bool,
     pub added_courses: Vec<Course>,
     pub removed_courses: Vec<Course>,
+    pub changed_courses: Vec<CourseChange>,
+    pub reappeared_courses: Vec<Reappearance>,
+    /// Codes of filter matches dropped by exclusion rules
+    pub excluded_courses: Vec<String>,
     pub duration_ms: u64,
+    /// SHA-256 of the page this run parsed (links to page_snapshots when archived)
+    pub content_hash: Option<String>,
+    pub error: Option<RunError>,
+}
+
+/// Classified error of a failed run
+#[derive(Debug, Clone)]
+pub struct RunError {
+    pub kind: String,
+    pub message: String,
+    /// Whether the failure is expected to clear up on its own
+    pub transient: bool,
 }
 
 /// Course data for web display
@@ -712,24 +1807,87 @@ pub struct CourseDisplay {
     pub faculty: String,
     pub url: String,
     pub first_seen_at: String,
+    pub details: Option<CourseDetails>,
+    pub source: String,
+    /// How many separate times the course has been available
+    pub appearances: u32,
+    /// How long the course was gone before its latest return
+    pub last_absence_seconds: Option<i64>,
 }
 
 /// Run log entry for web display
 #[derive(Debug, Clone)]
 pub struct RunLogEntry {
     pub id: i64,
+    pub source: String,
+    pub status: RunStatus,
     pub timestamp: String,
     pub total_courses_fetched: i64,
     pub raw_added_count: i64,
     pub raw_removed_count: i64,
     pub filtered_added_count: i64,
     pub filtered_removed_count: i64,
+    pub raw_changed_count: i64,
+    pub filtered_changed_count: i64,
     pub filter_used: String,
     pub notification_sent: bool,
     pub is_first_run: bool,
     pub added_courses: Vec<Course>,
     pub removed_courses: Vec<Course>,
+    pub changed_courses: Vec<CourseChange>,
+    pub reappeared_courses: Vec<Reappearance>,
+    pub excluded_courses: Vec<String>,
     pub duration_ms: i64,
+    pub content_hash: Option<String>,
+    /// Whether the raw page for this run is still in the snapshot archive
+    pub snapshot_available: bool,
+    pub error: Option<RunError>,
+}
+
+/// Build a run log entry from a row selected with RUN_LOG_COLUMNS
+fn run_log_entry_from_row(row: &libsql::Row) -> Result<RunLogEntry> {
+    let added_json: String = row.get(10)?;
+    let removed_json: String = row.get(11)?;
+    let changed_json: String = row.get(22)?;
+    let reappeared_json: String = row.get(23)?;
+    let excluded_json: String = row.get(24)?;
+
+    Ok(RunLogEntry {
+        id: row.get(0)?,
+        source: row.get(19)?,
+        status: RunStatus::from_db(&row.get::<String>(15)?),
+        timestamp: row.get(1)?,
+        total_courses_fetched: row.get(2)?,
+        raw_added_count: row.get(3)?,
+        raw_removed_count: row.get(4)?,
+        filtered_added_count: row.get(5)?,
+        filtered_removed_count: row.get(6)?,
+        raw_changed_count: row.get(20)?,
+        filtered_changed_count: row.get(21)?,
+        filter_used: row.get(7)?,
+        notification_sent: row.get::<i64>(8)? != 0,
+        is_first_run: row.get::<i64>(9)? != 0,
+        added_courses: parse_courses_json(&added_json),
+        removed_courses: parse_courses_json(&removed_json),
+        changed_courses: serde_json::from_str(&changed_json).unwrap_or_default(),
+        reappeared_courses: serde_json::from_str(&reappeared_json).unwrap_or_default(),
+        excluded_courses: serde_json::from_str(&excluded_json).unwrap_or_default(),
+        duration_ms: row.get(12)?,
+        content_hash: row.get::<Option<String>>(13)?,
+        snapshot_available: row.get::<i64>(14)? != 0,
+        error: match row.get::<Option<String>>(16)? {
+            Some(kind) => Some(RunError {
+                kind,
+                message: row.get::<Option<String>>(17)?.unwrap_or_default(),
+                transient: row.get::<Option<i64>>(18)?.unwrap_or(0) != 0,
+            }),
+            None => None,
+        },
+    })
+}
+
+fn parse_details_json(json: Option<String>) -> Option<CourseDetails> {
+    json.and_then(|json| serde_json::from_str(&json).ok())
 }
 
 /// Parse courses JSON, handling both old format (array of strings) and new format (array of Course objects)
@@ -759,6 +1917,7 @@ fn parse_courses_json(json: &str) -> Vec<Course> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::DEFAULT_SOURCE;
 
     fn test_course() -> Course {
         Course::new(
@@ -780,6 +1939,30 @@ mod tests {
         )
     }
 
+    #[tokio::test]
+    async fn test_migration_resolves_relative_links() {
+        let mut db = Database::open_in_memory().await.unwrap();
+        let relative = Course {
+            url: "/studier/emner/matnat/ifi/IN1000/#info".to_string(),
+            ..make_course("IN1000", 10.0)
+        };
+        let unlinked = Course {
+            url: String::new(),
+            ..make_course("IN1010", 10.0)
+        };
+        db.sync_courses(DEFAULT_SOURCE, &[relative, unlinked, make_course("IN2000", 10.0)], &Debounce::default())
+            .await
+            .unwrap();
+
+        db.conn.execute("DELETE FROM schema_version WHERE version = 16", ()).await.unwrap();
+        db.run_migrations().await.unwrap();
+
+        let courses = db.get_all_courses(DEFAULT_SOURCE).await.unwrap();
+        assert_eq!(courses["IN1000"].url, "https://www.uio.no/studier/emner/matnat/ifi/IN1000/");
+        assert_eq!(courses["IN1010"].url, "");
+        assert_eq!(courses["IN2000"].url, "https://example.com/IN2000");
+    }
+
     #[tokio::test]
     async fn test_sync_courses() {
         let db = Database::open_in_memory().await.unwrap();
@@ -793,13 +1976,13 @@ mod tests {
         );
 
         // First sync - first run
-        let result = db.sync_courses(&[course1.clone(), course2.clone()]).await.unwrap();
+        let result = db.sync_courses(DEFAULT_SOURCE, &[course1.clone(), course2.clone()], &Debounce::default()).await.unwrap();
         assert!(result.is_first_run);
         assert!(result.added.is_empty()); // First run doesn't report added
         assert_eq!(result.total_courses, 2);
 
         // Second sync - remove course2
-        let result = db.sync_courses(&[course1.clone()]).await.unwrap();
+        let result = db.sync_courses(DEFAULT_SOURCE, std::slice::from_ref(&course1), &Debounce::default()).await.unwrap();
         assert!(!result.is_first_run);
         assert!(result.added.is_empty());
         assert_eq!(result.removed.len(), 1);
@@ -817,16 +2000,16 @@ mod tests {
 
         // First sync - populates DB
         let result = db
-            .sync_courses(&[course_a.clone(), course_b.clone(), course_c.clone()])
+            .sync_courses(DEFAULT_SOURCE, &[course_a.clone(), course_b.clone(), course_c.clone()], &Debounce::default())
             .await
             .unwrap();
         assert!(result.is_first_run);
-        assert_eq!(db.get_course_count().await.unwrap(), 3);
+        assert_eq!(db.get_course_count(DEFAULT_SOURCE).await.unwrap(), 3);
 
         // Second sync - course_a (2.5pts) removed, new course_d (2.5pts) added
         let course_d = make_course("NEWCOURSE", 2.5); // New 2.5 point course
         let result = db
-            .sync_courses(&[course_b.clone(), course_c.clone(), course_d.clone()])
+            .sync_courses(DEFAULT_SOURCE, &[course_b.clone(), course_c.clone(), course_d.clone()], &Debounce::default())
             .await
             .unwrap();
 
@@ -843,14 +2026,334 @@ mod tests {
         assert_eq!(result.removed[0].points, 2.5);
 
         // Verify DB state: should have B, C, D
-        assert_eq!(db.get_course_count().await.unwrap(), 3);
-        let all_courses = db.get_all_courses().await.unwrap();
+        assert_eq!(db.get_course_count(DEFAULT_SOURCE).await.unwrap(), 3);
+        let all_courses = db.get_all_courses(DEFAULT_SOURCE).await.unwrap();
         assert!(all_courses.contains_key("IN1000"));
         assert!(all_courses.contains_key("HIS2011M"));
         assert!(all_courses.contains_key("NEWCOURSE"));
         assert!(!all_courses.contains_key("HFLESER1031")); // Removed
     }
 
+    #[tokio::test]
+    async fn test_sync_detects_modified_courses() {
+        let db = Database::open_in_memory().await.unwrap();
+        let before = Utc::now() - chrono::Duration::seconds(1);
+
+        db.sync_courses(DEFAULT_SOURCE, &[make_course("IN1000", 5.0), make_course("IN1010", 10.0)], &Debounce::default())
+            .await
+            .unwrap();
+
+        let result = db
+            .sync_courses(DEFAULT_SOURCE, &[make_course("IN1000", 10.0), make_course("IN1010", 10.0)], &Debounce::default())
+            .await
+            .unwrap();
+        assert!(result.has_changes());
+        assert!(result.added.is_empty() && result.removed.is_empty());
+        assert_eq!(result.changed.len(), 1);
+        assert_eq!(result.changed[0].code(), "IN1000");
+        assert_eq!(result.changed[0].summary(), "points: 5 → 10");
+        assert_eq!(db.get_all_courses(DEFAULT_SOURCE).await.unwrap()["IN1000"].points, 10.0);
+        assert_eq!(db.count_changes_since(DEFAULT_SOURCE, before).await.unwrap(), 1);
+
+        // An identical scrape is not a modification
+        let result = db
+            .sync_courses(DEFAULT_SOURCE, &[make_course("IN1000", 10.0), make_course("IN1010", 10.0)], &Debounce::default())
+            .await
+            .unwrap();
+        assert!(!result.has_changes());
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_roundtrip() {
+        let db = Database::open_in_memory().await.unwrap();
+        let record = SubscriberRecord {
+            id: 0,
+            name: "Kari".to_string(),
+            filter: Some("points <= 5".to_string()),
+            email: Some("kari@example.com".to_string()),
+            phone: None,
+            language: "en".to_string(),
+            quiet_hours: Some("22:00-07:00".to_string()),
+        };
+        let id = db.add_subscriber(&record).await.unwrap();
+        assert!(db.add_subscriber(&record).await.is_err(), "names are unique");
+
+        let stored = db.get_subscribers().await.unwrap();
+        assert_eq!(stored, vec![SubscriberRecord { id, ..record }]);
+
+        db.remove_subscriber(id).await.unwrap();
+        assert!(db.get_subscribers().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_held_changes_per_subscriber_and_source() {
+        let db = Database::open_in_memory().await.unwrap();
+        let first = ScrapeDiff::new(vec![make_course("IN1000", 10.0)], vec![]);
+        let second = ScrapeDiff::new(vec![], vec![make_course("MAT1100", 10.0)]);
+        db.hold_changes(1, DEFAULT_SOURCE, &first).await.unwrap();
+        db.hold_changes(1, DEFAULT_SOURCE, &second).await.unwrap();
+        db.hold_changes(1, "english", &first).await.unwrap();
+        db.hold_changes(2, DEFAULT_SOURCE, &first).await.unwrap();
+
+        let held = db.get_held_changes(1, DEFAULT_SOURCE).await.unwrap();
+        assert_eq!(held.len(), 2);
+        assert_eq!(held[0].added[0].code, "IN1000");
+        assert_eq!(held[1].removed[0].code, "MAT1100");
+
+        db.clear_held_changes(1, DEFAULT_SOURCE).await.unwrap();
+        assert!(db.get_held_changes(1, DEFAULT_SOURCE).await.unwrap().is_empty());
+        assert_eq!(db.get_held_changes(1, "english").await.unwrap().len(), 1);
+
+        // Removing a subscriber drops what was held for them
+        db.remove_subscriber(2).await.unwrap();
+        assert!(db.get_held_changes(2, DEFAULT_SOURCE).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_watchlist_roundtrip() {
+        let db = Database::open_in_memory().await.unwrap();
+        db.add_watch("IN1000").await.unwrap();
+        db.add_watch("MAT*").await.unwrap();
+        db.add_watch("IN1000").await.unwrap();
+        assert_eq!(db.get_watchlist().await.unwrap(), vec!["IN1000", "MAT*"]);
+
+        db.remove_watch("IN1000").await.unwrap();
+        assert_eq!(db.get_watchlist().await.unwrap(), vec!["MAT*"]);
+    }
+
+    #[tokio::test]
+    async fn test_returning_course_is_reappearance() {
+        let db = Database::open_in_memory().await.unwrap();
+        let debounce = Debounce::default();
+        let in1000 = make_course("IN1000", 10.0);
+        let in1010 = make_course("IN1010", 10.0);
+
+        db.sync_courses(DEFAULT_SOURCE, &[in1000.clone(), in1010.clone()], &debounce).await.unwrap();
+        let first_seen = db.get_courses_for_display().await.unwrap()[1].first_seen_at.clone();
+
+        let result = db.sync_courses(DEFAULT_SOURCE, std::slice::from_ref(&in1000), &debounce).await.unwrap();
+        assert_eq!(result.removed.len(), 1);
+        assert_eq!(db.get_course_count(DEFAULT_SOURCE).await.unwrap(), 1);
+        assert!(db.get_removed_courses(DEFAULT_SOURCE).await.unwrap().contains_key("IN1010"));
+
+        let result = db
+            .sync_courses(DEFAULT_SOURCE, &[in1000.clone(), in1010.clone()], &debounce)
+            .await
+            .unwrap();
+        assert!(result.added.is_empty());
+        assert_eq!(result.reappeared.len(), 1);
+        assert_eq!(result.reappeared[0].course.code, "IN1010");
+        assert_eq!(result.reappeared[0].previous_appearances, 1);
+
+        // The course keeps its history and counts the new appearance
+        let display = db.get_courses_for_display().await.unwrap();
+        assert_eq!(display[1].code, "IN1010");
+        assert_eq!(display[1].first_seen_at, first_seen);
+        assert_eq!(display[1].appearances, 2);
+        assert!(display[1].last_absence_seconds.is_some());
+        assert_eq!(display[0].appearances, 1);
+        assert!(db.get_removed_courses(DEFAULT_SOURCE).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_debounce_suppresses_flaps() {
+        let db = Database::open_in_memory().await.unwrap();
+        let debounce = Debounce {
+            removal_cycles: Some(2),
+            removal_after: None,
+            addition_cycles: 2,
+        };
+        let in1000 = make_course("IN1000", 10.0);
+        let in1010 = make_course("IN1010", 10.0);
+        let sos1000 = make_course("SOS1000", 10.0);
+
+        db.sync_courses(DEFAULT_SOURCE, &[in1000.clone(), in1010.clone()], &debounce).await.unwrap();
+
+        // IN1010 drops off for one cycle and comes back: nothing is reported
+        let result = db.sync_courses(DEFAULT_SOURCE, std::slice::from_ref(&in1000), &debounce).await.unwrap();
+        assert!(!result.has_changes());
+        assert_eq!(db.get_course_count(DEFAULT_SOURCE).await.unwrap(), 2);
+        let pending = db.get_pending_changes(DEFAULT_SOURCE).await.unwrap();
+        assert_eq!(pending["IN1010"].kind, PendingKind::Removed);
+
+        let result = db
+            .sync_courses(DEFAULT_SOURCE, &[in1000.clone(), in1010.clone(), sos1000.clone()], &debounce)
+            .await
+            .unwrap();
+        assert!(!result.has_changes());
+        let pending = db.get_pending_changes(DEFAULT_SOURCE).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending["SOS1000"].kind, PendingKind::Added);
+
+        // The second consecutive cycle confirms the addition
+        let result = db
+            .sync_courses(DEFAULT_SOURCE, &[in1000.clone(), sos1000.clone()], &debounce)
+            .await
+            .unwrap();
+        assert_eq!(result.added.len(), 1);
+        assert_eq!(result.added[0].code, "SOS1000");
+        assert!(result.removed.is_empty());
+
+        // ...and the second consecutive absence confirms the removal
+        let result = db
+            .sync_courses(DEFAULT_SOURCE, &[in1000.clone(), sos1000.clone()], &debounce)
+            .await
+            .unwrap();
+        assert_eq!(result.removed.len(), 1);
+        assert_eq!(result.removed[0].code, "IN1010");
+        assert!(db.get_pending_changes(DEFAULT_SOURCE).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_debounce_removal_by_cycles_or_age() {
+        let now = Utc::now();
+        let debounce = Debounce {
+            removal_cycles: Some(3),
+            removal_after: Some(chrono::Duration::minutes(10)),
+            addition_cycles: 1,
+        };
+        assert!(!debounce.removal_confirmed(1, now - chrono::Duration::minutes(5), now));
+        assert!(debounce.removal_confirmed(3, now, now));
+        assert!(debounce.removal_confirmed(1, now - chrono::Duration::minutes(10), now));
+        assert!(Debounce::default().removal_confirmed(1, now, now));
+        assert_eq!(debounce.description(), "removed after 3 cycles or 10 min, added at once");
+    }
+
+    #[tokio::test]
+    async fn test_sources_are_synced_independently() {
+        let db = Database::open_in_memory().await.unwrap();
+
+        db.sync_courses("regular", &[make_course("IN1000", 10.0)], &Debounce::default()).await.unwrap();
+        // The same code in another listing is a separate course, and that source's first run
+        let result = db
+            .sync_courses("english", &[make_course("IN1000", 10.0), make_course("ECON1100", 10.0)], &Debounce::default())
+            .await
+            .unwrap();
+        assert!(result.is_first_run);
+
+        // Emptying one source must not remove the other source's courses
+        let result = db.sync_courses("english", &[make_course("ECON1100", 10.0)], &Debounce::default()).await.unwrap();
+        assert_eq!(result.removed.len(), 1);
+        assert_eq!(db.get_course_count("regular").await.unwrap(), 1);
+        assert_eq!(db.get_course_count("english").await.unwrap(), 1);
+
+        let display = db.get_courses_for_display().await.unwrap();
+        let sources: Vec<_> = display.iter().map(|c| (c.code.as_str(), c.source.as_str())).collect();
+        assert_eq!(sources, vec![("ECON1100", "english"), ("IN1000", "regular")]);
+    }
+
+    #[tokio::test]
+    async fn test_count_changes_since() {
+        let db = Database::open_in_memory().await.unwrap();
+        let before = Utc::now() - chrono::Duration::seconds(1);
+
+        db.sync_courses("regular", &[make_course("IN1000", 10.0)], &Debounce::default()).await.unwrap();
+        // First runs are not logged as changes
+        assert_eq!(db.count_changes_since("regular", before).await.unwrap(), 0);
+
+        db.sync_courses("regular", &[make_course("IN1010", 10.0)], &Debounce::default()).await.unwrap();
+        assert_eq!(db.count_changes_since("regular", before).await.unwrap(), 2);
+        assert_eq!(db.count_changes_since("english", before).await.unwrap(), 0);
+        let later = Utc::now() + chrono::Duration::seconds(1);
+        assert_eq!(db.count_changes_since("regular", later).await.unwrap(), 0);
+    }
+
+    fn run_log_with_hash(hash: &str) -> RunLog {
+        RunLog {
+            source: DEFAULT_SOURCE.to_string(),
+            status: RunStatus::Ok,
+            total_courses_fetched: 0,
+            raw_added_count: 0,
+            raw_removed_count: 0,
+            filtered_added_count: 0,
+            filtered_removed_count: 0,
+            raw_changed_count: 0,
+            filtered_changed_count: 0,
+            filter_used: "all courses".to_string(),
+            notification_sent: false,
+            is_first_run: false,
+            added_courses: vec![],
+            removed_courses: vec![],
+            changed_courses: vec![],
+            reappeared_courses: vec![],
+            excluded_courses: vec![],
+            duration_ms: 0,
+            content_hash: Some(hash.to_string()),
+            error: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_archive_links_runs_and_prunes() {
+        let db = Database::open_in_memory().await.unwrap();
+
+        db.store_snapshot("hash-a", "<html>a</html>", 2).await.unwrap();
+        let run_a = db.log_run(&run_log_with_hash("hash-a")).await.unwrap();
+
+        // Same page again is deduplicated
+        db.store_snapshot("hash-a", "<html>a</html>", 2).await.unwrap();
+
+        db.store_snapshot("hash-b", "<html>b</html>", 2).await.unwrap();
+        db.store_snapshot("hash-c", "<html>c</html>", 2).await.unwrap();
+        let run_c = db.log_run(&run_log_with_hash("hash-c")).await.unwrap();
+
+        // Retention of 2 keeps b and c, prunes a
+        assert_eq!(db.get_snapshot("hash-a").await.unwrap(), None);
+        assert_eq!(db.get_snapshot("hash-c").await.unwrap().as_deref(), Some("<html>c</html>"));
+
+        let entry_a = db.get_run_log(run_a).await.unwrap().unwrap();
+        assert_eq!(entry_a.content_hash.as_deref(), Some("hash-a"));
+        assert!(!entry_a.snapshot_available);
+
+        let entry_c = db.get_run_log(run_c).await.unwrap().unwrap();
+        assert!(entry_c.snapshot_available);
+    }
+
+    #[tokio::test]
+    async fn test_run_log_keeps_excluded_courses() {
+        let db = Database::open_in_memory().await.unwrap();
+
+        let run = RunLog {
+            excluded_courses: vec!["EVU1000".to_string(), "MAT9100".to_string()],
+            ..run_log_with_hash("hash-a")
+        };
+        let id = db.log_run(&run).await.unwrap();
+
+        let entry = db.get_run_log(id).await.unwrap().unwrap();
+        assert_eq!(entry.excluded_courses, vec!["EVU1000", "MAT9100"]);
+        assert!(db.get_run_logs(10).await.unwrap()[0].excluded_courses.len() == 2);
+    }
+
+    #[tokio::test]
+    async fn test_count_repeated_drops() {
+        let db = Database::open_in_memory().await.unwrap();
+        let run = |status, kind: Option<&str>, fetched| RunLog {
+            status,
+            total_courses_fetched: fetched,
+            error: kind.map(|kind| RunError {
+                kind: kind.to_string(),
+                message: String::new(),
+                transient: true,
+            }),
+            ..run_log_with_hash("hash-a")
+        };
+
+        db.log_run(&run(RunStatus::Ok, None, 100)).await.unwrap();
+        db.log_run(&run(RunStatus::Anomalous, Some("course_drop"), 30)).await.unwrap();
+        db.log_run(&run(RunStatus::Anomalous, Some("course_drop"), 40)).await.unwrap();
+        db.log_run(&run(RunStatus::Anomalous, Some("course_drop"), 40)).await.unwrap();
+        // A failed fetch does not break the streak
+        db.log_run(&run(RunStatus::Failed, Some("network"), 0)).await.unwrap();
+        db.log_run(&run(RunStatus::Anomalous, Some("course_drop"), 40)).await.unwrap();
+
+        assert_eq!(db.count_repeated_drops(DEFAULT_SOURCE, 40).await.unwrap(), 3);
+        assert_eq!(db.count_repeated_drops(DEFAULT_SOURCE, 30).await.unwrap(), 0);
+        assert_eq!(db.count_repeated_drops("english", 40).await.unwrap(), 0);
+
+        db.log_run(&run(RunStatus::Anomalous, Some("no_tables"), 0)).await.unwrap();
+        assert_eq!(db.count_repeated_drops(DEFAULT_SOURCE, 40).await.unwrap(), 0);
+    }
+
     #[tokio::test]
     async fn test_course_code_is_unique_identifier() {
         let db = Database::open_in_memory().await.unwrap();
@@ -873,18 +2376,19 @@ mod tests {
         );
 
         // Insert first version
-        let is_new = db.upsert_course(&course_v1, Utc::now()).await.unwrap();
-        assert!(is_new);
+        let result = db.sync_courses(DEFAULT_SOURCE, &[course_v1], &Debounce::default()).await.unwrap();
+        assert!(result.is_first_run);
 
-        // Insert second version with same code - should update, not create new
-        let is_new = db.upsert_course(&course_v2, Utc::now()).await.unwrap();
-        assert!(!is_new); // Not new because code already exists
+        // Sync second version with same code - should update, not create new
+        let result = db.sync_courses(DEFAULT_SOURCE, &[course_v2], &Debounce::default()).await.unwrap();
+        assert!(result.added.is_empty()); // Not new because code already exists
+        assert_eq!(result.changed.len(), 1);
 
         // Should still have only 1 course
-        assert_eq!(db.get_course_count().await.unwrap(), 1);
+        assert_eq!(db.get_course_count(DEFAULT_SOURCE).await.unwrap(), 1);
 
         // Course should have updated values
-        let courses = db.get_all_courses().await.unwrap();
+        let courses = db.get_all_courses(DEFAULT_SOURCE).await.unwrap();
         let course = courses.get("TEST123").unwrap();
         assert_eq!(course.name, "Updated Name");
         assert_eq!(course.points, 10.0);
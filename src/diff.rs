@@ -1,21 +1,37 @@
+use std::collections::BTreeSet;
 use tracing::{debug, info, instrument};
 
-use crate::config::PointsFilter;
+use crate::config::CourseFilter;
 use crate::db::SyncResult;
-use crate::models::{Course, ScrapeDiff};
+use crate::models::{Course, CourseChange, Reappearance, ScrapeDiff};
+use crate::watchlist::Watchlist;
 
-/// Filter sync results based on points criteria
-#[instrument(skip(result), fields(
+/// Filter sync results based on points, department and level criteria, then drop
+/// courses hit by an exclusion rule. Courses on the watchlist always pass and are
+/// marked as watched.
+#[instrument(skip(result, watchlist), fields(
     input_added = result.added.len(),
     input_removed = result.removed.len(),
+    input_changed = result.changed.len(),
+    input_reappeared = result.reappeared.len(),
     filter = %filter.description()
 ))]
-pub fn filter_changes(result: &SyncResult, filter: &PointsFilter) -> ScrapeDiff {
+pub fn filter_changes(result: &SyncResult, filter: &CourseFilter, watchlist: &Watchlist) -> ScrapeDiff {
+    let mut excluded: Vec<String> = Vec::new();
+    let mut not_excluded = |course: &Course, kind: &str| match filter.exclusion(course) {
+        Some(rule) => {
+            debug!(course_code = %course.code, kind, rule = %rule, "Course excluded");
+            excluded.push(course.code.clone());
+            false
+        }
+        None => true,
+    };
+
     let added: Vec<Course> = result
         .added
         .iter()
         .filter(|c| {
-            let matches = filter.matches(c.points);
+            let matches = filter.matches(c) || watchlist.matches(&c.code);
             if !matches {
                 debug!(
                     course_code = %c.code,
@@ -24,7 +40,7 @@ pub fn filter_changes(result: &SyncResult, filter: &PointsFilter) -> ScrapeDiff
                     "Added course filtered out"
                 );
             }
-            matches
+            matches && (watchlist.matches(&c.code) || not_excluded(c, "added"))
         })
         .cloned()
         .collect();
@@ -33,7 +49,7 @@ pub fn filter_changes(result: &SyncResult, filter: &PointsFilter) -> ScrapeDiff
         .removed
         .iter()
         .filter(|c| {
-            let matches = filter.matches(c.points);
+            let matches = filter.matches(c) || watchlist.matches(&c.code);
             if !matches {
                 debug!(
                     course_code = %c.code,
@@ -42,12 +58,65 @@ pub fn filter_changes(result: &SyncResult, filter: &PointsFilter) -> ScrapeDiff
                     "Removed course filtered out"
                 );
             }
-            matches
+            matches && (watchlist.matches(&c.code) || not_excluded(c, "removed"))
+        })
+        .cloned()
+        .collect();
+
+    // A change is relevant if the course matched the filter before or after it
+    let changed: Vec<CourseChange> = result
+        .changed
+        .iter()
+        .filter(|change| {
+            let matches = filter.matches(&change.before)
+                || filter.matches(&change.after)
+                || watchlist.matches(change.code());
+            if !matches {
+                debug!(
+                    course_code = %change.code(),
+                    changes = %change.summary(),
+                    filter = %filter.description(),
+                    "Modified course filtered out"
+                );
+            }
+            matches && (watchlist.matches(change.code()) || not_excluded(&change.after, "modified"))
+        })
+        .cloned()
+        .collect();
+
+    let reappeared: Vec<Reappearance> = result
+        .reappeared
+        .iter()
+        .filter(|r| {
+            let matches = filter.matches(&r.course) || watchlist.matches(&r.course.code);
+            if !matches {
+                debug!(
+                    course_code = %r.course.code,
+                    points = r.course.points,
+                    filter = %filter.description(),
+                    "Reappeared course filtered out"
+                );
+            }
+            matches && (watchlist.matches(&r.course.code) || not_excluded(&r.course, "reappeared"))
         })
         .cloned()
         .collect();
 
-    let diff = ScrapeDiff::new(added.clone(), removed.clone());
+    let watched: BTreeSet<String> = added
+        .iter()
+        .chain(&removed)
+        .map(|c| c.code.as_str())
+        .chain(changed.iter().map(|c| c.code()))
+        .chain(reappeared.iter().map(|r| r.course.code.as_str()))
+        .filter(|code| watchlist.matches(code))
+        .map(String::from)
+        .collect();
+
+    let diff = ScrapeDiff::new(added.clone(), removed.clone())
+        .with_changed(changed)
+        .with_reappeared(reappeared)
+        .with_watched(watched)
+        .with_excluded(excluded);
 
     info!(
         filter = %filter.description(),
@@ -55,8 +124,14 @@ pub fn filter_changes(result: &SyncResult, filter: &PointsFilter) -> ScrapeDiff
         input_removed = result.removed.len(),
         output_added = diff.added.len(),
         output_removed = diff.removed.len(),
+        output_changed = diff.changed.len(),
+        output_reappeared = diff.reappeared.len(),
+        watched_codes = ?diff.watched,
+        excluded_codes = ?diff.excluded,
         filtered_out_added = result.added.len() - diff.added.len(),
         filtered_out_removed = result.removed.len() - diff.removed.len(),
+        filtered_out_changed = result.changed.len() - diff.changed.len(),
+        filtered_out_reappeared = result.reappeared.len() - diff.reappeared.len(),
         added_codes = ?added.iter().map(|c| format!("{}({:.1}pts)", c.code, c.points)).collect::<Vec<_>>(),
         removed_codes = ?removed.iter().map(|c| format!("{}({:.1}pts)", c.code, c.points)).collect::<Vec<_>>(),
         "Filter applied to changes"
@@ -68,6 +143,8 @@ pub fn filter_changes(result: &SyncResult, filter: &PointsFilter) -> ScrapeDiff
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::PointsFilter;
+    use crate::exclude::Exclusions;
 
     fn make_course(code: &str, points: f32) -> Course {
         Course::new(
@@ -84,12 +161,14 @@ mod tests {
         let result = SyncResult {
             added: vec![make_course("A", 2.5), make_course("B", 10.0)],
             removed: vec![make_course("C", 2.5), make_course("D", 5.0)],
+            changed: vec![],
+            reappeared: vec![],
             is_first_run: false,
             total_courses: 10,
         };
 
         let filter = PointsFilter::Exact(2.5);
-        let diff = filter_changes(&result, &filter);
+        let diff = filter_changes(&result, &filter.into(), &Watchlist::default());
 
         assert_eq!(diff.added.len(), 1);
         assert_eq!(diff.added[0].code, "A");
@@ -106,6 +185,8 @@ mod tests {
                 make_course("C", 10.0),
             ],
             removed: vec![],
+            changed: vec![],
+            reappeared: vec![],
             is_first_run: false,
             total_courses: 10,
         };
@@ -114,7 +195,7 @@ mod tests {
             min: None,
             max: Some(5.0),
         };
-        let diff = filter_changes(&result, &filter);
+        let diff = filter_changes(&result, &filter.into(), &Watchlist::default());
 
         assert_eq!(diff.added.len(), 2);
         assert!(diff.added.iter().any(|c| c.code == "A"));
@@ -126,12 +207,14 @@ mod tests {
         let result = SyncResult {
             added: vec![make_course("A", 2.5), make_course("B", 10.0)],
             removed: vec![make_course("C", 5.0)],
+            changed: vec![],
+            reappeared: vec![],
             is_first_run: false,
             total_courses: 10,
         };
 
         let filter = PointsFilter::None;
-        let diff = filter_changes(&result, &filter);
+        let diff = filter_changes(&result, &filter.into(), &Watchlist::default());
 
         assert_eq!(diff.added.len(), 2);
         assert_eq!(diff.removed.len(), 1);
@@ -153,12 +236,14 @@ mod tests {
                 make_course("HFLESER1031", 2.5), // Should be included
                 make_course("JUR1120", 10.0),    // Should be filtered out
             ],
+            changed: vec![],
+            reappeared: vec![],
             is_first_run: false,
             total_courses: 100,
         };
 
         let filter = PointsFilter::Exact(2.5);
-        let diff = filter_changes(&result, &filter);
+        let diff = filter_changes(&result, &filter.into(), &Watchlist::default());
 
         // Should notify about the NEW 2.5 point course
         assert_eq!(diff.added.len(), 1, "Should have exactly 1 added 2.5pt course");
@@ -197,12 +282,14 @@ mod tests {
         let result = SyncResult {
             added: vec![course1],
             removed: vec![course2],
+            changed: vec![],
+            reappeared: vec![],
             is_first_run: false,
             total_courses: 10,
         };
 
         let filter = PointsFilter::Exact(2.5);
-        let diff = filter_changes(&result, &filter);
+        let diff = filter_changes(&result, &filter.into(), &Watchlist::default());
 
         // Both should be present because they have different codes
         assert_eq!(diff.added.len(), 1);
@@ -210,4 +297,88 @@ mod tests {
         assert_eq!(diff.removed.len(), 1);
         assert_eq!(diff.removed[0].code, "CODE2");
     }
+
+    /// A points change is relevant when either the old or the new value matches
+    #[test]
+    fn test_filter_changed_matches_before_or_after() {
+        let before = make_course("A", 2.5);
+        let after = Course {
+            points: 5.0,
+            ..before.clone()
+        };
+        let unrelated = make_course("B", 10.0);
+        let unrelated_after = Course {
+            name: "Renamed".to_string(),
+            ..unrelated.clone()
+        };
+
+        let result = SyncResult {
+            added: vec![],
+            removed: vec![],
+            changed: vec![
+                CourseChange::between(&before, &after).unwrap(),
+                CourseChange::between(&unrelated, &unrelated_after).unwrap(),
+            ],
+            reappeared: vec![],
+            is_first_run: false,
+            total_courses: 10,
+        };
+
+        let diff = filter_changes(&result, &PointsFilter::Exact(2.5).into(), &Watchlist::default());
+
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].code(), "A");
+        assert_eq!(diff.total_changes(), 1);
+    }
+
+    /// Watched courses pass any filter and are marked for highlighting
+    #[test]
+    fn test_watchlist_bypasses_filter() {
+        let result = SyncResult {
+            added: vec![make_course("IN1000", 10.0), make_course("SOS1000", 10.0), make_course("A", 2.5)],
+            removed: vec![],
+            changed: vec![],
+            reappeared: vec![],
+            is_first_run: false,
+            total_courses: 10,
+        };
+
+        let watchlist = Watchlist::new(["IN1*"]).unwrap();
+        let diff = filter_changes(&result, &PointsFilter::Exact(2.5).into(), &watchlist);
+
+        let codes: Vec<_> = diff.added.iter().map(|c| c.code.as_str()).collect();
+        assert_eq!(codes, vec!["IN1000", "A"]);
+        assert_eq!(diff.watched.iter().collect::<Vec<_>>(), vec!["IN1000"]);
+
+        let (watched, rest) = diff.split_watched();
+        assert_eq!(watched.added[0].code, "IN1000");
+        assert_eq!(rest.added.len(), 1);
+        assert!(!rest.has_watch_hits());
+    }
+
+    /// Exclusions drop matching courses after the filter; watched courses still pass
+    #[test]
+    fn test_exclusions_apply_after_filter() {
+        let result = SyncResult {
+            added: vec![make_course("IN1000", 10.0), make_course("IN9000", 10.0), make_course("EVU1000", 10.0)],
+            removed: vec![make_course("MAT9100", 5.0)],
+            changed: vec![],
+            reappeared: vec![],
+            is_first_run: false,
+            total_courses: 10,
+        };
+
+        let filter = CourseFilter {
+            exclusions: Exclusions::new(["level:9000", "code:EVU*"]).unwrap(),
+            ..CourseFilter::from(PointsFilter::Exact(10.0))
+        };
+        let watchlist = Watchlist::new(["IN9000"]).unwrap();
+        let diff = filter_changes(&result, &filter, &watchlist);
+
+        let codes: Vec<_> = diff.added.iter().map(|c| c.code.as_str()).collect();
+        assert_eq!(codes, vec!["IN1000", "IN9000"]);
+        // MAT9100 never matched the points filter, so it is not counted as excluded
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.excluded, vec!["EVU1000"]);
+    }
 }
@@ -0,0 +1,309 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use futures::stream::{self, StreamExt};
+use reqwest::Url;
+use scraper::{ElementRef, Html, Selector};
+use std::time::Instant;
+use tracing::{debug, info, instrument, warn};
+
+use crate::db::Database;
+use crate::models::{Course, CourseDetails};
+use crate::source::HttpSettings;
+
+/// Fetches course detail pages from uio.no, with a concurrency limit and a database cache
+pub struct Enricher {
+    client: reqwest::Client,
+    /// Page the course links were scraped from; relative links are resolved against it
+    base_url: Url,
+    concurrency: usize,
+    cache_max_age: chrono::Duration,
+}
+
+impl Enricher {
+    pub fn new(
+        base_url: &str,
+        concurrency: usize,
+        cache_max_age: chrono::Duration,
+        http: &HttpSettings,
+    ) -> Result<Self> {
+        let base_url = Url::parse(base_url)
+            .with_context(|| format!("Invalid base URL for course detail pages: {}", base_url))?;
+        let client = http
+            .build_client()
+            .context("Failed to create HTTP client for course detail pages")?;
+
+        info!(
+            base_url = %base_url,
+            concurrency = concurrency,
+            cache_max_age_days = cache_max_age.num_days(),
+            "Course enrichment enabled"
+        );
+
+        Ok(Self {
+            client,
+            base_url,
+            concurrency: concurrency.max(1),
+            cache_max_age,
+        })
+    }
+
+    /// Attach details to the given courses of a source, from the cache when fresh and from
+    /// uio.no otherwise. A detail page that can't be fetched leaves its course without details.
+    /// Returns the number of courses that ended up with details.
+    #[instrument(skip(self, db, courses))]
+    pub async fn enrich<'a>(
+        &self,
+        db: &Database,
+        source: &str,
+        courses: impl IntoIterator<Item = &'a mut Course>,
+    ) -> Result<usize> {
+        let start = Instant::now();
+        let mut courses: Vec<&mut Course> = courses.into_iter().collect();
+        let codes: Vec<String> = courses.iter().map(|c| c.code.clone()).collect();
+        let mut cached = db
+            .get_course_details(source, &codes, Utc::now() - self.cache_max_age)
+            .await?;
+
+        let to_fetch: Vec<(String, Url)> = courses
+            .iter()
+            .filter(|c| !cached.contains_key(&c.code))
+            .filter_map(|c| match self.base_url.join(&c.url) {
+                Ok(url) if !c.url.is_empty() => Some((c.code.clone(), url)),
+                _ => {
+                    debug!(course_code = %c.code, url = %c.url, "No usable detail page link");
+                    None
+                }
+            })
+            .collect();
+
+        let fetched: Vec<(String, CourseDetails)> = stream::iter(to_fetch)
+            .map(|(code, url)| async move {
+                match self.fetch_details(&url).await {
+                    Ok(details) => Some((code, details)),
+                    Err(e) => {
+                        warn!(
+                            course_code = %code,
+                            url = %url,
+                            error = %format!("{:#}", e),
+                            "Failed to fetch course detail page"
+                        );
+                        None
+                    }
+                }
+            })
+            .buffer_unordered(self.concurrency)
+            .filter_map(|result| async move { result })
+            .collect()
+            .await;
+
+        db.store_course_details(source, &fetched).await?;
+
+        let cache_hits = cached.len();
+        let fetched_count = fetched.len();
+        cached.extend(fetched);
+
+        let mut enriched = 0;
+        for course in courses.iter_mut() {
+            if let Some(details) = cached.get(&course.code) {
+                course.details = Some(details.clone());
+                enriched += 1;
+            }
+        }
+
+        info!(
+            courses = courses.len(),
+            cache_hits = cache_hits,
+            fetched = fetched_count,
+            enriched = enriched,
+            duration_ms = start.elapsed().as_millis(),
+            "Course enrichment completed"
+        );
+
+        Ok(enriched)
+    }
+
+    async fn fetch_details(&self, url: &Url) -> Result<CourseDetails> {
+        let html = self
+            .client
+            .get(url.clone())
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        let details = parse_details(&html);
+        if details.is_empty() {
+            warn!(url = %url, "Course detail page has no recognisable fields");
+        } else {
+            debug!(url = %url, details = ?details, "Parsed course detail page");
+        }
+        Ok(details)
+    }
+}
+
+/// Extract details from a course page: the facts box first, then the page sections
+pub fn parse_details(html: &str) -> CourseDetails {
+    let document = Html::parse_document(html);
+    let dt_selector = Selector::parse("dl dt").expect("Invalid dt selector");
+
+    let mut details = CourseDetails::default();
+
+    // Facts box: <dt>Undervisningsspråk</dt><dd>Norsk</dd>
+    for dt in document.select(&dt_selector) {
+        let label = element_text(&dt).to_lowercase();
+        let Some(value) = dt
+            .next_siblings()
+            .filter_map(ElementRef::wrap)
+            .find(|el| el.value().name() == "dd")
+            .map(|dd| element_text(&dd))
+            .filter(|v| !v.is_empty())
+        else {
+            continue;
+        };
+
+        let field = match label.trim_end_matches(':') {
+            "undervisningsspråk" | "teaching language" | "language of instruction" => {
+                &mut details.language
+            }
+            "undervisning" | "undervisningssemester" | "teaching" | "semester" => {
+                &mut details.semester
+            }
+            "undervisningsperiode" | "teaching period" => &mut details.teaching_period,
+            "eksamensform" | "exam form" | "form of assessment" => &mut details.exam_form,
+            "forkunnskapskrav" | "prerequisites" => &mut details.prerequisites,
+            _ => continue,
+        };
+        field.get_or_insert(value);
+    }
+
+    // Longer descriptions live in sections under <h2 id="...">
+    if details.exam_form.is_none() {
+        details.exam_form = section_text(&document, &["eksamen", "examination"]);
+    }
+    if details.prerequisites.is_none() {
+        details.prerequisites = section_text(&document, &["forkunnskapskrav", "prerequisites"]);
+    }
+
+    details
+}
+
+/// First paragraph of the section started by an h2 with one of the given ids
+fn section_text(document: &Html, ids: &[&str]) -> Option<String> {
+    let h2_selector = Selector::parse("h2[id]").expect("Invalid h2 selector");
+
+    let heading = document
+        .select(&h2_selector)
+        .find(|h2| h2.value().attr("id").is_some_and(|id| ids.contains(&id)))?;
+
+    heading
+        .next_siblings()
+        .filter_map(ElementRef::wrap)
+        .take_while(|el| el.value().name() != "h2")
+        .find(|el| el.value().name() == "p")
+        .map(|p| element_text(&p))
+        .filter(|text| !text.is_empty())
+}
+
+fn element_text(element: &ElementRef) -> String {
+    element
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::config::DEFAULT_SOURCE;
+    use axum::http::StatusCode;
+    use axum::{routing::get, Router};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    const COURSE_PAGE: &str = include_str!("../tests/fixtures/course-page.html");
+
+    #[test]
+    fn test_parse_details_from_course_page() {
+        let details = parse_details(COURSE_PAGE);
+        assert_eq!(details.language.as_deref(), Some("Norsk"));
+        assert_eq!(details.semester.as_deref(), Some("Høst"));
+        assert_eq!(details.teaching_period.as_deref(), Some("Uke 34–48"));
+        assert_eq!(details.exam_form.as_deref(), Some("Skriftlig digital eksamen, 4 timer."));
+        assert_eq!(
+            details.prerequisites.as_deref(),
+            Some("Matematikk R1 (eller Matematikk S1 og S2).")
+        );
+        assert_eq!(
+            details.summary(),
+            "Norsk | Høst | Uke 34–48 | Skriftlig digital eksamen, 4 timer."
+        );
+
+        assert!(parse_details("<html><body><p>Ingen fakta</p></body></html>").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_enrich_fetches_once_then_uses_cache() {
+        let hits = Arc::new(AtomicU32::new(0));
+        let page_hits = hits.clone();
+        let router = Router::new()
+            .route(
+                "/emner/IN1000/index.html",
+                get(move || {
+                    let hits = page_hits.clone();
+                    async move {
+                        hits.fetch_add(1, Ordering::SeqCst);
+                        COURSE_PAGE
+                    }
+                }),
+            )
+            .route("/emner/GONE1000/index.html", get(|| async { (StatusCode::NOT_FOUND, "gone") }));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+
+        let db = Database::open_in_memory().await.unwrap();
+        let enricher = Enricher::new(
+            &format!("http://{}/ledige-plasser/", addr),
+            2,
+            chrono::Duration::days(30),
+            &HttpSettings::default(),
+        )
+        .unwrap();
+
+        let course = |code: &str| {
+            Course::new(
+                code.to_string(),
+                "Course".to_string(),
+                10.0,
+                format!("/emner/{}/index.html", code),
+                "Faculty".to_string(),
+            )
+        };
+
+        let mut courses = vec![course("IN1000"), course("GONE1000")];
+        assert_eq!(enricher.enrich(&db, DEFAULT_SOURCE, &mut courses).await.unwrap(), 1);
+        assert_eq!(
+            courses[0].details.as_ref().unwrap().language.as_deref(),
+            Some("Norsk")
+        );
+        // A missing detail page leaves the course as scraped
+        assert!(courses[1].details.is_none());
+
+        let mut again = vec![course("IN1000")];
+        assert_eq!(enricher.enrich(&db, DEFAULT_SOURCE, &mut again).await.unwrap(), 1);
+        assert_eq!(again[0].details, courses[0].details);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // Another source has its own cache entries (its pages may differ)
+        let mut english = vec![course("IN1000")];
+        assert_eq!(enricher.enrich(&db, "english", &mut english).await.unwrap(), 1);
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,144 @@
+use std::error::Error as StdError;
+use thiserror::Error;
+
+/// Classified failure while fetching or parsing the course page
+#[derive(Debug, Error)]
+pub enum ScrapeError {
+    #[error("request timed out: {0}")]
+    Timeout(String),
+
+    #[error("DNS lookup failed: {0}")]
+    Dns(String),
+
+    #[error("connection failed: {0}")]
+    Connect(String),
+
+    #[error("server error: HTTP {0}")]
+    ServerError(u16),
+
+    #[error("client error: HTTP {0}")]
+    ClientError(u16),
+
+    #[error("failed to parse page: {0}")]
+    Parse(String),
+
+    #[error("unrecognised table layout: {0}")]
+    Layout(String),
+
+    #[error("failed to read page: {0}")]
+    Io(String),
+}
+
+impl ScrapeError {
+    /// Short machine-readable kind, stored in the run log
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ScrapeError::Timeout(_) => "timeout",
+            ScrapeError::Dns(_) => "dns",
+            ScrapeError::Connect(_) => "connect",
+            ScrapeError::ServerError(_) => "server_error",
+            ScrapeError::ClientError(_) => "client_error",
+            ScrapeError::Parse(_) => "parse",
+            ScrapeError::Layout(_) => "layout",
+            ScrapeError::Io(_) => "io",
+        }
+    }
+
+    /// Whether retrying later can reasonably be expected to succeed
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ScrapeError::Timeout(_)
+            | ScrapeError::Dns(_)
+            | ScrapeError::Connect(_)
+            | ScrapeError::ServerError(_) => true,
+            // Request Timeout and Too Many Requests are the client errors worth retrying
+            ScrapeError::ClientError(status) => matches!(status, 408 | 429),
+            ScrapeError::Parse(_) | ScrapeError::Layout(_) | ScrapeError::Io(_) => false,
+        }
+    }
+
+    /// Classify an HTTP status that is neither success nor 304
+    pub fn from_status(status: reqwest::StatusCode) -> Self {
+        if status.is_server_error() {
+            ScrapeError::ServerError(status.as_u16())
+        } else {
+            ScrapeError::ClientError(status.as_u16())
+        }
+    }
+
+    /// Classify a reqwest transport error
+    pub fn from_reqwest(err: &reqwest::Error) -> Self {
+        let detail = error_chain(err);
+
+        if err.is_timeout() {
+            ScrapeError::Timeout(detail)
+        } else if is_dns_failure(&detail) {
+            ScrapeError::Dns(detail)
+        } else {
+            ScrapeError::Connect(detail)
+        }
+    }
+
+    /// Find a ScrapeError anywhere in an anyhow error chain
+    pub fn find(err: &anyhow::Error) -> Option<&ScrapeError> {
+        err.chain().find_map(|cause| cause.downcast_ref::<ScrapeError>())
+    }
+}
+
+/// Flatten an error and its sources into one line (reqwest hides the useful part in sources)
+fn error_chain(err: &dyn StdError) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+fn is_dns_failure(detail: &str) -> bool {
+    let detail = detail.to_lowercase();
+    detail.contains("dns error")
+        || detail.contains("failed to lookup address")
+        || detail.contains("name or service not known")
+        || detail.contains("no such host")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transient_classification() {
+        assert!(ScrapeError::Timeout("t".into()).is_transient());
+        assert!(ScrapeError::Dns("d".into()).is_transient());
+        assert!(ScrapeError::ServerError(503).is_transient());
+        assert!(ScrapeError::ClientError(429).is_transient());
+
+        assert!(!ScrapeError::ClientError(404).is_transient());
+        assert!(!ScrapeError::Parse("p".into()).is_transient());
+        assert!(!ScrapeError::Layout("l".into()).is_transient());
+    }
+
+    #[test]
+    fn test_from_status() {
+        assert!(matches!(
+            ScrapeError::from_status(reqwest::StatusCode::BAD_GATEWAY),
+            ScrapeError::ServerError(502)
+        ));
+        assert!(matches!(
+            ScrapeError::from_status(reqwest::StatusCode::NOT_FOUND),
+            ScrapeError::ClientError(404)
+        ));
+    }
+
+    #[test]
+    fn test_find_through_context() {
+        let err = anyhow::Error::new(ScrapeError::ServerError(500)).context("Fetch phase failed");
+        assert_eq!(ScrapeError::find(&err).map(|e| e.kind()), Some("server_error"));
+
+        let other = anyhow::anyhow!("something else");
+        assert!(ScrapeError::find(&other).is_none());
+    }
+}
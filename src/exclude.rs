@@ -0,0 +1,132 @@
+use anyhow::{bail, Context, Result};
+use std::fmt;
+
+use crate::models::Course;
+use crate::search::fold;
+use crate::watchlist::WatchPattern;
+
+/// Courses that are never notified (unless they are on the watchlist), e.g. PhD-level
+/// courses or continuing education that come and go all the time
+#[derive(Debug, Clone, Default)]
+pub struct Exclusions {
+    rules: Vec<ExclusionRule>,
+}
+
+/// One exclusion rule: "code:EVU*", "faculty:odontologi" or "level:9000"
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExclusionRule {
+    /// Course code or pattern with * and ?
+    Code(WatchPattern),
+    /// Faculty name contains this text (ignoring case and Norwegian spelling variants)
+    Faculty(String),
+    Level(u16),
+}
+
+impl ExclusionRule {
+    pub fn parse(rule: &str) -> Result<Self> {
+        let (kind, value) = rule
+            .split_once(':')
+            .with_context(|| format!("Invalid exclusion '{}': expected code:, faculty: or level:", rule.trim()))?;
+        let value = value.trim();
+        if value.is_empty() {
+            bail!("Invalid exclusion '{}': missing value", rule.trim());
+        }
+
+        match kind.trim().to_lowercase().as_str() {
+            "code" => Ok(ExclusionRule::Code(WatchPattern::parse(value)?)),
+            "faculty" => Ok(ExclusionRule::Faculty(value.to_string())),
+            "level" => match value.parse::<u16>() {
+                Ok(level) if level > 0 && level % 1000 == 0 => Ok(ExclusionRule::Level(level)),
+                _ => bail!("Invalid exclusion '{}': level must be 1000, 2000, ..., 9000", rule.trim()),
+            },
+            other => bail!(
+                "Invalid exclusion '{}': unknown kind '{}' (use code, faculty or level)",
+                rule.trim(),
+                other
+            ),
+        }
+    }
+
+    pub fn excludes(&self, course: &Course) -> bool {
+        match self {
+            ExclusionRule::Code(pattern) => pattern.matches(&course.code),
+            ExclusionRule::Faculty(text) => fold(&course.faculty).contains(&fold(text)),
+            ExclusionRule::Level(level) => course.level() == Some(*level),
+        }
+    }
+}
+
+impl fmt::Display for ExclusionRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExclusionRule::Code(pattern) => write!(f, "code:{}", pattern.as_str()),
+            ExclusionRule::Faculty(text) => write!(f, "faculty:{}", text),
+            ExclusionRule::Level(level) => write!(f, "level:{}", level),
+        }
+    }
+}
+
+impl Exclusions {
+    pub fn new<S: AsRef<str>>(rules: impl IntoIterator<Item = S>) -> Result<Self> {
+        let rules = rules
+            .into_iter()
+            .filter(|rule| !rule.as_ref().trim().is_empty())
+            .map(|rule| ExclusionRule::parse(rule.as_ref()))
+            .collect::<Result<_>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn rules(&self) -> impl Iterator<Item = &ExclusionRule> {
+        self.rules.iter()
+    }
+
+    /// The first rule excluding the course
+    pub fn matching(&self, course: &Course) -> Option<&ExclusionRule> {
+        self.rules.iter().find(|rule| rule.excludes(course))
+    }
+
+    pub fn description(&self) -> String {
+        let rules: Vec<String> = self.rules().map(|r| r.to_string()).collect();
+        rules.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn course(code: &str, faculty: &str) -> Course {
+        Course::new(code.to_string(), String::new(), 10.0, String::new(), faculty.to_string())
+    }
+
+    #[test]
+    fn test_rules_by_code_faculty_and_level() {
+        let exclusions = Exclusions::new(["level:9000", "code:EVU*", "faculty:odontologisk"]).unwrap();
+        assert_eq!(exclusions.description(), "level:9000, code:EVU*, faculty:odontologisk");
+
+        assert_eq!(
+            exclusions.matching(&course("MAT9100", "MatNat")),
+            Some(&ExclusionRule::Level(9000))
+        );
+        assert!(exclusions.matching(&course("evu1010", "HF")).is_some());
+        assert!(exclusions.matching(&course("ODO1000", "Det odontologiske fakultet")).is_some());
+        assert!(Exclusions::new(["faculty:ODONTOLOGISKE"])
+            .unwrap()
+            .matching(&course("ODO1000", "Det odontologiske fakultet"))
+            .is_some());
+        assert!(exclusions.matching(&course("MAT1100", "MatNat")).is_none());
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        assert!(ExclusionRule::parse("9000").is_err());
+        assert!(ExclusionRule::parse("level:9001").is_err());
+        assert!(ExclusionRule::parse("points:5").is_err());
+        assert!(ExclusionRule::parse("code:").is_err());
+        assert!(ExclusionRule::parse("code:EVU;1").is_err());
+    }
+}
@@ -0,0 +1,691 @@
+use regex::{Regex, RegexBuilder};
+use std::fmt;
+
+use crate::models::Course;
+use crate::search::Keywords;
+
+/// Fields that can be used in filter expressions, for error messages
+const FIELD_NAMES: &str = "points, level, code, name, faculty, department, url, language, \
+                           semester, period, exam, prerequisites, attr[\"column\"]";
+
+/// A parsed filter expression over course fields, e.g.
+/// `points <= 5 and faculty ~ "matematisk" and not code ^= "EXPH"`
+#[derive(Debug, Clone)]
+pub struct FilterExpr {
+    source: String,
+    root: Expr,
+}
+
+/// Invalid filter expression, pointing at the offending position
+#[derive(Debug)]
+pub struct FilterParseError {
+    pub expression: String,
+    /// Character offset of the problem in the expression
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for FilterParseError {
+    /// The message followed by the expression with a caret under the problem
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at column {}\n  {}\n  {}^",
+            self.message,
+            self.position + 1,
+            self.expression,
+            " ".repeat(self.position)
+        )
+    }
+}
+
+impl std::error::Error for FilterParseError {}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare(Comparison),
+}
+
+#[derive(Debug, Clone)]
+struct Comparison {
+    field: Field,
+    op: Op,
+    value: Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Field {
+    Points,
+    Level,
+    Code,
+    Name,
+    Faculty,
+    Department,
+    Url,
+    Language,
+    Semester,
+    TeachingPeriod,
+    ExamForm,
+    Prerequisites,
+    /// Extra table column by normalized header text
+    Attribute(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    /// `~`: contains, ignoring case
+    Contains,
+    /// `^=`: starts with, ignoring case
+    Prefix,
+    /// `$=`: ends with, ignoring case
+    Suffix,
+    /// `=~`: regular expression, ignoring case
+    Regex,
+    /// `~~`: keywords, ignoring case and Norwegian spelling variants (see `search::Keywords`)
+    Keywords,
+}
+
+#[derive(Debug, Clone)]
+enum Value {
+    Number(f64),
+    /// Lowercased text
+    Text(String),
+    Pattern(Regex),
+    Keywords(Keywords),
+}
+
+impl FilterExpr {
+    pub fn parse(expression: &str) -> Result<Self, FilterParseError> {
+        let tokens = tokenize(expression)?;
+        let mut parser = Parser {
+            expression,
+            tokens,
+            pos: 0,
+            depth: 0,
+        };
+        let root = parser.parse_or()?;
+        if let Some(token) = parser.peek() {
+            return Err(parser.error_at(
+                token.start,
+                format!("unexpected {} after the end of the expression", token.kind),
+            ));
+        }
+
+        Ok(Self {
+            source: expression.trim().to_string(),
+            root,
+        })
+    }
+
+    pub fn matches(&self, course: &Course) -> bool {
+        self.root.matches(course)
+    }
+}
+
+impl fmt::Display for FilterExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl Expr {
+    fn matches(&self, course: &Course) -> bool {
+        match self {
+            Expr::And(a, b) => a.matches(course) && b.matches(course),
+            Expr::Or(a, b) => a.matches(course) || b.matches(course),
+            Expr::Not(e) => !e.matches(course),
+            Expr::Compare(c) => c.matches(course),
+        }
+    }
+}
+
+impl Comparison {
+    /// Comparisons against a field the course doesn't have are false
+    fn matches(&self, course: &Course) -> bool {
+        match (&self.value, self.field.number(course)) {
+            (Value::Number(expected), Some(actual)) => match self.op {
+                Op::Eq => (actual - expected).abs() < 0.01,
+                Op::Ne => (actual - expected).abs() >= 0.01,
+                Op::Lt => actual < *expected,
+                Op::Le => actual <= *expected,
+                Op::Gt => actual > *expected,
+                Op::Ge => actual >= *expected,
+                _ => false,
+            },
+            (Value::Text(expected), _) => {
+                let Some(actual) = self.field.text(course) else {
+                    return false;
+                };
+                let actual = actual.to_lowercase();
+                match self.op {
+                    Op::Eq => actual == *expected,
+                    Op::Ne => actual != *expected,
+                    Op::Contains => actual.contains(expected.as_str()),
+                    Op::Prefix => actual.starts_with(expected.as_str()),
+                    Op::Suffix => actual.ends_with(expected.as_str()),
+                    _ => false,
+                }
+            }
+            (Value::Pattern(regex), _) => self
+                .field
+                .text(course)
+                .is_some_and(|actual| regex.is_match(&actual)),
+            (Value::Keywords(keywords), _) => self
+                .field
+                .text(course)
+                .is_some_and(|actual| keywords.matches(&actual)),
+            _ => false,
+        }
+    }
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name.to_lowercase().as_str() {
+            "points" | "sp" => Field::Points,
+            "level" => Field::Level,
+            "code" => Field::Code,
+            "name" => Field::Name,
+            "faculty" => Field::Faculty,
+            "department" | "dept" => Field::Department,
+            "url" => Field::Url,
+            "language" => Field::Language,
+            "semester" => Field::Semester,
+            "period" | "teaching_period" => Field::TeachingPeriod,
+            "exam" | "exam_form" => Field::ExamForm,
+            "prerequisites" => Field::Prerequisites,
+            _ => return None,
+        })
+    }
+
+    fn is_numeric(&self) -> bool {
+        matches!(self, Field::Points | Field::Level)
+    }
+
+    fn number(&self, course: &Course) -> Option<f64> {
+        match self {
+            Field::Points => Some(course.points as f64),
+            Field::Level => course.level().map(f64::from),
+            _ => None,
+        }
+    }
+
+    fn text(&self, course: &Course) -> Option<String> {
+        let details = course.details.as_ref();
+        match self {
+            Field::Points => Some(course.points.to_string()),
+            Field::Level => course.level().map(|l| l.to_string()),
+            Field::Code => Some(course.code.clone()),
+            Field::Name => Some(course.name.clone()),
+            Field::Faculty => Some(course.faculty.clone()),
+            Field::Department => Some(course.department().to_string()),
+            Field::Url => Some(course.url.clone()),
+            Field::Language => details.and_then(|d| d.language.clone()),
+            Field::Semester => details.and_then(|d| d.semester.clone()),
+            Field::TeachingPeriod => details.and_then(|d| d.teaching_period.clone()),
+            Field::ExamForm => details.and_then(|d| d.exam_form.clone()),
+            Field::Prerequisites => details.and_then(|d| d.prerequisites.clone()),
+            Field::Attribute(key) => course.attributes.get(key).cloned(),
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Op::Eq => "=",
+            Op::Ne => "!=",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+            Op::Contains => "~",
+            Op::Prefix => "^=",
+            Op::Suffix => "$=",
+            Op::Regex => "=~",
+            Op::Keywords => "~~",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Word(String),
+    Str(String),
+    Number(f64),
+    Op(Op),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenKind::Word(w) => write!(f, "'{}'", w),
+            TokenKind::Str(s) => write!(f, "\"{}\"", s),
+            TokenKind::Number(n) => write!(f, "{}", n),
+            TokenKind::Op(op) => write!(f, "'{}'", op),
+            TokenKind::And => f.write_str("'and'"),
+            TokenKind::Or => f.write_str("'or'"),
+            TokenKind::Not => f.write_str("'not'"),
+            TokenKind::LParen => f.write_str("'('"),
+            TokenKind::RParen => f.write_str("')'"),
+            TokenKind::LBracket => f.write_str("'['"),
+            TokenKind::RBracket => f.write_str("']'"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    /// Character offset in the expression
+    start: usize,
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, FilterParseError> {
+    let error = |position: usize, message: String| FilterParseError {
+        expression: expression.to_string(),
+        position,
+        message,
+    };
+
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let two: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+
+        let kind = if c.is_whitespace() {
+            i += 1;
+            continue;
+        } else if let Some(op) = match two.as_str() {
+            "<=" => Some(Op::Le),
+            ">=" => Some(Op::Ge),
+            "!=" => Some(Op::Ne),
+            "==" => Some(Op::Eq),
+            "=~" => Some(Op::Regex),
+            "~~" => Some(Op::Keywords),
+            "^=" => Some(Op::Prefix),
+            "$=" => Some(Op::Suffix),
+            _ => None,
+        } {
+            i += 2;
+            TokenKind::Op(op)
+        } else if two == "&&" {
+            i += 2;
+            TokenKind::And
+        } else if two == "||" {
+            i += 2;
+            TokenKind::Or
+        } else if let Some(kind) = match c {
+            '<' => Some(TokenKind::Op(Op::Lt)),
+            '>' => Some(TokenKind::Op(Op::Gt)),
+            '=' => Some(TokenKind::Op(Op::Eq)),
+            '~' => Some(TokenKind::Op(Op::Contains)),
+            '!' => Some(TokenKind::Not),
+            '(' => Some(TokenKind::LParen),
+            ')' => Some(TokenKind::RParen),
+            '[' => Some(TokenKind::LBracket),
+            ']' => Some(TokenKind::RBracket),
+            _ => None,
+        } {
+            i += 1;
+            kind
+        } else if c == '"' || c == '\'' {
+            let mut text = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => return Err(error(start, "unterminated string".to_string())),
+                    // Only the quote itself is escaped, so regex escapes like \d pass through
+                    Some('\\') if chars.get(i + 1) == Some(&c) => {
+                        text.push(c);
+                        i += 2;
+                    }
+                    Some(&q) if q == c => {
+                        i += 1;
+                        break;
+                    }
+                    Some(&other) => {
+                        text.push(other);
+                        i += 1;
+                    }
+                }
+            }
+            TokenKind::Str(text)
+        } else if c.is_ascii_digit() || (c == '.' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit())) {
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let number = text
+                .parse()
+                .map_err(|_| error(start, format!("invalid number '{}'", text)))?;
+            TokenKind::Number(number)
+        } else if c.is_alphanumeric() || c == '_' || c == '-' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '-') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            match word.to_lowercase().as_str() {
+                "and" => TokenKind::And,
+                "or" => TokenKind::Or,
+                "not" => TokenKind::Not,
+                _ => TokenKind::Word(word),
+            }
+        } else {
+            return Err(error(start, format!("unexpected character '{}'", c)));
+        };
+
+        tokens.push(Token { kind, start });
+    }
+
+    Ok(tokens)
+}
+
+/// Deepest nesting of parentheses and `not` the parser accepts before giving up
+const MAX_DEPTH: usize = 64;
+
+/// Recursive descent parser: `or` binds loosest, then `and`, then `not`
+struct Parser<'a> {
+    expression: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Current nesting of parentheses and `not`
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    /// Position of the next token, or the end of the expression
+    fn here(&self) -> usize {
+        self.peek()
+            .map(|t| t.start)
+            .unwrap_or_else(|| self.expression.chars().count())
+    }
+
+    fn error_at(&self, position: usize, message: String) -> FilterParseError {
+        FilterParseError {
+            expression: self.expression.to_string(),
+            position,
+            message,
+        }
+    }
+
+    fn expected(&self, what: &str) -> FilterParseError {
+        match self.peek() {
+            Some(token) => self.error_at(token.start, format!("expected {}, found {}", what, token.kind)),
+            None => self.error_at(self.here(), format!("expected {}, found end of expression", what)),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, FilterParseError> {
+        let mut expr = self.parse_and()?;
+        while self.peek().is_some_and(|t| t.kind == TokenKind::Or) {
+            self.pos += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, FilterParseError> {
+        let mut expr = self.parse_unary()?;
+        while self.peek().is_some_and(|t| t.kind == TokenKind::And) {
+            self.pos += 1;
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, FilterParseError> {
+        let nested = matches!(self.peek().map(|t| &t.kind), Some(TokenKind::Not | TokenKind::LParen));
+        if !nested {
+            return Ok(Expr::Compare(self.parse_comparison()?));
+        }
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error_at(
+                self.here(),
+                format!("expression is nested more than {} levels deep", MAX_DEPTH),
+            ));
+        }
+
+        self.depth += 1;
+        let expr = self.parse_nested();
+        self.depth -= 1;
+        expr
+    }
+
+    /// `not` or a parenthesized expression
+    fn parse_nested(&mut self) -> Result<Expr, FilterParseError> {
+        match self.next().map(|t| t.kind) {
+            Some(TokenKind::Not) => Ok(Expr::Not(Box::new(self.parse_unary()?))),
+            _ => {
+                let expr = self.parse_or()?;
+                match self.peek() {
+                    Some(t) if t.kind == TokenKind::RParen => {
+                        self.pos += 1;
+                        Ok(expr)
+                    }
+                    _ => Err(self.expected("')'")),
+                }
+            }
+        }
+    }
+
+    fn parse_comparison(&mut self) -> Result<Comparison, FilterParseError> {
+        let field = self.parse_field()?;
+
+        let (op, op_start) = match self.peek() {
+            Some(Token { kind: TokenKind::Op(op), start }) => (*op, *start),
+            _ => return Err(self.expected("a comparison operator (=, !=, <, <=, >, >=, ~, ^=, $=, =~, ~~)")),
+        };
+        self.pos += 1;
+
+        let value_start = self.here();
+        let raw = match self.next().map(|t| t.kind) {
+            Some(TokenKind::Number(n)) => RawValue::Number(n),
+            Some(TokenKind::Str(s)) | Some(TokenKind::Word(s)) => RawValue::Text(s),
+            _ => {
+                self.pos -= 1;
+                return Err(self.expected(&format!("a value after '{}'", op)));
+            }
+        };
+
+        let value = match (op, raw) {
+            (Op::Regex, RawValue::Text(pattern)) => RegexBuilder::new(&pattern)
+                .case_insensitive(true)
+                .build()
+                .map(Value::Pattern)
+                .map_err(|e| self.error_at(value_start, format!("invalid regex: {}", e)))?,
+            (Op::Regex, RawValue::Number(n)) => {
+                return Err(self.error_at(value_start, format!("expected a regex string, found {}", n)))
+            }
+            (Op::Lt | Op::Le | Op::Gt | Op::Ge, _) if !field.is_numeric() => {
+                return Err(self.error_at(
+                    op_start,
+                    format!("'{}' only works with numeric fields (points, level)", op),
+                ))
+            }
+            (Op::Contains | Op::Prefix | Op::Suffix | Op::Keywords, _) if field.is_numeric() => {
+                return Err(self.error_at(op_start, format!("'{}' only works with text fields", op)))
+            }
+            (Op::Keywords, raw) => {
+                let text = match raw {
+                    RawValue::Text(text) => text,
+                    RawValue::Number(n) => n.to_string(),
+                };
+                Keywords::parse(&text)
+                    .map(Value::Keywords)
+                    .ok_or_else(|| self.error_at(value_start, "expected keywords after '~~'".to_string()))?
+            }
+            (_, RawValue::Number(n)) if field.is_numeric() => Value::Number(n),
+            (_, RawValue::Text(text)) if field.is_numeric() => {
+                return Err(self.error_at(value_start, format!("expected a number, found \"{}\"", text)))
+            }
+            (_, RawValue::Number(n)) => Value::Text(n.to_string()),
+            (_, RawValue::Text(text)) => Value::Text(text.to_lowercase()),
+        };
+
+        Ok(Comparison { field, op, value })
+    }
+
+    fn parse_field(&mut self) -> Result<Field, FilterParseError> {
+        let (name, start) = match self.peek() {
+            Some(Token { kind: TokenKind::Word(name), start }) => (name.clone(), *start),
+            _ => return Err(self.expected("a field name")),
+        };
+        self.pos += 1;
+
+        if name.eq_ignore_ascii_case("attr") {
+            if !self.peek().is_some_and(|t| t.kind == TokenKind::LBracket) {
+                return Err(self.expected("'[' after attr"));
+            }
+            self.pos += 1;
+            let key = match self.next().map(|t| t.kind) {
+                Some(TokenKind::Str(key)) | Some(TokenKind::Word(key)) => key,
+                _ => {
+                    self.pos -= 1;
+                    return Err(self.expected("a column name"));
+                }
+            };
+            if !self.peek().is_some_and(|t| t.kind == TokenKind::RBracket) {
+                return Err(self.expected("']'"));
+            }
+            self.pos += 1;
+            let key = key.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+            return Ok(Field::Attribute(key));
+        }
+
+        Field::parse(&name).ok_or_else(|| {
+            self.error_at(start, format!("unknown field '{}' (expected one of {})", name, FIELD_NAMES))
+        })
+    }
+}
+
+enum RawValue {
+    Number(f64),
+    Text(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CourseDetails;
+
+    fn course(code: &str, points: f32, faculty: &str) -> Course {
+        Course::new(
+            code.to_string(),
+            format!("Course {}", code),
+            points,
+            format!("https://www.uio.no/studier/emner/{}/", code),
+            faculty.to_string(),
+        )
+    }
+
+    fn matches(expression: &str, course: &Course) -> bool {
+        FilterExpr::parse(expression).unwrap().matches(course)
+    }
+
+    #[test]
+    fn test_combined_expression() {
+        let expr = r#"points <= 5 and faculty ~ "matematisk" and not code ^= "EXPH""#;
+        assert!(matches(expr, &course("MAT1001", 5.0, "Det matematisk-naturvitenskapelige fakultet")));
+        assert!(!matches(expr, &course("MAT1100", 10.0, "Det matematisk-naturvitenskapelige fakultet")));
+        assert!(!matches(expr, &course("EXPHIL03", 5.0, "Det matematisk-naturvitenskapelige fakultet")));
+        assert!(!matches(expr, &course("JUS1211", 5.0, "Det juridiske fakultet")));
+    }
+
+    #[test]
+    fn test_precedence_and_grouping() {
+        let c = course("IN1000", 10.0, "MatNat");
+        // and binds tighter than or
+        assert!(matches("points = 5 and level = 2000 or department = IN", &c));
+        assert!(!matches("points = 5 and (level = 2000 or department = IN)", &c));
+        assert!(matches("!(points < 10) && level >= 1000", &c));
+    }
+
+    #[test]
+    fn test_regex_details_and_attributes() {
+        let mut c = course("HFLESER1031", 2.5, "HF");
+        c.attributes.insert("ledige plasser".to_string(), "12".to_string());
+        c.details = Some(CourseDetails {
+            language: Some("Norsk".to_string()),
+            ..CourseDetails::default()
+        });
+
+        assert!(matches(r#"code =~ "^hf(leser)?\d+$""#, &c));
+        assert!(matches(r#"language = norsk"#, &c));
+        assert!(matches(r#"attr["Ledige plasser"] = 12"#, &c));
+        // Missing fields never match
+        assert!(!matches(r#"semester ~ "høst""#, &c));
+        assert!(!matches(r#"attr[venteliste] = 0"#, &c));
+    }
+
+    #[test]
+    fn test_keywords_operator() {
+        let mut c = course("SOK1101", 10.0, "SV");
+        c.name = "Innføring i økonomi".to_string();
+        assert!(matches("name ~~ okonomi", &c));
+        assert!(matches(r#"name ~~ "innforing okonomisk" and points = 10"#, &c));
+        assert!(!matches("name ~~ statsvitenskap", &c));
+        assert!(FilterExpr::parse("points ~~ 5").unwrap_err().message.contains("text fields"));
+        assert!(FilterExpr::parse(r#"name ~~ " ""#).unwrap_err().message.contains("expected keywords"));
+    }
+
+    #[test]
+    fn test_invalid_expressions_report_position() {
+        let err = FilterExpr::parse("points <= and level = 1000").unwrap_err();
+        assert_eq!(err.position, 10);
+        assert!(err.message.contains("expected a value after '<='"));
+        assert!(err.to_string().contains("column 11\n  points <= and level = 1000\n            ^"));
+
+        let err = FilterExpr::parse("pionts > 5").unwrap_err();
+        assert!(err.message.starts_with("unknown field 'pionts'"));
+
+        assert!(FilterExpr::parse("name < 5").unwrap_err().message.contains("numeric fields"));
+        assert!(FilterExpr::parse("points ~ 5").unwrap_err().message.contains("text fields"));
+        assert!(FilterExpr::parse("points = ten").unwrap_err().message.contains("expected a number"));
+        assert!(FilterExpr::parse(r#"code =~ "(""#).unwrap_err().message.starts_with("invalid regex"));
+        assert!(FilterExpr::parse(r#"name = "open"#).unwrap_err().message.contains("unterminated"));
+        assert!(FilterExpr::parse("(points = 5").unwrap_err().message.contains("expected ')'"));
+        assert!(FilterExpr::parse("points = 5 level = 1000").unwrap_err().message.contains("unexpected"));
+    }
+
+    #[test]
+    fn test_deep_nesting_is_rejected() {
+        let nested = |depth| format!("{}points = 5{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(FilterExpr::parse(&nested(MAX_DEPTH)).is_ok());
+        assert!(FilterExpr::parse(&format!("{}points = 5", "not ".repeat(MAX_DEPTH))).is_ok());
+
+        let err = FilterExpr::parse(&nested(100_000)).unwrap_err();
+        assert_eq!(err.position, MAX_DEPTH);
+        assert!(err.message.contains("nested more than 64 levels"));
+        assert!(FilterExpr::parse(&format!("{}points = 5", "not ".repeat(MAX_DEPTH + 1))).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+use thiserror::Error;
+
+use crate::course_scraper::PageStructure;
+
+/// Reason a scraped page was rejected before sync
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum Anomaly {
+    #[error("page contains no tables (maintenance page or markup change?)")]
+    NoTables,
+
+    #[error("page contains {tables} table(s) but no faculty headings")]
+    NoFacultyHeadings { tables: usize },
+
+    #[error("course count dropped from {previous} to {current} ({drop_percent:.0}%, limit {limit:.0}%)")]
+    CourseDrop {
+        previous: usize,
+        current: usize,
+        drop_percent: f64,
+        limit: f64,
+    },
+}
+
+impl Anomaly {
+    /// Short machine-readable kind, stored in the run log
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Anomaly::NoTables => "no_tables",
+            Anomaly::NoFacultyHeadings { .. } => "no_faculty_headings",
+            Anomaly::CourseDrop { .. } => "course_drop",
+        }
+    }
+}
+
+/// Rejects scrapes that would otherwise mark most stored courses as removed
+///
+/// The stored count only changes when a page is synced, so a real, lasting drop
+/// (a faculty withdrawing its courses) would be rejected forever. Once the same
+/// dropped count has been rejected `accept_after` times in a row, the guard treats
+/// it as the new baseline and lets the page through. Structural anomalies (no
+/// tables, no faculty headings) are never accepted this way.
+#[derive(Debug, Clone)]
+pub struct SanityGuard {
+    enabled: bool,
+    max_drop_percent: f64,
+    /// Consecutive rejections of the same course count before it is accepted (0 never accepts)
+    accept_after: usize,
+}
+
+impl SanityGuard {
+    pub fn new(max_drop_percent: f64, accept_after: usize) -> Self {
+        Self {
+            enabled: true,
+            max_drop_percent,
+            accept_after,
+        }
+    }
+
+    /// A guard that accepts every page
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            max_drop_percent: 100.0,
+            accept_after: 0,
+        }
+    }
+
+    pub fn description(&self) -> String {
+        if !self.enabled {
+            "disabled".to_string()
+        } else if self.accept_after == 0 {
+            format!("max drop {:.0}%", self.max_drop_percent)
+        } else {
+            format!(
+                "max drop {:.0}%, accepted after {} rejections",
+                self.max_drop_percent, self.accept_after
+            )
+        }
+    }
+
+    /// Whether a drop rejected this many times in a row has become the new baseline
+    pub fn accepts_lasting_drop(&self, rejections: usize) -> bool {
+        self.accept_after > 0 && rejections >= self.accept_after
+    }
+
+    /// Check a parsed page against the number of courses currently stored.
+    /// `rejections` is how many runs in a row were rejected for dropping to this same count.
+    pub fn check(
+        &self,
+        structure: &PageStructure,
+        course_count: usize,
+        stored_count: usize,
+        rejections: usize,
+    ) -> Result<(), Anomaly> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        if structure.tables == 0 {
+            return Err(Anomaly::NoTables);
+        }
+
+        if structure.faculty_headings == 0 {
+            return Err(Anomaly::NoFacultyHeadings {
+                tables: structure.tables,
+            });
+        }
+
+        // Nothing stored yet (first run), so there is nothing to drop from
+        if stored_count > 0 && course_count < stored_count {
+            let drop_percent = (stored_count - course_count) as f64 / stored_count as f64 * 100.0;
+            if drop_percent > self.max_drop_percent && !self.accepts_lasting_drop(rejections) {
+                return Err(Anomaly::CourseDrop {
+                    previous: stored_count,
+                    current: course_count,
+                    drop_percent,
+                    limit: self.max_drop_percent,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEALTHY: PageStructure = PageStructure {
+        tables: 3,
+        faculty_headings: 3,
+    };
+
+    #[test]
+    fn test_structural_anomalies() {
+        let guard = SanityGuard::new(50.0, 3);
+
+        let empty = PageStructure::default();
+        assert_eq!(guard.check(&empty, 0, 100, 0), Err(Anomaly::NoTables));
+
+        let headless = PageStructure {
+            tables: 2,
+            faculty_headings: 0,
+        };
+        let anomaly = guard.check(&headless, 40, 40, 0).unwrap_err();
+        assert_eq!(anomaly.kind(), "no_faculty_headings");
+
+        assert!(guard.check(&HEALTHY, 40, 40, 0).is_ok());
+    }
+
+    #[test]
+    fn test_course_drop_limit() {
+        let guard = SanityGuard::new(50.0, 3);
+
+        // Exactly at the limit is accepted, beyond it is rejected
+        assert!(guard.check(&HEALTHY, 50, 100, 0).is_ok());
+        let anomaly = guard.check(&HEALTHY, 49, 100, 0).unwrap_err();
+        assert_eq!(anomaly.kind(), "course_drop");
+        assert_eq!(
+            anomaly.to_string(),
+            "course count dropped from 100 to 49 (51%, limit 50%)"
+        );
+
+        // Growth and first runs are never a drop
+        assert!(guard.check(&HEALTHY, 200, 100, 0).is_ok());
+        assert!(guard.check(&HEALTHY, 1, 0, 0).is_ok());
+    }
+
+    #[test]
+    fn test_lasting_drop_becomes_baseline() {
+        let guard = SanityGuard::new(50.0, 3);
+
+        assert!(guard.check(&HEALTHY, 10, 100, 2).is_err());
+        assert!(guard.check(&HEALTHY, 10, 100, 3).is_ok());
+        // Broken pages are rejected no matter how often they repeat
+        assert!(guard.check(&PageStructure::default(), 0, 100, 3).is_err());
+
+        // 0 keeps rejecting the drop until the guard is disabled
+        let strict = SanityGuard::new(50.0, 0);
+        assert!(strict.check(&HEALTHY, 10, 100, 1000).is_err());
+    }
+
+    #[test]
+    fn test_disabled_guard_accepts_everything() {
+        let guard = SanityGuard::disabled();
+        assert!(guard.check(&PageStructure::default(), 0, 100, 0).is_ok());
+    }
+}
@@ -1,27 +1,54 @@
 mod config;
+mod config_file;
 mod course_scraper;
 mod db;
 mod diff;
+mod enrich;
+mod error;
+mod exclude;
+mod filter;
+mod guard;
 mod models;
 mod notifier;
+mod profile;
+mod route;
+mod schedule;
+mod search;
+mod source;
+mod subscriber;
+mod watchlist;
 mod web;
 
+use std::collections::HashMap;
 use std::env;
 use std::process::ExitCode;
+use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
-use tokio::time::interval;
+use tokio::task::JoinSet;
 use tracing::{debug, error, info, warn, Level};
 use tracing_subscriber::FmtSubscriber;
 
-use config::{validate_interval, Cli, Command, Config, PointsFilter};
-use course_scraper::CourseScraper;
-use db::{Database, RunLog};
+use config::{Cli, Command, Config, CourseFilter, ScheduleArgs, SourceSettings, DEFAULT_SOURCE};
+use course_scraper::{CourseScraper, ScrapeOutcome, UnparsedPage};
+use db::{Database, Debounce, RunError, RunLog, RunStatus, SyncResult};
 use diff::filter_changes;
+use enrich::Enricher;
+use error::ScrapeError;
+use exclude::Exclusions;
+use guard::SanityGuard;
 use models::{Course, ScrapeDiff};
-use notifier::{ConsoleNotifier, EmailNotifier, Notifier, NotifierChain, SmsNotifier};
-use web::AppConfig;
+use notifier::{Channels, ConsoleNotifier, EmailNotifier, Notifier, NotifierChain, SlackNotifier, SmsNotifier};
+use schedule::{HotPolicy, Schedule, CHANGE_WINDOW_HOURS, TIME_ZONE};
+use source::{redact_proxy, source_from_spec};
+use subscriber::Subscriber;
+use watchlist::Watchlist;
+#[cfg(test)]
+use config::PointsFilter;
+#[cfg(test)]
+use source::{HttpSettings, RetryPolicy};
+use web::{AppConfig, PollStatus, SourceInfo};
 
 #[tokio::main]
 async fn main() -> ExitCode {
@@ -32,7 +59,8 @@ async fn main() -> ExitCode {
 
     let result = match cli.command {
         Command::Check { config } => run_check(config).await,
-        Command::Start { config, interval } => run_start(config, interval).await,
+        Command::Start { config, schedule } => run_start(config, schedule).await,
+        Command::NextRuns { schedule, count } => run_next_runs(schedule, count),
         Command::TestEmail { to, from } => run_test_email(to, from).await,
         Command::TestSms { to, from } => run_test_sms(to, from).await,
     };
@@ -59,25 +87,53 @@ async fn run_check(config: Config) -> Result<()> {
     );
     log_config(&config);
 
-    let scraper = CourseScraper::new(config.url.clone());
+    let sources = config.sources()?;
     let mut db = open_database(&config).await?;
-    let filter = config.points_filter();
+    let filter = config.course_filter()?;
     let notifiers = build_notifiers(&config)?;
 
     info!(
         notifier_count = notifiers.len(),
+        source_count = sources.len(),
         "Configuration loaded, starting check"
     );
 
-    run_scrape_cycle(&scraper, &mut db, &filter, &notifiers).await
+    if let [source] = sources.as_slice() {
+        let scraper = build_scraper(&config, source)?;
+        let options = CycleOptions::from_config(&config, source)?;
+        return run_scrape_cycle(&scraper, &mut db, &filter, &notifiers, &options).await;
+    }
+
+    // Check every source, even if an earlier one fails
+    let mut failed = 0;
+    for source in &sources {
+        let scraper = build_scraper(&config, source)?;
+        let options = CycleOptions::from_config(&config, source)?;
+        if let Err(e) = run_scrape_cycle(&scraper, &mut db, &filter, &notifiers, &options).await {
+            error!(
+                source = %source.name,
+                error = %format!("{:#}", e),
+                "Check failed for source"
+            );
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("Check failed for {} of {} sources", failed, sources.len());
+    }
+
+    Ok(())
 }
 
-async fn run_start(config: Config, interval_secs: u64) -> Result<()> {
+async fn run_start(config: Config, schedule_args: ScheduleArgs) -> Result<()> {
     init_logging(config.verbose);
 
     // Validate configuration
     config.validate()?;
-    validate_interval(interval_secs)?;
+    let interval_secs = schedule_args.interval;
+    let schedule = schedule_args.schedule(interval_secs)?;
+    let hot = schedule_args.hot_policy()?;
 
     info!(
         version = env!("CARGO_PKG_VERSION"),
@@ -85,15 +141,22 @@ async fn run_start(config: Config, interval_secs: u64) -> Result<()> {
     );
     log_config(&config);
     info!(
-        interval_secs = interval_secs,
+        schedule = %schedule.description(),
         interval_human = format!("{}m {}s", interval_secs / 60, interval_secs % 60),
-        "Scrape interval configured"
+        hot_polling = %hot.description(),
+        "Scrape schedule configured"
     );
 
-    let scraper = CourseScraper::new(config.url.clone());
+    let sources = config.sources()?;
+    // A per-source interval replaces --interval in that source's schedule
+    let schedules = sources
+        .iter()
+        .map(|source| schedule_args.schedule(source.interval_secs.unwrap_or(interval_secs)))
+        .collect::<Result<Vec<_>>>()?;
+    let statuses: Vec<Arc<RwLock<PollStatus>>> = sources.iter().map(|_| Arc::default()).collect();
     let db = open_database(&config).await?;
-    let filter = config.points_filter();
-    let notifiers = build_notifiers(&config)?;
+    let filter = config.course_filter()?;
+    let notifiers = Arc::new(build_notifiers(&config)?);
     let port = config.port;
 
     // Build display-safe config for web UI
@@ -104,13 +167,34 @@ async fn run_start(config: Config, interval_secs: u64) -> Result<()> {
         sms_enabled: config.sms_enabled(),
         sms_from: config.sms_from.clone(),
         sms_to: config.sms_recipients(),
+        slack_enabled: config.slack_enabled(),
         points_filter: filter.description(),
+        watchlist: config.watchlist()?.patterns().map(String::from).collect(),
+        exclusions: config.exclusions()?.rules().map(ToString::to_string).collect(),
+        urgent_channels: config.urgent_channels.clone(),
+        routes: config
+            .route_rules()?
+            .iter()
+            .map(|rule| format!("{}: {}", rule.notifier, rule.route))
+            .collect(),
         database_type: if config.uses_turso() {
             "Turso (remote)".to_string()
         } else {
             "SQLite (local)".to_string()
         },
-        scrape_url: config.url.clone(),
+        sources: sources
+            .iter()
+            .zip(&schedules)
+            .zip(&statuses)
+            .map(|((source, schedule), status)| SourceInfo {
+                name: source.name.clone(),
+                url: source.location.clone(),
+                profile: source.profile.clone(),
+                schedule: schedule.description(),
+                status: status.clone(),
+            })
+            .collect(),
+        hot_polling: hot.description(),
     };
 
     // Start web server in background
@@ -121,28 +205,83 @@ async fn run_start(config: Config, interval_secs: u64) -> Result<()> {
         }
     });
 
-    // Re-open database for scrape loop (web server took ownership)
-    let mut db = open_database(&config).await?;
-
-    let mut ticker = interval(Duration::from_secs(interval_secs));
+    // One scrape loop per source, each with its own database connection
+    let mut loops = JoinSet::new();
+    for ((source, schedule), status) in sources.iter().zip(schedules).zip(statuses) {
+        let scraper = build_scraper(&config, source)?;
+        let options = CycleOptions::from_config(&config, source)?;
+        let db = open_database(&config).await?;
+
+        loops.spawn(run_source_loop(
+            scraper,
+            db,
+            filter.clone(),
+            notifiers.clone(),
+            options,
+            Polling {
+                schedule,
+                hot: hot.clone(),
+                status,
+            },
+        ));
+    }
 
     info!(
-        interval_secs = interval_secs,
+        source_count = sources.len(),
         notifier_count = notifiers.len(),
-        db_type = %db.db_type(),
         port = port,
-        "Entering scrape loop (Ctrl+C to stop)"
+        "Entering scrape loops (Ctrl+C to stop)"
+    );
+
+    // The loops only end if a task panics or a cron schedule has no further runs
+    while let Some(result) = loops.join_next().await {
+        if let Err(e) = result {
+            error!(error = %e, "Scrape loop stopped unexpectedly");
+        }
+    }
+
+    Ok(())
+}
+
+/// Scrape one source forever on its own schedule
+async fn run_source_loop(
+    scraper: CourseScraper,
+    mut db: Database,
+    filter: CourseFilter,
+    notifiers: Arc<NotifierChain>,
+    options: CycleOptions,
+    polling: Polling,
+) {
+    info!(
+        source = %scraper.name(),
+        schedule = %polling.schedule.description(),
+        hot_polling = %polling.hot.description(),
+        db_type = %db.db_type(),
+        "Entering scrape loop"
     );
 
+    // The first cycle runs right away, later ones follow the schedule
+    let mut next_run = Some(chrono::Utc::now());
+
     loop {
-        ticker.tick().await;
+        let Some(due) = next_run else {
+            warn!(
+                source = %scraper.name(),
+                schedule = %polling.schedule.description(),
+                "Schedule has no further runs - stopping scrape loop"
+            );
+            return;
+        };
+        tokio::time::sleep((due - chrono::Utc::now()).to_std().unwrap_or_default()).await;
 
-        debug!("Ticker fired, starting new cycle");
+        let started = chrono::Utc::now();
+        debug!(source = %scraper.name(), "Scheduled run due, starting new cycle");
 
-        if let Err(e) = run_scrape_cycle(&scraper, &mut db, &filter, &notifiers).await {
+        if let Err(e) = run_scrape_cycle(&scraper, &mut db, &filter, &notifiers, &options).await {
             // Check if this is a Turso connection error that can be recovered
             if Database::is_connection_error(&e) {
                 warn!(
+                    source = %scraper.name(),
                     error = %e,
                     "Turso connection error detected - attempting reconnection"
                 );
@@ -151,8 +290,9 @@ async fn run_start(config: Config, interval_secs: u64) -> Result<()> {
                     Ok(_) => {
                         info!("Reconnection successful - retrying scrape cycle");
                         // Retry immediately after reconnection
-                        if let Err(retry_err) = run_scrape_cycle(&scraper, &mut db, &filter, &notifiers).await {
+                        if let Err(retry_err) = run_scrape_cycle(&scraper, &mut db, &filter, &notifiers, &options).await {
                             error!(
+                                source = %scraper.name(),
                                 error = %retry_err,
                                 "Scrape cycle failed after reconnection - will retry next interval"
                             );
@@ -160,19 +300,136 @@ async fn run_start(config: Config, interval_secs: u64) -> Result<()> {
                     }
                     Err(reconnect_err) => {
                         error!(
+                            source = %scraper.name(),
                             error = %reconnect_err,
                             "Failed to reconnect to Turso - will retry next interval"
                         );
                     }
                 }
             } else {
-                error!(
+                let run_error = classify_error(&e);
+                if run_error.transient {
+                    warn!(
+                        source = %scraper.name(),
+                        error = %e,
+                        error_kind = %run_error.kind,
+                        "Scrape cycle failed with a transient error - will retry next interval"
+                    );
+                } else {
+                    error!(
+                        source = %scraper.name(),
+                        error = %e,
+                        error_kind = %run_error.kind,
+                        "Scrape cycle failed with a permanent error - check the URL, source and parser; \
+                         will keep retrying every interval"
+                    );
+                }
+            }
+        }
+
+        next_run = plan_next_run(&polling, &db, scraper.name(), started).await;
+    }
+}
+
+/// When a scrape loop runs, and where it publishes its plan
+struct Polling {
+    schedule: Schedule,
+    hot: HotPolicy,
+    /// Shown on the configuration page
+    status: Arc<RwLock<PollStatus>>,
+}
+
+/// Next run of a source after a cycle that started at `started`, tightened while hot.
+/// Publishes the plan for the configuration page.
+async fn plan_next_run(
+    polling: &Polling,
+    db: &Database,
+    source: &str,
+    started: chrono::DateTime<chrono::Utc>,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    let Polling {
+        schedule,
+        hot,
+        status,
+    } = polling;
+    let mut hot_reason = hot.period_at(started).map(|period| format!("hot period {}", period));
+
+    if hot_reason.is_none() {
+        if let Some(threshold) = hot.change_threshold() {
+            let since = started - chrono::Duration::hours(CHANGE_WINDOW_HOURS);
+            match db.count_changes_since(source, since).await {
+                Ok(changes) if changes >= threshold => {
+                    hot_reason = Some(format!("{} changes in the last {}h", changes, CHANGE_WINDOW_HOURS));
+                }
+                Ok(_) => {}
+                Err(e) => warn!(
+                    source = %source,
                     error = %e,
-                    "Scrape cycle failed - will retry next interval"
-                );
+                    "Failed to count recent changes - using the relaxed schedule"
+                ),
             }
         }
     }
+
+    let is_hot = hot_reason.is_some();
+    let next_run = hot.next_run(schedule, started, is_hot);
+    let interval_secs = next_run.map(|next| (next - started).num_seconds().max(0) as u64);
+
+    debug!(
+        source = %source,
+        hot = ?hot_reason,
+        interval_secs = ?interval_secs,
+        next_run = ?next_run.map(|at| at.with_timezone(&TIME_ZONE).to_rfc3339()),
+        "Next run planned"
+    );
+
+    let mut status = status.write().unwrap();
+    if status.hot_reason != hot_reason {
+        info!(
+            source = %source,
+            hot = ?hot_reason,
+            interval_secs = ?interval_secs,
+            "Polling rate changed"
+        );
+    }
+    *status = PollStatus {
+        interval_secs,
+        hot_reason,
+        next_run,
+    };
+
+    next_run
+}
+
+/// Print the upcoming runs of the schedule `start` would use
+fn run_next_runs(args: ScheduleArgs, count: usize) -> Result<()> {
+    let schedule = args.schedule(args.interval)?;
+    let hot = args.hot_policy()?;
+    let now = chrono::Utc::now();
+
+    println!("Schedule: {}", schedule.description());
+    println!("Hot polling: {}", hot.description());
+    if hot.change_threshold().is_some() {
+        println!("(Polling tightened by recent changes is not included in the preview)");
+    }
+    let runs = schedule.upcoming(&hot, now, count);
+    if runs.is_empty() {
+        println!("No upcoming runs.");
+    }
+    for run in runs {
+        let wait = (run - now).num_seconds();
+        let marker = if hot.period_at(run).is_some() { "  hot" } else { "" };
+        println!(
+            "  {}  (in {}h {:02}m {:02}s){}",
+            run.with_timezone(&TIME_ZONE).format("%a %Y-%m-%d %H:%M:%S %Z"),
+            wait / 3600,
+            wait % 3600 / 60,
+            wait % 60,
+            marker
+        );
+    }
+
+    Ok(())
 }
 
 async fn run_test_email(to: String, from: String) -> Result<()> {
@@ -338,6 +595,20 @@ async fn run_test_sms(to: String, from: String) -> Result<()> {
     Ok(())
 }
 
+/// Build the scraper for one configured source
+fn build_scraper(config: &Config, source: &SourceSettings) -> Result<CourseScraper> {
+    let page_source = source_from_spec(&source.location, config.retry_policy(), &config.http_settings()?)?;
+    let profile = source
+        .parser
+        .compile()
+        .with_context(|| format!("Invalid parser profile '{}'", source.profile))?;
+    let page_url = reqwest::Url::parse(&source.page_url)
+        .with_context(|| format!("Invalid page URL '{}' for source '{}'", source.page_url, source.name))?;
+    Ok(CourseScraper::new(source.name.clone(), page_source)
+        .with_profile(profile)
+        .with_page_url(page_url))
+}
+
 /// Open database based on configuration (local SQLite or Turso)
 async fn open_database(config: &Config) -> Result<Database> {
     if let Some(ref db_url) = config.database_url {
@@ -373,12 +644,76 @@ fn init_logging(verbose: bool) {
 
 fn log_config(config: &Config) {
     // Log database configuration
+    if let Some(ref path) = config.config_file {
+        info!(config_file = %path.display(), "Sources loaded from config file");
+    } else if let Some(ref source) = config.source {
+        info!(source = %source, "Page source overridden");
+    }
+
+    if let Some(retention) = config.snapshot_retention {
+        info!(snapshot_retention = retention, "Page snapshot archive enabled");
+    }
+
+    if config.enrich {
+        info!(
+            enrich_concurrency = config.enrich_concurrency,
+            enrich_cache_days = config.enrich_cache_days,
+            "Course detail enrichment enabled"
+        );
+    }
+
+    info!(
+        user_agent = %config.user_agent,
+        connect_timeout_secs = config.http_connect_timeout,
+        timeout_secs = config.http_timeout,
+        proxy = ?config.http_proxy.as_deref().map(redact_proxy),
+        extra_headers = config.http_headers.len(),
+        extra_ca_certs = config.http_ca_certs.len(),
+        "HTTP client settings"
+    );
+
+    if config.disable_sanity_guard {
+        warn!("Sanity guard disabled - broken pages will be synced and notified");
+    } else {
+        info!(
+            max_drop_percent = config.max_drop_percent,
+            accept_drop_after = config.accept_drop_after,
+            "Sanity guard enabled"
+        );
+    }
+
+    if !config.watchlist.is_empty() {
+        info!(
+            watchlist = ?config.watchlist,
+            urgent_channels = ?config.urgent_channels,
+            "Watchlist configured"
+        );
+    }
+
+    if !config.exclude.is_empty() {
+        info!(exclusions = ?config.exclude, "Exclusion rules configured");
+    }
+
+    if !config.routes.is_empty() {
+        info!(routes = ?config.routes, "Notification routing configured");
+    }
+
+    let debounce = config.debounce();
+    if debounce.is_enabled() {
+        info!(rules = %debounce.description(), "Flap suppression enabled");
+    }
+
+    // The expression was checked by validate()
+    let filter = config
+        .course_filter()
+        .map(|filter| filter.description())
+        .unwrap_or_else(|e| e.to_string());
     if config.uses_turso() {
         info!(
             url = %config.url,
             db_type = "turso",
             db_url = %config.database_url.as_deref().unwrap_or("not set"),
-            filter = %config.points_filter().description(),
+            filter = %filter,
             "Core configuration"
         );
     } else {
@@ -386,7 +721,7 @@ fn log_config(config: &Config) {
             url = %config.url,
             db_type = "sqlite",
             db_path = %config.db.display(),
-            filter = %config.points_filter().description(),
+            filter = %filter,
             "Core configuration"
         );
     }
@@ -412,7 +747,7 @@ fn build_notifiers(config: &Config) -> Result<NotifierChain> {
     let mut notifiers = NotifierChain::new();
 
     // Always add console notifier
-    notifiers.add(ConsoleNotifier::new());
+    add_notifier(&mut notifiers, config, ConsoleNotifier::new())?;
     debug!(notifier = "console", "Added console notifier");
 
     // Add email notifier if configured
@@ -441,7 +776,8 @@ fn build_notifiers(config: &Config) -> Result<NotifierChain> {
             "Added email notifier"
         );
 
-        notifiers.add(EmailNotifier::new(api_key, from, recipients));
+        let email = EmailNotifier::new(api_key, from, recipients).with_page_urls(public_page_urls(config)?);
+        add_notifier(&mut notifiers, config, email)?;
     }
 
     // Add SMS notifier if configured
@@ -478,7 +814,15 @@ fn build_notifiers(config: &Config) -> Result<NotifierChain> {
             "Added SMS notifier"
         );
 
-        notifiers.add(SmsNotifier::new(account_sid, auth_token, from, recipients));
+        add_notifier(&mut notifiers, config, SmsNotifier::new(account_sid, auth_token, from, recipients))?;
+    }
+
+    // Add Slack notifier if configured
+    if let Some(webhook_url) = config.slack_webhook_url.as_deref().filter(|_| config.slack_enabled()) {
+        let slack = SlackNotifier::new(webhook_url.trim().to_string(), Duration::from_secs(config.http_timeout))?
+            .with_page_urls(public_page_urls(config)?);
+        info!(notifier = "slack", "Added Slack notifier");
+        add_notifier(&mut notifiers, config, slack)?;
     }
 
     info!(
@@ -489,11 +833,119 @@ fn build_notifiers(config: &Config) -> Result<NotifierChain> {
     Ok(notifiers)
 }
 
+/// Public page of each source, for links in notifications; only pages fetched over HTTP have one
+fn public_page_urls(config: &Config) -> Result<HashMap<String, String>> {
+    Ok(config
+        .sources()?
+        .into_iter()
+        .filter(|source| source.location.starts_with("http://") || source.location.starts_with("https://"))
+        .map(|source| (source.name, source.page_url))
+        .collect())
+}
+
+/// Senders for subscribers: a provider is available once its credentials and sender are set,
+/// even when the configuration has no recipients of its own
+fn build_channels(config: &Config) -> Result<Channels> {
+    let email = match (env::var("RESEND_API_KEY"), config.email_from.clone()) {
+        (Ok(api_key), Some(from)) => {
+            Some(EmailNotifier::new(api_key, from, Vec::new()).with_page_urls(public_page_urls(config)?))
+        }
+        _ => None,
+    };
+    let sms = match (
+        env::var("TWILIO_ACCOUNT_SID"),
+        env::var("TWILIO_AUTH_TOKEN"),
+        config.sms_from.clone(),
+    ) {
+        (Ok(account_sid), Ok(auth_token), Some(from)) => {
+            Some(SmsNotifier::new(account_sid, auth_token, from, Vec::new()))
+        }
+        _ => None,
+    };
+
+    debug!(
+        email = email.is_some(),
+        sms = sms.is_some(),
+        "Subscriber channels available"
+    );
+    Ok(Channels { email, sms })
+}
+
+/// Add a notifier with its route from --route/--urgent-channels, if any
+fn add_notifier<N: Notifier + 'static>(notifiers: &mut NotifierChain, config: &Config, notifier: N) -> Result<()> {
+    match config.route_for(notifier.name())? {
+        Some(route) => {
+            info!(notifier = notifier.name(), route = %route, "Notifier only receives routed changes");
+            notifiers.add_routed(notifier, route);
+        }
+        None => notifiers.add(notifier),
+    }
+    Ok(())
+}
+
+/// Turn a cycle error into the kind/transient pair recorded in the run log
+fn classify_error(err: &anyhow::Error) -> RunError {
+    match ScrapeError::find(err) {
+        Some(scrape_err) => RunError {
+            kind: scrape_err.kind().to_string(),
+            message: format!("{:#}", err),
+            transient: scrape_err.is_transient(),
+        },
+        // Unclassified errors (database, etc.) are retried on the next interval anyway
+        None => RunError {
+            kind: "other".to_string(),
+            message: format!("{:#}", err),
+            transient: true,
+        },
+    }
+}
+
+/// Per-cycle settings that are not part of the scraper, database or notifiers
+struct CycleOptions {
+    /// Archive raw pages, keeping at most this many snapshots (None disables archiving)
+    snapshot_retention: Option<usize>,
+    /// Rejects broken-looking pages before they reach sync
+    guard: SanityGuard,
+    /// Fetches detail pages for new courses (None when enrichment is disabled)
+    enricher: Option<Enricher>,
+    /// How long courses must be absent/present before they are reported
+    debounce: Debounce,
+    /// Courses notified regardless of the filter (database entries are added each cycle)
+    watchlist: Watchlist,
+    /// Senders for subscribers stored in the database
+    channels: Channels,
+}
+
+impl CycleOptions {
+    fn from_config(config: &Config, source: &SourceSettings) -> Result<Self> {
+        let enricher = if config.enrich {
+            Some(Enricher::new(
+                &source.page_url,
+                config.enrich_concurrency,
+                chrono::Duration::days(config.enrich_cache_days.into()),
+                &config.http_settings()?,
+            )?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            snapshot_retention: config.snapshot_retention,
+            guard: config.sanity_guard(),
+            enricher,
+            debounce: config.debounce(),
+            watchlist: config.watchlist()?,
+            channels: build_channels(config)?,
+        })
+    }
+}
+
 async fn run_scrape_cycle(
     scraper: &CourseScraper,
     db: &mut Database,
-    filter: &PointsFilter,
+    filter: &CourseFilter,
     notifiers: &NotifierChain,
+    options: &CycleOptions,
 ) -> Result<()> {
     let cycle_start = Instant::now();
     static CYCLE_COUNT: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
@@ -501,37 +953,231 @@ async fn run_scrape_cycle(
 
     info!(
         cycle_number = cycle_number,
+        source = %scraper.name(),
+        location = %scraper.source_description(),
         filter = %filter.description(),
+        sanity_guard = %options.guard.description(),
         db_type = %db.db_type(),
         "Starting scrape cycle"
     );
 
     // Fetch courses
     let fetch_start = Instant::now();
-    let courses = match scraper.fetch_courses().await {
-        Ok(courses) => {
+    let page = match scraper.fetch_courses().await {
+        Ok(ScrapeOutcome::Unchanged(unchanged)) => {
+            info!(
+                cycle_number = cycle_number,
+                content_hash = %unchanged.content_hash,
+                not_modified = unchanged.not_modified,
+                total_courses = unchanged.course_count,
+                total_duration_ms = cycle_start.elapsed().as_millis(),
+                "Page unchanged - skipping parse, sync and notifications"
+            );
+
+            // Quiet hours may have ended since the last change
+            let notification_sent = notify_subscribers(
+                db,
+                &SyncResult::default(),
+                &options.watchlist,
+                &filter.exclusions,
+                scraper.name(),
+                options,
+                cycle_number,
+            )
+            .await;
+
+            let run_log = RunLog {
+                source: scraper.name().to_string(),
+                status: RunStatus::Unchanged,
+                total_courses_fetched: unchanged.course_count,
+                raw_added_count: 0,
+                raw_removed_count: 0,
+                filtered_added_count: 0,
+                filtered_removed_count: 0,
+                raw_changed_count: 0,
+                filtered_changed_count: 0,
+                filter_used: filter.description(),
+                notification_sent,
+                is_first_run: false,
+                added_courses: Vec::new(),
+                removed_courses: Vec::new(),
+                changed_courses: Vec::new(),
+                reappeared_courses: Vec::new(),
+                excluded_courses: Vec::new(),
+                duration_ms: cycle_start.elapsed().as_millis() as u64,
+                content_hash: Some(unchanged.content_hash),
+                error: None,
+            };
+
+            if let Err(e) = db.log_run(&run_log).await {
+                warn!(
+                    cycle_number = cycle_number,
+                    error = %e,
+                    "Failed to log run to database"
+                );
+            }
+
+            return Ok(());
+        }
+        Ok(ScrapeOutcome::Changed(page)) => {
             info!(
                 cycle_number = cycle_number,
-                courses_fetched = courses.len(),
+                courses_fetched = page.courses.len(),
+                content_hash = %page.content_hash,
                 fetch_duration_ms = fetch_start.elapsed().as_millis(),
                 "Fetch phase completed"
             );
-            courses
+            page
         }
         Err(e) => {
+            let run_error = classify_error(&e);
             error!(
                 cycle_number = cycle_number,
                 error = %e,
+                error_kind = %run_error.kind,
+                transient = run_error.transient,
                 fetch_duration_ms = fetch_start.elapsed().as_millis(),
                 "Fetch phase failed"
             );
+
+            // Like rejected pages, make sure no retry or later cycle can answer with a 304
+            scraper.invalidate();
+
+            // Keep a page the parser choked on, so it can be inspected
+            let unparsed = e.downcast_ref::<UnparsedPage>();
+            if let Some(page) = unparsed {
+                archive_snapshot(db, &page.content_hash, &page.html, options, cycle_number).await;
+            }
+
+            let run_log = RunLog {
+                source: scraper.name().to_string(),
+                status: RunStatus::Failed,
+                total_courses_fetched: 0,
+                raw_added_count: 0,
+                raw_removed_count: 0,
+                filtered_added_count: 0,
+                filtered_removed_count: 0,
+                raw_changed_count: 0,
+                filtered_changed_count: 0,
+                filter_used: filter.description(),
+                notification_sent: false,
+                is_first_run: false,
+                added_courses: Vec::new(),
+                removed_courses: Vec::new(),
+                changed_courses: Vec::new(),
+                reappeared_courses: Vec::new(),
+                excluded_courses: Vec::new(),
+                duration_ms: cycle_start.elapsed().as_millis() as u64,
+                content_hash: unparsed.map(|page| page.content_hash.clone()),
+                error: Some(run_error),
+            };
+
+            if let Err(log_err) = db.log_run(&run_log).await {
+                warn!(
+                    cycle_number = cycle_number,
+                    error = %log_err,
+                    "Failed to log run to database"
+                );
+            }
+
             return Err(e);
         }
     };
 
+    let courses = &page.courses;
+
+    // Reject pages that would mark most stored courses as removed
+    let stored_count = db.get_course_count(scraper.name()).await?;
+    let rejections = db.count_repeated_drops(scraper.name(), courses.len()).await?;
+    if let Err(anomaly) = options.guard.check(&page.structure, courses.len(), stored_count, rejections) {
+        error!(
+            cycle_number = cycle_number,
+            anomaly = %anomaly,
+            anomaly_kind = anomaly.kind(),
+            courses_parsed = courses.len(),
+            stored_courses = stored_count,
+            tables = page.structure.tables,
+            faculty_headings = page.structure.faculty_headings,
+            content_hash = %page.content_hash,
+            "Scraped page rejected by sanity guard - skipping sync and notifications"
+        );
+
+        // Re-evaluate the page in full next cycle instead of treating it as unchanged
+        scraper.invalidate();
+
+        let run_log = RunLog {
+            source: scraper.name().to_string(),
+            status: RunStatus::Anomalous,
+            total_courses_fetched: courses.len(),
+            raw_added_count: 0,
+            raw_removed_count: 0,
+            filtered_added_count: 0,
+            filtered_removed_count: 0,
+            raw_changed_count: 0,
+            filtered_changed_count: 0,
+            filter_used: filter.description(),
+            notification_sent: false,
+            is_first_run: false,
+            added_courses: Vec::new(),
+            removed_courses: Vec::new(),
+            changed_courses: Vec::new(),
+            reappeared_courses: Vec::new(),
+            excluded_courses: Vec::new(),
+            duration_ms: cycle_start.elapsed().as_millis() as u64,
+            content_hash: Some(page.content_hash.clone()),
+            error: Some(RunError {
+                kind: anomaly.kind().to_string(),
+                message: anomaly.to_string(),
+                transient: true,
+            }),
+        };
+
+        archive_snapshot(db, &page.content_hash, &page.html, options, cycle_number).await;
+
+        if let Err(e) = db.log_run(&run_log).await {
+            warn!(
+                cycle_number = cycle_number,
+                error = %e,
+                "Failed to log run to database"
+            );
+        }
+
+        return Ok(());
+    }
+    if rejections > 0 && courses.len() < stored_count {
+        warn!(
+            cycle_number = cycle_number,
+            courses_parsed = courses.len(),
+            stored_courses = stored_count,
+            rejections = rejections,
+            "Course count drop persisted - accepting it as the new baseline"
+        );
+    }
+
     // Sync with database
     let sync_start = Instant::now();
-    let sync_result = db.sync_courses(&courses).await?;
+    let mut sync_result = match db.sync_courses(scraper.name(), courses, &options.debounce).await {
+        Ok(result) => result,
+        Err(e) => {
+            // Make sure the same page is fully processed again next cycle
+            scraper.invalidate();
+            return Err(e);
+        }
+    };
+    // Pending additions and removals only advance when a page is synced, so keep syncing
+    // the same page until they are confirmed or dropped
+    match db.get_pending_changes(scraper.name()).await {
+        Ok(pending) if pending.is_empty() => scraper.mark_processed(&page),
+        Ok(_) => scraper.invalidate(),
+        Err(e) => {
+            warn!(
+                cycle_number = cycle_number,
+                error = %e,
+                "Could not load pending changes - syncing the page again next cycle"
+            );
+            scraper.invalidate();
+        }
+    }
 
     info!(
         cycle_number = cycle_number,
@@ -540,11 +1186,47 @@ async fn run_scrape_cycle(
         total_courses = sync_result.total_courses,
         raw_added = sync_result.added.len(),
         raw_removed = sync_result.removed.len(),
+        raw_changed = sync_result.changed.len(),
+        raw_reappeared = sync_result.reappeared.len(),
         "Sync phase completed"
     );
 
+    // Look up details for new and returning courses so filters and notifications can use them
+    if let Some(ref enricher) = options.enricher {
+        if !sync_result.added.is_empty() || !sync_result.reappeared.is_empty() {
+            let courses = sync_result
+                .added
+                .iter_mut()
+                .chain(sync_result.reappeared.iter_mut().map(|r| &mut r.course));
+            if let Err(e) = enricher.enrich(db, scraper.name(), courses).await {
+                warn!(
+                    cycle_number = cycle_number,
+                    error = %e,
+                    "Course enrichment failed - notifying without details"
+                );
+            }
+        }
+    }
+
+    // Watchlist entries added from the web UI apply from the next cycle on
+    let watchlist = match db.get_watchlist().await {
+        Ok(stored) => options.watchlist.with_stored(&stored),
+        Err(e) => {
+            warn!(
+                cycle_number = cycle_number,
+                error = %e,
+                "Could not load stored watchlist - using configured entries only"
+            );
+            options.watchlist.clone()
+        }
+    };
+
     // Apply filter (even on first run, to track what would have been notified)
-    let filtered_diff = filter_changes(&sync_result, filter);
+    let mut filtered_diff = filter_changes(&sync_result, filter, &watchlist);
+    // Label notifications with the source when more than the implicit one is configured
+    if scraper.name() != DEFAULT_SOURCE {
+        filtered_diff = filtered_diff.with_source(scraper.name());
+    }
 
     // Prepare notification tracking
     let mut notification_sent = false;
@@ -568,6 +1250,8 @@ async fn run_scrape_cycle(
             cycle_number = cycle_number,
             added_courses = ?sync_result.added.iter().map(|c| format!("{}({:.1}pts)", c.code, c.points)).collect::<Vec<_>>(),
             removed_courses = ?sync_result.removed.iter().map(|c| format!("{}({:.1}pts)", c.code, c.points)).collect::<Vec<_>>(),
+            changed_courses = ?sync_result.changed.iter().map(|c| format!("{}({})", c.code(), c.summary())).collect::<Vec<_>>(),
+            reappeared_courses = ?sync_result.reappeared.iter().map(|r| format!("{}(after {})", r.course.code, r.absence())).collect::<Vec<_>>(),
             "Raw changes before filtering"
         );
 
@@ -577,6 +1261,8 @@ async fn run_scrape_cycle(
                 filter = %filter.description(),
                 raw_added = sync_result.added.len(),
                 raw_removed = sync_result.removed.len(),
+                raw_changed = sync_result.changed.len(),
+                raw_reappeared = sync_result.reappeared.len(),
                 total_duration_ms = cycle_start.elapsed().as_millis(),
                 "No changes match filter criteria - no notifications sent"
             );
@@ -585,11 +1271,14 @@ async fn run_scrape_cycle(
                 cycle_number = cycle_number,
                 filtered_added = filtered_diff.added.len(),
                 filtered_removed = filtered_diff.removed.len(),
+                filtered_changed = filtered_diff.changed.len(),
+                filtered_reappeared = filtered_diff.reappeared.len(),
                 filter = %filter.description(),
                 "Changes passed filter - sending notifications"
             );
 
-            // Send notifications
+            // Operator channels from the configuration (console, Slack, fixed recipients) with
+            // the global filter, routes and urgent channels; subscribers are notified below
             let notify_start = Instant::now();
             let results = notifiers.notify_all(&filtered_diff).await;
 
@@ -605,6 +1294,8 @@ async fn run_scrape_cycle(
                             notifier = %name,
                             added_count = filtered_diff.added.len(),
                             removed_count = filtered_diff.removed.len(),
+                            changed_count = filtered_diff.changed.len(),
+                            reappeared_count = filtered_diff.reappeared.len(),
                             "Notification sent successfully"
                         );
                     }
@@ -631,23 +1322,44 @@ async fn run_scrape_cycle(
                 "Notification phase completed"
             );
         }
+
+    }
+
+    // Subscribers get the changes matching their own filter, whatever the global one lets through,
+    // and changes held during their quiet hours once those are over
+    if !sync_result.is_first_run
+        && notify_subscribers(db, &sync_result, &watchlist, &filter.exclusions, scraper.name(), options, cycle_number)
+            .await
+    {
+        notification_sent = true;
     }
 
     // Log this run to the database (store RAW courses so users can see what changed)
     let run_log = RunLog {
+        source: scraper.name().to_string(),
+        status: RunStatus::Ok,
         total_courses_fetched: courses.len(),
         raw_added_count: sync_result.added.len(),
         raw_removed_count: sync_result.removed.len(),
         filtered_added_count: filtered_diff.added.len(),
         filtered_removed_count: filtered_diff.removed.len(),
+        raw_changed_count: sync_result.changed.len(),
+        filtered_changed_count: filtered_diff.changed.len(),
         filter_used: filter.description(),
         notification_sent,
         is_first_run: sync_result.is_first_run,
         added_courses: sync_result.added.clone(),
         removed_courses: sync_result.removed.clone(),
+        changed_courses: sync_result.changed.clone(),
+        reappeared_courses: sync_result.reappeared.clone(),
+        excluded_courses: filtered_diff.excluded.clone(),
         duration_ms: cycle_start.elapsed().as_millis() as u64,
+        content_hash: Some(page.content_hash.clone()),
+        error: None,
     };
 
+    archive_snapshot(db, &page.content_hash, &page.html, options, cycle_number).await;
+
     if let Err(e) = db.log_run(&run_log).await {
         warn!(
             cycle_number = cycle_number,
@@ -662,8 +1374,418 @@ async fn run_scrape_cycle(
         notification_sent = notification_sent,
         changes_added = filtered_diff.added.len(),
         changes_removed = filtered_diff.removed.len(),
+        changes_modified = filtered_diff.changed.len(),
+        changes_reappeared = filtered_diff.reappeared.len(),
         "Scrape cycle completed"
     );
 
     Ok(())
 }
+
+/// Send each subscriber the changes matching their filter; returns whether any notification went out.
+///
+/// During a subscriber's quiet hours only watchlist hits are sent. Everything else is held
+/// in the database per subscriber and source, and delivered with the first cycle of that
+/// source after the quiet hours end.
+async fn notify_subscribers(
+    db: &Database,
+    sync_result: &SyncResult,
+    watchlist: &Watchlist,
+    exclusions: &Exclusions,
+    source: &str,
+    options: &CycleOptions,
+    cycle_number: u64,
+) -> bool {
+    let records = match db.get_subscribers().await {
+        Ok(records) => records,
+        Err(e) => {
+            warn!(
+                cycle_number = cycle_number,
+                error = %e,
+                "Could not load subscribers - skipping personal notifications"
+            );
+            return false;
+        }
+    };
+
+    let now = chrono::Utc::now();
+    let mut sent = false;
+    for record in &records {
+        let mut subscriber = match Subscriber::parse(record) {
+            Ok(subscriber) => subscriber,
+            Err(e) => {
+                warn!(
+                    cycle_number = cycle_number,
+                    subscriber = %record.name,
+                    error = %format!("{:#}", e),
+                    "Skipping invalid subscriber"
+                );
+                continue;
+            }
+        };
+
+        // The global exclusions apply to everyone
+        subscriber.filter.exclusions = exclusions.clone();
+        let mut diff = filter_changes(sync_result, &subscriber.filter, watchlist);
+        // Only watchlist hits break through quiet hours, the rest waits until they are over
+        let quiet = subscriber.is_quiet(now);
+        let mut held_count = 0;
+        if quiet {
+            let (watched, held) = diff.split_watched();
+            if !held.is_empty() {
+                match db.hold_changes(record.id, source, &held).await {
+                    Ok(()) => debug!(
+                        cycle_number = cycle_number,
+                        subscriber = %subscriber.name,
+                        held_changes = held.total_changes(),
+                        "Holding changes until quiet hours end"
+                    ),
+                    Err(e) => warn!(
+                        cycle_number = cycle_number,
+                        subscriber = %subscriber.name,
+                        error = %e,
+                        "Could not hold changes for quiet hours - they will not be sent"
+                    ),
+                }
+            }
+            diff = watched;
+        } else {
+            match db.get_held_changes(record.id, source).await {
+                Ok(held) => {
+                    held_count = held.len();
+                    diff = held.into_iter().fold(ScrapeDiff::default(), ScrapeDiff::merge).merge(diff);
+                }
+                Err(e) => warn!(
+                    cycle_number = cycle_number,
+                    subscriber = %subscriber.name,
+                    error = %e,
+                    "Could not load changes held during quiet hours"
+                ),
+            }
+        }
+        if source != DEFAULT_SOURCE {
+            diff = diff.with_source(source);
+        }
+        if diff.is_empty() {
+            debug!(
+                cycle_number = cycle_number,
+                subscriber = %subscriber.name,
+                quiet_hours = quiet,
+                "No changes for subscriber"
+            );
+            continue;
+        }
+
+        let notifiers = subscriber.notifiers(&options.channels);
+        if notifiers.is_empty() {
+            warn!(
+                cycle_number = cycle_number,
+                subscriber = %subscriber.name,
+                "No configured channel can reach subscriber (set RESEND_API_KEY/--email-from or Twilio credentials)"
+            );
+            continue;
+        }
+
+        let mut delivered = false;
+        for (name, result) in notifiers.notify_all(&diff).await {
+            match result {
+                Ok(()) => {
+                    delivered = true;
+                    info!(
+                        cycle_number = cycle_number,
+                        subscriber = %subscriber.name,
+                        notifier = %name,
+                        total_changes = diff.total_changes(),
+                        released_batches = held_count,
+                        "Subscriber notified"
+                    );
+                }
+                Err(e) => warn!(
+                    cycle_number = cycle_number,
+                    subscriber = %subscriber.name,
+                    notifier = %name,
+                    error = %e,
+                    "Subscriber notification failed"
+                ),
+            }
+        }
+
+        // Held changes stay queued until a channel has delivered them
+        if delivered && held_count > 0 {
+            if let Err(e) = db.clear_held_changes(record.id, source).await {
+                warn!(
+                    cycle_number = cycle_number,
+                    subscriber = %subscriber.name,
+                    error = %e,
+                    "Could not clear delivered held changes - they may be sent again"
+                );
+            }
+        }
+        sent |= delivered;
+    }
+
+    sent
+}
+
+/// Archive the raw page so parser problems can be investigated later
+async fn archive_snapshot(
+    db: &Database,
+    content_hash: &str,
+    html: &str,
+    options: &CycleOptions,
+    cycle_number: u64,
+) {
+    if let Some(retention) = options.snapshot_retention {
+        if let Err(e) = db.store_snapshot(content_hash, html, retention).await {
+            warn!(
+                cycle_number = cycle_number,
+                content_hash = %content_hash,
+                error = %e,
+                "Failed to archive page snapshot"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SNAPSHOTS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/snapshots");
+
+    #[tokio::test]
+    async fn test_scrape_cycle_offline_from_snapshots() {
+        let scraper = CourseScraper::new(
+            DEFAULT_SOURCE,
+            source_from_spec(&format!("file://{}", SNAPSHOTS), RetryPolicy::default(), &HttpSettings::default()).unwrap(),
+        );
+        let mut db = Database::open_in_memory().await.unwrap();
+        let filter = CourseFilter::from(PointsFilter::None);
+        let notifiers = NotifierChain::new();
+        let options = CycleOptions {
+            snapshot_retention: Some(10),
+            guard: SanityGuard::new(50.0, 3),
+            enricher: None,
+            debounce: Debounce::default(),
+            watchlist: Watchlist::default(),
+            channels: Channels::default(),
+        };
+
+        // First snapshot initializes the database
+        run_scrape_cycle(&scraper, &mut db, &filter, &notifiers, &options).await.unwrap();
+        assert_eq!(db.get_course_count(DEFAULT_SOURCE).await.unwrap(), 8);
+
+        // Second snapshot drops HFLESER1031 and adds SOS1000
+        run_scrape_cycle(&scraper, &mut db, &filter, &notifiers, &options).await.unwrap();
+        let courses = db.get_all_courses(DEFAULT_SOURCE).await.unwrap();
+        assert!(!courses.contains_key("HFLESER1031"));
+        assert!(courses.contains_key("SOS1000"));
+
+        let runs = db.get_run_logs(10).await.unwrap();
+        assert_eq!(runs.len(), 2);
+        assert!(runs[1].is_first_run);
+        assert_eq!(runs[0].source, DEFAULT_SOURCE);
+        assert_eq!(runs[0].added_courses[0].code, "SOS1000");
+        assert_eq!(runs[0].removed_courses[0].code, "HFLESER1031");
+
+        // The last snapshot repeats: recorded as unchanged without touching the courses
+        run_scrape_cycle(&scraper, &mut db, &filter, &notifiers, &options).await.unwrap();
+        let unchanged = &db.get_run_logs(1).await.unwrap()[0];
+        assert_eq!(unchanged.status, RunStatus::Unchanged);
+        assert_eq!(unchanged.total_courses_fetched, 8);
+        assert_eq!(unchanged.content_hash, runs[0].content_hash);
+
+        // A source that can't be read is recorded as a failed, permanent run
+        let broken = CourseScraper::new(
+            DEFAULT_SOURCE,
+            source_from_spec("file:///nonexistent/page.html", RetryPolicy::default(), &HttpSettings::default()).unwrap(),
+        );
+        assert!(run_scrape_cycle(&broken, &mut db, &filter, &notifiers, &options).await.is_err());
+        let failed = &db.get_run_logs(1).await.unwrap()[0];
+        assert_eq!(failed.status, RunStatus::Failed);
+        let run_error = failed.error.as_ref().unwrap();
+        assert_eq!(run_error.kind, "io");
+        assert!(!run_error.transient);
+
+        // Both pages were archived and linked to their runs
+        for run in &runs {
+            assert!(run.snapshot_available);
+            let hash = run.content_hash.as_deref().unwrap();
+            let html = db.get_snapshot(hash).await.unwrap().unwrap();
+            assert_eq!(course_scraper::content_hash(&html), hash);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_debounced_removal_confirms_while_page_is_unchanged() {
+        let scraper = CourseScraper::new(
+            DEFAULT_SOURCE,
+            source_from_spec(&format!("file://{}", SNAPSHOTS), RetryPolicy::default(), &HttpSettings::default()).unwrap(),
+        );
+        let mut db = Database::open_in_memory().await.unwrap();
+        let filter = CourseFilter::from(PointsFilter::None);
+        let notifiers = NotifierChain::new();
+        let options = CycleOptions {
+            snapshot_retention: None,
+            guard: SanityGuard::new(50.0, 3),
+            enricher: None,
+            debounce: Debounce {
+                removal_cycles: Some(2),
+                ..Debounce::default()
+            },
+            watchlist: Watchlist::default(),
+            channels: Channels::default(),
+        };
+
+        // Run past the last snapshot, which keeps repeating
+        for _ in 0..5 {
+            run_scrape_cycle(&scraper, &mut db, &filter, &notifiers, &options).await.unwrap();
+        }
+
+        let runs = db.get_run_logs(10).await.unwrap();
+        let statuses: Vec<_> = runs.iter().rev().map(|run| run.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                RunStatus::Ok,
+                RunStatus::Ok,
+                RunStatus::Ok,
+                RunStatus::Unchanged,
+                RunStatus::Unchanged
+            ]
+        );
+
+        // HFLESER1031 is missing from the second page on and confirmed on its second absence
+        assert!(runs[3].removed_courses.is_empty());
+        assert_eq!(runs[2].removed_courses.len(), 1);
+        assert_eq!(runs[2].removed_courses[0].code, "HFLESER1031");
+        assert!(db.get_pending_changes(DEFAULT_SOURCE).await.unwrap().is_empty());
+        assert!(!db.get_all_courses(DEFAULT_SOURCE).await.unwrap().contains_key("HFLESER1031"));
+    }
+
+    #[tokio::test]
+    async fn test_sanity_guard_rejects_maintenance_page() {
+        let mut db = Database::open_in_memory().await.unwrap();
+        let filter = CourseFilter::from(PointsFilter::None);
+        let notifiers = NotifierChain::new();
+        let options = CycleOptions {
+            snapshot_retention: Some(10),
+            guard: SanityGuard::new(50.0, 3),
+            enricher: None,
+            debounce: Debounce::default(),
+            watchlist: Watchlist::default(),
+            channels: Channels::default(),
+        };
+
+        let scraper = CourseScraper::new(
+            DEFAULT_SOURCE,
+            source_from_spec(&format!("file://{}", SNAPSHOTS), RetryPolicy::default(), &HttpSettings::default()).unwrap(),
+        );
+        run_scrape_cycle(&scraper, &mut db, &filter, &notifiers, &options).await.unwrap();
+        assert_eq!(db.get_course_count(DEFAULT_SOURCE).await.unwrap(), 8);
+
+        // A maintenance page must not wipe the stored courses
+        let maintenance = CourseScraper::new(
+            DEFAULT_SOURCE,
+            source_from_spec(
+                concat!("file://", env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/maintenance.html"),
+                RetryPolicy::default(),
+                &HttpSettings::default(),
+            )
+            .unwrap(),
+        );
+        run_scrape_cycle(&maintenance, &mut db, &filter, &notifiers, &options).await.unwrap();
+        assert_eq!(db.get_course_count(DEFAULT_SOURCE).await.unwrap(), 8);
+
+        let run = &db.get_run_logs(1).await.unwrap()[0];
+        assert_eq!(run.status, RunStatus::Anomalous);
+        assert_eq!(run.raw_removed_count, 0);
+        assert!(!run.notification_sent);
+        assert_eq!(run.error.as_ref().unwrap().kind, "no_tables");
+        assert!(run.snapshot_available);
+
+        // The same page is evaluated again rather than skipped as unchanged
+        run_scrape_cycle(&maintenance, &mut db, &filter, &notifiers, &options).await.unwrap();
+        let run = &db.get_run_logs(1).await.unwrap()[0];
+        assert_eq!(run.status, RunStatus::Anomalous);
+        assert_eq!(db.get_course_count(DEFAULT_SOURCE).await.unwrap(), 8);
+    }
+
+    #[tokio::test]
+    async fn test_quiet_hours_hold_unwatched_changes() {
+        let db = Database::open_in_memory().await.unwrap();
+        // Quiet hours around the current time
+        let now = chrono::Utc::now().with_timezone(&TIME_ZONE);
+        let quiet_hours = format!(
+            "{}-{}",
+            (now - chrono::Duration::hours(1)).format("%H:%M"),
+            (now + chrono::Duration::hours(1)).format("%H:%M")
+        );
+        let id = db
+            .add_subscriber(&db::SubscriberRecord {
+                id: 0,
+                name: "Kari".to_string(),
+                filter: None,
+                email: Some("kari@example.com".to_string()),
+                phone: None,
+                language: "no".to_string(),
+                quiet_hours: Some(quiet_hours),
+            })
+            .await
+            .unwrap();
+
+        let course = |code: &str| Course::new(code.to_string(), code.to_string(), 10.0, String::new(), "MatNat".to_string());
+        let sync_result = SyncResult {
+            added: vec![course("IN1000"), course("MAT1100")],
+            ..SyncResult::default()
+        };
+        let watchlist = Watchlist::new(&["IN1000".to_string()]).unwrap();
+        let options = CycleOptions {
+            snapshot_retention: None,
+            guard: SanityGuard::disabled(),
+            enricher: None,
+            debounce: Debounce::default(),
+            watchlist: watchlist.clone(),
+            channels: Channels::default(),
+        };
+
+        notify_subscribers(&db, &sync_result, &watchlist, &Exclusions::default(), DEFAULT_SOURCE, &options, 1).await;
+
+        // The watchlist hit goes out right away, the rest waits for the quiet hours to end
+        let held = db.get_held_changes(id, DEFAULT_SOURCE).await.unwrap();
+        assert_eq!(held.len(), 1);
+        assert_eq!(held[0].added.iter().map(|c| c.code.as_str()).collect::<Vec<_>>(), vec!["MAT1100"]);
+    }
+
+    #[tokio::test]
+    async fn test_parse_failure_archives_snapshot() {
+        let mut db = Database::open_in_memory().await.unwrap();
+        let options = CycleOptions {
+            snapshot_retention: Some(10),
+            guard: SanityGuard::new(50.0, 3),
+            enricher: None,
+            debounce: Debounce::default(),
+            watchlist: Watchlist::default(),
+            channels: Channels::default(),
+        };
+        let scraper = CourseScraper::new(
+            DEFAULT_SOURCE,
+            source_from_spec(
+                concat!("file://", env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/unknown-layout.html"),
+                RetryPolicy::default(),
+                &HttpSettings::default(),
+            )
+            .unwrap(),
+        );
+
+        let filter = CourseFilter::from(PointsFilter::None);
+        assert!(run_scrape_cycle(&scraper, &mut db, &filter, &NotifierChain::new(), &options).await.is_err());
+
+        let run = &db.get_run_logs(1).await.unwrap()[0];
+        assert_eq!(run.status, RunStatus::Failed);
+        assert_eq!(run.error.as_ref().unwrap().kind, "layout");
+        assert!(run.snapshot_available);
+        let html = db.get_snapshot(run.content_hash.as_deref().unwrap()).await.unwrap().unwrap();
+        assert!(html.contains("Hendelse"));
+    }
+}
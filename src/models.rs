@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Course {
@@ -7,6 +8,12 @@ pub struct Course {
     pub points: f32,
     pub url: String,
     pub faculty: String,
+    /// Extra table columns keyed by normalized header text (e.g. "ledige plasser")
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, String>,
+    /// Fields from the course's own page, filled in by the optional enrichment stage
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<CourseDetails>,
 }
 
 impl Course {
@@ -17,26 +24,362 @@ impl Course {
             points,
             url,
             faculty,
+            attributes: BTreeMap::new(),
+            details: None,
         }
     }
+
+    /// Department prefix of the code: "IN" for IN1000, "MAT-INF" for MAT-INF1100
+    pub fn department(&self) -> &str {
+        let end = self
+            .code
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(self.code.len());
+        &self.code[..end]
+    }
+
+    /// Course level from the first digit of the number: 1000 for IN1010, 4000 for INF4300
+    pub fn level(&self) -> Option<u16> {
+        let digit = self.code.chars().find(|c| c.is_ascii_digit())?.to_digit(10)?;
+        (digit > 0).then(|| digit as u16 * 1000)
+    }
+}
+
+/// A single field that differs between two scrapes of the same course
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: String,
+    pub after: String,
+}
+
+impl std::fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} → {}", self.field, self.before, self.after)
+    }
+}
+
+/// A course present in both scrapes whose points, name, faculty or URL changed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CourseChange {
+    pub before: Course,
+    pub after: Course,
+    pub fields: Vec<FieldChange>,
+}
+
+impl CourseChange {
+    /// Compare two versions of a course, returning `None` when nothing tracked changed
+    pub fn between(before: &Course, after: &Course) -> Option<Self> {
+        let mut fields = Vec::new();
+        let mut compare = |field: &str, old: String, new: String| {
+            if old != new {
+                fields.push(FieldChange {
+                    field: field.to_string(),
+                    before: old,
+                    after: new,
+                });
+            }
+        };
+
+        compare("name", before.name.clone(), after.name.clone());
+        compare("points", format!("{}", before.points), format!("{}", after.points));
+        compare("faculty", before.faculty.clone(), after.faculty.clone());
+        compare("url", before.url.clone(), after.url.clone());
+
+        (!fields.is_empty()).then(|| Self {
+            before: before.clone(),
+            after: after.clone(),
+            fields,
+        })
+    }
+
+    pub fn code(&self) -> &str {
+        &self.after.code
+    }
+
+    /// Field changes on one line, e.g. "points: 5 → 10, name: Old → New"
+    pub fn summary(&self) -> String {
+        self.fields
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// A course that is available again after having been removed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reappearance {
+    pub course: Course,
+    /// Seconds between the removal and the return
+    pub absent_seconds: i64,
+    /// How many times the course had been available before this return
+    pub previous_appearances: u32,
+}
+
+impl Reappearance {
+    /// How long the course was gone, e.g. "3d 4h"
+    pub fn absence(&self) -> String {
+        format_absence(self.absent_seconds)
+    }
+}
+
+/// Compact duration for absences: "3d 4h", "2h 15m", "12m" or "<1m"
+pub fn format_absence(seconds: i64) -> String {
+    let minutes = seconds / 60;
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        "<1m".to_string()
+    }
+}
+
+/// Canonical form of a course code: uppercase, without whitespace ("in 1000 " -> "IN1000")
+pub fn normalize_course_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+/// Extra information from a course's detail page on uio.no
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CourseDetails {
+    pub language: Option<String>,
+    pub semester: Option<String>,
+    pub teaching_period: Option<String>,
+    pub exam_form: Option<String>,
+    pub prerequisites: Option<String>,
+}
+
+impl CourseDetails {
+    pub fn is_empty(&self) -> bool {
+        self.language.is_none()
+            && self.semester.is_none()
+            && self.teaching_period.is_none()
+            && self.exam_form.is_none()
+            && self.prerequisites.is_none()
+    }
+
+    /// One-line summary for notifications and the dashboard, e.g. "Norsk | Høst | Skriftlig eksamen"
+    pub fn summary(&self) -> String {
+        [
+            &self.language,
+            &self.semester,
+            &self.teaching_period,
+            &self.exam_form,
+        ]
+        .into_iter()
+        .flatten()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(" | ")
+    }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ScrapeDiff {
     pub added: Vec<Course>,
     pub removed: Vec<Course>,
+    pub changed: Vec<CourseChange>,
+    pub reappeared: Vec<Reappearance>,
+    /// Codes in this diff that are on the watchlist, highlighted in notifications
+    pub watched: BTreeSet<String>,
+    /// Codes of changes that passed the filter but were dropped by exclusion rules (not notified)
+    pub excluded: Vec<String>,
+    /// Name of the configured source the changes came from, shown in notifications
+    pub source: Option<String>,
 }
 
 impl ScrapeDiff {
     pub fn new(added: Vec<Course>, removed: Vec<Course>) -> Self {
-        Self { added, removed }
+        Self {
+            added,
+            removed,
+            changed: Vec::new(),
+            reappeared: Vec::new(),
+            watched: BTreeSet::new(),
+            excluded: Vec::new(),
+            source: None,
+        }
+    }
+
+    pub fn with_changed(mut self, changed: Vec<CourseChange>) -> Self {
+        self.changed = changed;
+        self
+    }
+
+    pub fn with_reappeared(mut self, reappeared: Vec<Reappearance>) -> Self {
+        self.reappeared = reappeared;
+        self
+    }
+
+    pub fn with_watched(mut self, watched: BTreeSet<String>) -> Self {
+        self.watched = watched;
+        self
+    }
+
+    pub fn with_excluded(mut self, excluded: Vec<String>) -> Self {
+        self.excluded = excluded;
+        self
+    }
+
+    pub fn has_watch_hits(&self) -> bool {
+        !self.watched.is_empty()
+    }
+
+    /// Split into the watchlist hits and everything else
+    pub fn split_watched(&self) -> (ScrapeDiff, ScrapeDiff) {
+        let is_watched = |code: &str| self.watched.contains(code);
+        let (watched_added, added) = self.added.iter().cloned().partition(|c| is_watched(&c.code));
+        let (watched_removed, removed) = self.removed.iter().cloned().partition(|c| is_watched(&c.code));
+        let (watched_changed, changed) = self.changed.iter().cloned().partition(|c| is_watched(c.code()));
+        let (watched_reappeared, reappeared) = self
+            .reappeared
+            .iter()
+            .cloned()
+            .partition(|r| is_watched(&r.course.code));
+
+        let watched = ScrapeDiff {
+            added: watched_added,
+            removed: watched_removed,
+            changed: watched_changed,
+            reappeared: watched_reappeared,
+            watched: self.watched.clone(),
+            excluded: Vec::new(),
+            source: self.source.clone(),
+        };
+        let rest = ScrapeDiff {
+            added,
+            removed,
+            changed,
+            reappeared,
+            watched: BTreeSet::new(),
+            excluded: self.excluded.clone(),
+            source: self.source.clone(),
+        };
+        (watched, rest)
+    }
+
+    /// Append the changes of a later diff, e.g. to deliver held changes in one message
+    pub fn merge(mut self, later: ScrapeDiff) -> Self {
+        self.added.extend(later.added);
+        self.removed.extend(later.removed);
+        self.changed.extend(later.changed);
+        self.reappeared.extend(later.reappeared);
+        self.watched.extend(later.watched);
+        self.excluded.extend(later.excluded);
+        self.source = later.source.or(self.source);
+        self
+    }
+
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
     }
 
     pub fn is_empty(&self) -> bool {
-        self.added.is_empty() && self.removed.is_empty()
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+            && self.reappeared.is_empty()
     }
 
     pub fn total_changes(&self) -> usize {
-        self.added.len() + self.removed.len()
+        self.added.len() + self.removed.len() + self.changed.len() + self.reappeared.len()
+    }
+
+    /// Whether any added, removed, changed or reappeared course has this code
+    pub fn contains_code(&self, code: &str) -> bool {
+        self.added.iter().any(|c| c.code == code)
+            || self.removed.iter().any(|c| c.code == code)
+            || self.changed.iter().any(|c| c.code() == code)
+            || self.reappeared.iter().any(|r| r.course.code == code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn course(code: &str) -> Course {
+        Course::new(code.to_string(), String::new(), 10.0, String::new(), String::new())
+    }
+
+    #[test]
+    fn test_normalize_course_code() {
+        assert_eq!(normalize_course_code(" in 1000\u{a0}"), "IN1000");
+        assert_eq!(normalize_course_code("mat-inf1100"), "MAT-INF1100");
+    }
+
+    #[test]
+    fn test_merge_appends_later_changes() {
+        let earlier = ScrapeDiff::new(vec![course("IN1000")], vec![]);
+        let later = ScrapeDiff::new(vec![course("IN1010")], vec![course("MAT1100")]).with_source("english");
+
+        let merged = earlier.merge(later);
+        assert_eq!(merged.added.len(), 2);
+        assert_eq!(merged.added[0].code, "IN1000");
+        assert_eq!(merged.removed.len(), 1);
+        assert_eq!(merged.source.as_deref(), Some("english"));
+    }
+
+    #[test]
+    fn test_department_and_level() {
+        assert_eq!(course("IN1000").department(), "IN");
+        assert_eq!(course("IN1000").level(), Some(1000));
+        assert_eq!(course("MAT-INF1100").department(), "MAT-INF");
+        assert_eq!(course("HFLESER2031").level(), Some(2000));
+        assert_eq!(course("INF9380").level(), Some(9000));
+        assert_eq!(course("EXPHIL").department(), "EXPHIL");
+        assert_eq!(course("EXPHIL").level(), None);
+    }
+
+    #[test]
+    fn test_course_change_fields() {
+        let before = Course::new(
+            "IN1000".to_string(),
+            "Programmering".to_string(),
+            5.0,
+            "https://www.uio.no/studier/emner/IN1000/".to_string(),
+            "MatNat".to_string(),
+        );
+        assert!(CourseChange::between(&before, &before.clone()).is_none());
+
+        let after = Course {
+            points: 10.0,
+            name: "Programmering 1".to_string(),
+            ..before.clone()
+        };
+        let change = CourseChange::between(&before, &after).unwrap();
+        assert_eq!(change.code(), "IN1000");
+        assert_eq!(
+            change.summary(),
+            "name: Programmering → Programmering 1, points: 5 → 10"
+        );
+    }
+
+    #[test]
+    fn test_format_absence() {
+        assert_eq!(format_absence(30), "<1m");
+        assert_eq!(format_absence(12 * 60), "12m");
+        assert_eq!(format_absence(2 * 3600 + 15 * 60), "2h 15m");
+        assert_eq!(format_absence(3 * 86400 + 4 * 3600 + 59), "3d 4h");
+    }
+
+    #[test]
+    fn test_course_gaining_a_link_is_a_change() {
+        let after = Course {
+            url: "https://www.uio.no/studier/emner/IN1000/".to_string(),
+            ..course("IN1000")
+        };
+        let change = CourseChange::between(&course("IN1000"), &after).unwrap();
+        assert_eq!(change.fields.len(), 1);
+        assert_eq!(change.fields[0].field, "url");
     }
 }
@@ -3,7 +3,7 @@ use async_trait::async_trait;
 use tracing::{debug, info, instrument};
 
 use super::Notifier;
-use crate::models::{Course, ScrapeDiff};
+use crate::models::{Course, CourseChange, Reappearance, ScrapeDiff};
 
 pub struct ConsoleNotifier;
 
@@ -28,7 +28,9 @@ impl Notifier for ConsoleNotifier {
     #[instrument(skip(self, diff), fields(
         notifier = "console",
         added = diff.added.len(),
-        removed = diff.removed.len()
+        removed = diff.removed.len(),
+        changed = diff.changed.len(),
+        reappeared = diff.reappeared.len()
     ))]
     async fn notify(&self, diff: &ScrapeDiff) -> Result<()> {
         if diff.is_empty() {
@@ -39,27 +41,30 @@ impl Notifier for ConsoleNotifier {
         debug!(
             added_codes = ?diff.added.iter().map(|c| c.code.as_str()).collect::<Vec<_>>(),
             removed_codes = ?diff.removed.iter().map(|c| c.code.as_str()).collect::<Vec<_>>(),
+            changed_codes = ?diff.changed.iter().map(|c| c.code()).collect::<Vec<_>>(),
+            reappeared_codes = ?diff.reappeared.iter().map(|r| r.course.code.as_str()).collect::<Vec<_>>(),
             "Writing changes to console"
         );
 
         println!("\n{}", "=".repeat(60));
-        println!("COURSE AVAILABILITY CHANGES");
-        println!("{}", "=".repeat(60));
-
-        if !diff.added.is_empty() {
-            println!("\n[+] NEW COURSES AVAILABLE ({}):", diff.added.len());
-            println!("{}", "-".repeat(40));
-            for course in &diff.added {
-                print_course(course, "+");
-            }
+        match diff.source {
+            Some(ref source) => println!("COURSE AVAILABILITY CHANGES ({})", source),
+            None => println!("COURSE AVAILABILITY CHANGES"),
         }
+        println!("{}", "=".repeat(60));
 
-        if !diff.removed.is_empty() {
-            println!("\n[-] COURSES NO LONGER AVAILABLE ({}):", diff.removed.len());
-            println!("{}", "-".repeat(40));
-            for course in &diff.removed {
-                print_course(course, "-");
+        if diff.has_watch_hits() {
+            let (watched, rest) = diff.split_watched();
+            println!("\n[!] WATCHLIST ({}):", watched.total_changes());
+            println!("{}", "#".repeat(40));
+            print_sections(&watched);
+            if !rest.is_empty() {
+                println!("\n[=] OTHER CHANGES ({}):", rest.total_changes());
+                println!("{}", "#".repeat(40));
+                print_sections(&rest);
             }
+        } else {
+            print_sections(diff);
         }
 
         println!("\n{}", "=".repeat(60));
@@ -68,6 +73,9 @@ impl Notifier for ConsoleNotifier {
             notifier = "console",
             added_count = diff.added.len(),
             removed_count = diff.removed.len(),
+            changed_count = diff.changed.len(),
+            reappeared_count = diff.reappeared.len(),
+            watched_count = diff.watched.len(),
             total_changes = diff.total_changes(),
             "Console notification displayed"
         );
@@ -76,6 +84,40 @@ impl Notifier for ConsoleNotifier {
     }
 }
 
+fn print_sections(diff: &ScrapeDiff) {
+    if !diff.added.is_empty() {
+        println!("\n[+] NEW COURSES AVAILABLE ({}):", diff.added.len());
+        println!("{}", "-".repeat(40));
+        for course in &diff.added {
+            print_course(course, "+");
+        }
+    }
+
+    if !diff.removed.is_empty() {
+        println!("\n[-] COURSES NO LONGER AVAILABLE ({}):", diff.removed.len());
+        println!("{}", "-".repeat(40));
+        for course in &diff.removed {
+            print_course(course, "-");
+        }
+    }
+
+    if !diff.reappeared.is_empty() {
+        println!("\n[*] COURSES AVAILABLE AGAIN ({}):", diff.reappeared.len());
+        println!("{}", "-".repeat(40));
+        for reappearance in &diff.reappeared {
+            print_reappearance(reappearance);
+        }
+    }
+
+    if !diff.changed.is_empty() {
+        println!("\n[~] COURSES MODIFIED ({}):", diff.changed.len());
+        println!("{}", "-".repeat(40));
+        for change in &diff.changed {
+            print_change(change);
+        }
+    }
+}
+
 fn print_course(course: &Course, prefix: &str) {
     println!(
         "[{}] {} - {}",
@@ -85,6 +127,35 @@ fn print_course(course: &Course, prefix: &str) {
         "    Points: {} | Faculty: {}",
         course.points, course.faculty
     );
+    if let Some(summary) = course.details.as_ref().map(|d| d.summary()).filter(|s| !s.is_empty()) {
+        println!("    Details: {}", summary);
+    }
+    if !course.url.is_empty() {
+        println!("    URL: {}", course.url);
+    }
+    println!();
+}
+
+fn print_change(change: &CourseChange) {
+    println!("[~] {} - {}", change.code(), change.after.name);
+    for field in &change.fields {
+        println!("    {}: {} -> {}", field.field, field.before, field.after);
+    }
+    println!();
+}
+
+fn print_reappearance(reappearance: &Reappearance) {
+    let course = &reappearance.course;
+    println!("[*] {} - {}", course.code, course.name);
+    println!(
+        "    Back after {} | Available {} time(s) before",
+        reappearance.absence(),
+        reappearance.previous_appearances
+    );
+    println!(
+        "    Points: {} | Faculty: {}",
+        course.points, course.faculty
+    );
     if !course.url.is_empty() {
         println!("    URL: {}", course.url);
     }
@@ -1,19 +1,25 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::time::Instant;
 use tracing::{debug, info, instrument, warn};
 
-use super::Notifier;
-use crate::models::{Course, ScrapeDiff};
+use super::{html_escape, Language, Notifier, Texts};
+use crate::config::DEFAULT_SOURCE;
+use crate::models::{Course, CourseChange, Reappearance, ScrapeDiff};
 
 const RESEND_API_URL: &str = "https://api.resend.com/emails";
 
+#[derive(Clone)]
 pub struct EmailNotifier {
     client: reqwest::Client,
     api_key: String,
     from: String,
     to: Vec<String>,
+    language: Language,
+    /// Public page of each source, linked in the footer
+    page_urls: HashMap<String, String>,
 }
 
 impl EmailNotifier {
@@ -24,15 +30,49 @@ impl EmailNotifier {
             api_key,
             from,
             to,
+            language: Language::default(),
+            page_urls: HashMap::new(),
+        }
+    }
+
+    /// Link the footer to the page of the source a diff came from; sources without
+    /// an entry (e.g. read from a file) get no link
+    pub fn with_page_urls(mut self, page_urls: HashMap<String, String>) -> Self {
+        self.page_urls = page_urls;
+        self
+    }
+
+    /// The same sender, for other recipients and in their language
+    pub fn for_recipients(&self, to: Vec<String>, language: Language) -> Self {
+        Self {
+            to,
+            language,
+            ..self.clone()
         }
     }
 
     fn build_email_content(&self, diff: &ScrapeDiff) -> (String, String) {
-        let subject = format!(
-            "UiO Emnevarsel: {} nye, {} fjernet",
+        let texts = self.language.texts();
+        let mut counts = format!(
+            "{} {}, {} {}",
             diff.added.len(),
-            diff.removed.len()
+            texts.count_new,
+            diff.removed.len(),
+            texts.count_removed
         );
+        if !diff.reappeared.is_empty() {
+            counts.push_str(&format!(", {} {}", diff.reappeared.len(), texts.count_back));
+        }
+        if !diff.changed.is_empty() {
+            counts.push_str(&format!(", {} {}", diff.changed.len(), texts.count_changed));
+        }
+        let mut subject = match diff.source {
+            Some(ref source) => format!("{} ({}): {}", texts.title, source, counts),
+            None => format!("{}: {}", texts.title, counts),
+        };
+        if diff.has_watch_hits() {
+            subject = format!("{} {}", texts.watched_tag, subject);
+        }
 
         let mut html = String::new();
         html.push_str(r#"<!DOCTYPE html><html><head><style>"#);
@@ -42,6 +82,9 @@ impl EmailNotifier {
             h2 { color: #0066cc; margin-top: 30px; }
             .course { background: #f5f5f5; border-left: 4px solid #0066cc; padding: 15px; margin: 10px 0; }
             .course.removed { border-left-color: #cc3333; }
+            .course.changed { border-left-color: #cc8800; }
+            .watch { border: 2px solid #cc3333; padding: 0 15px 10px; margin-top: 20px; }
+            .watch h2 { color: #cc3333; }
             .course-code { font-weight: bold; font-size: 1.1em; }
             .course-name { color: #333; margin: 5px 0; }
             .course-meta { color: #666; font-size: 0.9em; }
@@ -50,28 +93,31 @@ impl EmailNotifier {
         "#);
         html.push_str("</style></head><body>");
 
-        html.push_str("<h1>Endringer i ledige plasser ved UiO</h1>");
+        html.push_str(&format!("<h1>{}</h1>", texts.heading));
+        if let Some(ref source) = diff.source {
+            html.push_str(&format!(r#"<p class="course-meta">{}: {}</p>"#, texts.source, html_escape(source)));
+        }
 
-        if !diff.added.is_empty() {
-            html.push_str(&format!("<h2>Nye ledige plasser ({})</h2>", diff.added.len()));
-            for course in &diff.added {
-                html.push_str(&format_course_html(course, false));
-            }
+        if diff.has_watch_hits() {
+            let (watched, rest) = diff.split_watched();
+            html.push_str(r#"<div class="watch">"#);
+            html.push_str(&format!("<h2>{} ({})</h2>", texts.watched, watched.total_changes()));
+            push_sections(&mut html, &watched, "h3", texts);
+            html.push_str("</div>");
+            push_sections(&mut html, &rest, "h2", texts);
+        } else {
+            push_sections(&mut html, diff, "h2", texts);
         }
 
-        if !diff.removed.is_empty() {
+        html.push_str(r#"<div class="footer">"#);
+        html.push_str(texts.footer);
+        if let Some(page_url) = self.page_urls.get(diff.source.as_deref().unwrap_or(DEFAULT_SOURCE)) {
             html.push_str(&format!(
-                "<h2>Ikke lenger ledige plasser ({})</h2>",
-                diff.removed.len()
+                r#"<br><a href="{}">{}</a>"#,
+                html_escape(page_url),
+                texts.footer_link
             ));
-            for course in &diff.removed {
-                html.push_str(&format_course_html(course, true));
-            }
         }
-
-        html.push_str(r#"<div class="footer">"#);
-        html.push_str("Denne varslingen ble sendt av UiOBot - Overvåker ledige plasser.<br>");
-        html.push_str(r#"<a href="https://www.uio.no/studier/emner/ledige-plasser/">Se alle emner med ledige plasser</a>"#);
         html.push_str("</div>");
         html.push_str("</body></html>");
 
@@ -79,30 +125,116 @@ impl EmailNotifier {
     }
 }
 
-fn format_course_html(course: &Course, is_removed: bool) -> String {
+/// Render the added/removed/reappeared/changed sections with the given heading tag
+fn push_sections(html: &mut String, diff: &ScrapeDiff, h: &str, texts: &Texts) {
+    if !diff.added.is_empty() {
+        html.push_str(&format!("<{h}>{} ({})</{h}>", texts.added_heading, diff.added.len()));
+        for course in &diff.added {
+            html.push_str(&format_course_html(course, false, None, texts));
+        }
+    }
+
+    if !diff.removed.is_empty() {
+        html.push_str(&format!(
+            "<{h}>{} ({})</{h}>",
+            texts.removed_heading,
+            diff.removed.len()
+        ));
+        for course in &diff.removed {
+            html.push_str(&format_course_html(course, true, None, texts));
+        }
+    }
+
+    if !diff.reappeared.is_empty() {
+        html.push_str(&format!(
+            "<{h}>{} ({})</{h}>",
+            texts.reappeared_heading,
+            diff.reappeared.len()
+        ));
+        for reappearance in &diff.reappeared {
+            html.push_str(&format_reappearance_html(reappearance, texts));
+        }
+    }
+
+    if !diff.changed.is_empty() {
+        html.push_str(&format!("<{h}>{} ({})</{h}>", texts.changed_heading, diff.changed.len()));
+        for change in &diff.changed {
+            html.push_str(&format_change_html(change));
+        }
+    }
+}
+
+fn format_course_html(course: &Course, is_removed: bool, note: Option<&str>, texts: &Texts) -> String {
     let class = if is_removed { "course removed" } else { "course" };
     let mut html = format!(r#"<div class="{}">"#, class);
 
     if !course.url.is_empty() {
         html.push_str(&format!(
             r#"<div class="course-code"><a href="{}">{}</a></div>"#,
-            course.url, course.code
+            html_escape(&course.url),
+            html_escape(&course.code)
         ));
     } else {
         html.push_str(&format!(
             r#"<div class="course-code">{}</div>"#,
-            course.code
+            html_escape(&course.code)
         ));
     }
 
     html.push_str(&format!(
         r#"<div class="course-name">{}</div>"#,
-        course.name
+        html_escape(&course.name)
     ));
     html.push_str(&format!(
-        r#"<div class="course-meta">{} studiepoeng | {}</div>"#,
-        course.points, course.faculty
+        r#"<div class="course-meta">{} {} | {}</div>"#,
+        course.points,
+        texts.points,
+        html_escape(&course.faculty)
+    ));
+    if let Some(summary) = course.details.as_ref().map(|d| d.summary()).filter(|s| !s.is_empty()) {
+        html.push_str(&format!(r#"<div class="course-meta">{}</div>"#, html_escape(&summary)));
+    }
+    if let Some(note) = note {
+        html.push_str(&format!(r#"<div class="course-meta">{}</div>"#, html_escape(note)));
+    }
+    html.push_str("</div>");
+    html
+}
+
+fn format_reappearance_html(reappearance: &Reappearance, texts: &Texts) -> String {
+    let note = texts.reappearance_note(&reappearance.absence(), reappearance.previous_appearances);
+    format_course_html(&reappearance.course, false, Some(&note), texts)
+}
+
+fn format_change_html(change: &CourseChange) -> String {
+    let course = &change.after;
+    let mut html = String::from(r#"<div class="course changed">"#);
+
+    if !course.url.is_empty() {
+        html.push_str(&format!(
+            r#"<div class="course-code"><a href="{}">{}</a></div>"#,
+            html_escape(&course.url),
+            html_escape(&course.code)
+        ));
+    } else {
+        html.push_str(&format!(
+            r#"<div class="course-code">{}</div>"#,
+            html_escape(&course.code)
+        ));
+    }
+
+    html.push_str(&format!(
+        r#"<div class="course-name">{}</div>"#,
+        html_escape(&course.name)
     ));
+    for field in &change.fields {
+        html.push_str(&format!(
+            r#"<div class="course-meta">{}: <s>{}</s> &rarr; {}</div>"#,
+            field.field,
+            html_escape(&field.before),
+            html_escape(&field.after)
+        ));
+    }
     html.push_str("</div>");
     html
 }
@@ -125,7 +257,9 @@ impl Notifier for EmailNotifier {
         notifier = "email",
         recipients = ?self.to,
         added = diff.added.len(),
-        removed = diff.removed.len()
+        removed = diff.removed.len(),
+        changed = diff.changed.len(),
+        reappeared = diff.reappeared.len()
     ))]
     async fn notify(&self, diff: &ScrapeDiff) -> Result<()> {
         if diff.is_empty() {
@@ -145,6 +279,8 @@ impl Notifier for EmailNotifier {
             html_size_bytes = html.len(),
             added_courses = diff.added.len(),
             removed_courses = diff.removed.len(),
+            changed_courses = diff.changed.len(),
+            reappeared_courses = diff.reappeared.len(),
             "Preparing to send email"
         );
 
@@ -201,6 +337,8 @@ impl Notifier for EmailNotifier {
             subject = %subject,
             added_courses = diff.added.len(),
             removed_courses = diff.removed.len(),
+            changed_courses = diff.changed.len(),
+            reappeared_courses = diff.reappeared.len(),
             duration_ms = start.elapsed().as_millis(),
             response = %response_body,
             "Email sent successfully via Resend API"
@@ -209,3 +347,118 @@ impl Notifier for EmailNotifier {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::FieldChange;
+    use std::collections::BTreeSet;
+
+    fn course(code: &str) -> Course {
+        Course::new(
+            code.to_string(),
+            format!("Emne {}", code),
+            10.0,
+            format!("https://www.uio.no/studier/emner/{}/", code),
+            "MatNat".to_string(),
+        )
+    }
+
+    fn notifier() -> EmailNotifier {
+        EmailNotifier::new(String::new(), String::new(), Vec::new())
+    }
+
+    #[test]
+    fn test_footer_links_the_source_page() {
+        let page_urls = HashMap::from([
+            (DEFAULT_SOURCE.to_string(), "https://www.uio.no/studier/emner/ledige-plasser/".to_string()),
+            ("english".to_string(), "https://www.uio.no/english/studies/courses/vacancies/".to_string()),
+        ]);
+        let notifier = notifier().with_page_urls(page_urls);
+        let footer = |diff: &ScrapeDiff| {
+            let (_, html) = notifier.build_email_content(diff);
+            html[html.find(r#"<div class="footer">"#).unwrap()..].to_string()
+        };
+
+        let diff = ScrapeDiff::new(vec![course("IN1000")], vec![]);
+        assert!(footer(&diff).contains(r#"href="https://www.uio.no/studier/emner/ledige-plasser/""#));
+        let english = footer(&diff.clone().with_source("english"));
+        assert!(english.contains(r#"href="https://www.uio.no/english/studies/courses/vacancies/""#));
+
+        // No link for a source without a public page
+        assert!(!footer(&diff.with_source("local")).contains("<a "));
+    }
+
+    #[test]
+    fn test_watched_section_comes_first() {
+        let modified = Course {
+            points: 5.0,
+            ..course("IN2010")
+        };
+        let mut diff = ScrapeDiff::new(vec![course("IN1000"), course("IN1050")], vec![course("MAT1100")])
+            .with_source("regular")
+            .with_watched(BTreeSet::from(["IN1050".to_string()]));
+        diff.reappeared.push(Reappearance {
+            course: course("SOS1000"),
+            absent_seconds: 3 * 3600,
+            previous_appearances: 1,
+        });
+        diff.changed.push(CourseChange::between(&course("IN2010"), &modified).unwrap());
+
+        let (subject, html) = notifier().build_email_content(&diff);
+        assert_eq!(
+            subject,
+            "[Overvåket] UiO Emnevarsel (regular): 2 nye, 1 fjernet, 1 tilbake, 1 endret"
+        );
+
+        let position = |text: &str| html.find(text).unwrap_or_else(|| panic!("missing {}", text));
+        let order = [
+            r#"<div class="watch"><h2>Overvåkede emner (1)</h2><h3>Nye ledige plasser (1)</h3>"#,
+            "/IN1050/",
+            "<h2>Nye ledige plasser (1)</h2>",
+            "/IN1000/",
+            "<h2>Ikke lenger ledige plasser (1)</h2>",
+            "<h2>Ledige plasser igjen (1)</h2>",
+            "/SOS1000/",
+            "<h2>Endrede emner (1)</h2>",
+            "points: <s>10</s> &rarr; 5",
+        ];
+        for pair in order.windows(2) {
+            assert!(position(pair[0]) < position(pair[1]), "{} before {}", pair[0], pair[1]);
+        }
+
+        // Without watch hits there is no tag and no watched block
+        let (subject, html) = notifier().build_email_content(&ScrapeDiff::new(vec![course("IN1000")], vec![]));
+        assert_eq!(subject, "UiO Emnevarsel: 1 nye, 0 fjernet");
+        assert!(!html.contains(r#"<div class="watch">"#));
+    }
+
+    #[test]
+    fn test_scraped_text_is_escaped() {
+        let before = Course {
+            name: "Algoritmer & <data>".to_string(),
+            ..course("IN2010")
+        };
+        let after = Course {
+            name: "Algoritmer <b>".to_string(),
+            ..before.clone()
+        };
+        let change = CourseChange {
+            before: before.clone(),
+            after: after.clone(),
+            fields: vec![FieldChange {
+                field: "name".to_string(),
+                before: before.name.clone(),
+                after: after.name.clone(),
+            }],
+        };
+        let mut diff = ScrapeDiff::new(vec![before], vec![]);
+        diff.changed.push(change);
+
+        let (_, html) = notifier().build_email_content(&diff);
+        assert!(html.contains("Algoritmer &amp; &lt;data&gt;"));
+        assert!(html.contains("<s>Algoritmer &amp; &lt;data&gt;</s> &rarr; Algoritmer &lt;b&gt;"));
+        assert!(!html.contains("<b>"));
+        assert!(!html.contains("<data>"));
+    }
+}
@@ -0,0 +1,131 @@
+use anyhow::{bail, Result};
+
+/// Language email and SMS notifications are written in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    Norwegian,
+    English,
+}
+
+impl Language {
+    /// "no"/"nb"/"norsk" or "en"/"english"
+    pub fn parse(text: &str) -> Result<Self> {
+        match text.trim().to_lowercase().as_str() {
+            "no" | "nb" | "norsk" | "norwegian" => Ok(Language::Norwegian),
+            "en" | "english" => Ok(Language::English),
+            other => bail!("Unknown language '{}': use no or en", other),
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::Norwegian => "no",
+            Language::English => "en",
+        }
+    }
+
+    pub fn texts(&self) -> &'static Texts {
+        match self {
+            Language::Norwegian => &NORWEGIAN,
+            Language::English => &ENGLISH,
+        }
+    }
+}
+
+/// Fixed wording of email and SMS notifications
+pub struct Texts {
+    pub title: &'static str,
+    pub heading: &'static str,
+    pub source: &'static str,
+    pub watched_tag: &'static str,
+    pub watched: &'static str,
+    pub other_changes: &'static str,
+    pub count_new: &'static str,
+    pub count_removed: &'static str,
+    pub count_back: &'static str,
+    pub count_changed: &'static str,
+    pub added_heading: &'static str,
+    pub removed_heading: &'static str,
+    pub reappeared_heading: &'static str,
+    pub changed_heading: &'static str,
+    pub sms_added: &'static str,
+    pub sms_removed: &'static str,
+    pub sms_reappeared: &'static str,
+    pub sms_changed: &'static str,
+    pub points: &'static str,
+    pub points_short: &'static str,
+    pub footer: &'static str,
+    pub footer_link: &'static str,
+    back_after: &'static str,
+    gone_for: &'static str,
+    times_before: &'static str,
+}
+
+impl Texts {
+    /// "Tilbake etter 2h 15m | ledig 1 gang(er) før"
+    pub fn reappearance_note(&self, absence: &str, previous_appearances: u32) -> String {
+        format!("{} {} | {}", self.back_after, absence, self.times_before.replace("{}", &previous_appearances.to_string()))
+    }
+
+    /// "borte i 2h 15m, ledig 1 gang(er) før", for one-line formats (SMS, Slack)
+    pub fn short_reappearance_note(&self, absence: &str, previous_appearances: u32) -> String {
+        format!("{} {}, {}", self.gone_for, absence, self.times_before.replace("{}", &previous_appearances.to_string()))
+    }
+}
+
+static NORWEGIAN: Texts = Texts {
+    title: "UiO Emnevarsel",
+    heading: "Endringer i ledige plasser ved UiO",
+    source: "Kilde",
+    watched_tag: "[Overvåket]",
+    watched: "Overvåkede emner",
+    other_changes: "Andre endringer",
+    count_new: "nye",
+    count_removed: "fjernet",
+    count_back: "tilbake",
+    count_changed: "endret",
+    added_heading: "Nye ledige plasser",
+    removed_heading: "Ikke lenger ledige plasser",
+    reappeared_heading: "Ledige plasser igjen",
+    changed_heading: "Endrede emner",
+    sms_added: "Nye",
+    sms_removed: "Fjernet",
+    sms_reappeared: "Tilbake",
+    sms_changed: "Endret",
+    points: "studiepoeng",
+    points_short: "stp",
+    footer: "Denne varslingen ble sendt av UiOBot - Overvåker ledige plasser.",
+    footer_link: "Se alle emner med ledige plasser",
+    back_after: "Tilbake etter",
+    gone_for: "borte i",
+    times_before: "ledig {} gang(er) før",
+};
+
+static ENGLISH: Texts = Texts {
+    title: "UiO course alert",
+    heading: "Changes in available places at UiO",
+    source: "Source",
+    watched_tag: "[Watched]",
+    watched: "Watched courses",
+    other_changes: "Other changes",
+    count_new: "new",
+    count_removed: "removed",
+    count_back: "back",
+    count_changed: "modified",
+    added_heading: "Newly available places",
+    removed_heading: "No longer available",
+    reappeared_heading: "Available again",
+    changed_heading: "Modified courses",
+    sms_added: "New",
+    sms_removed: "Removed",
+    sms_reappeared: "Back",
+    sms_changed: "Modified",
+    points: "credits",
+    points_short: "ECTS",
+    footer: "This notification was sent by UiOBot - monitoring available places.",
+    footer_link: "See all courses with available places",
+    back_after: "Back after",
+    gone_for: "gone for",
+    times_before: "available {} time(s) before",
+};
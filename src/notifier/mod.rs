@@ -1,9 +1,13 @@
 mod console;
 mod email;
+mod language;
+mod slack;
 mod sms;
 
 pub use console::ConsoleNotifier;
 pub use email::EmailNotifier;
+pub use language::{Language, Texts};
+pub use slack::SlackNotifier;
 pub use sms::SmsNotifier;
 
 use anyhow::Result;
@@ -12,6 +16,10 @@ use std::time::Instant;
 use tracing::{debug, info, instrument};
 
 use crate::models::ScrapeDiff;
+use crate::route::Route;
+
+/// Names of all notifiers, as returned by `Notifier::name`
+pub const NOTIFIER_NAMES: &[&str] = &["console", "email", "sms", "slack"];
 
 #[async_trait]
 pub trait Notifier: Send + Sync {
@@ -24,7 +32,8 @@ pub trait Notifier: Send + Sync {
 
 /// Collection of notifiers that can be notified together
 pub struct NotifierChain {
-    notifiers: Vec<Box<dyn Notifier>>,
+    /// Each notifier with the route selecting what it receives (None receives everything)
+    notifiers: Vec<(Box<dyn Notifier>, Option<Route>)>,
 }
 
 impl NotifierChain {
@@ -34,37 +43,57 @@ impl NotifierChain {
 
     pub fn add<N: Notifier + 'static>(&mut self, notifier: N) {
         debug!(notifier = notifier.name(), "Adding notifier to chain");
-        self.notifiers.push(Box::new(notifier));
+        self.notifiers.push((Box::new(notifier), None));
+    }
+
+    /// Add a notifier that only receives the changes selected by `route`, and nothing when none are
+    pub fn add_routed<N: Notifier + 'static>(&mut self, notifier: N, route: Route) {
+        debug!(notifier = notifier.name(), route = %route, "Adding routed notifier to chain");
+        self.notifiers.push((Box::new(notifier), Some(route)));
     }
 
     pub fn len(&self) -> usize {
         self.notifiers.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.notifiers.is_empty()
+    }
+
     #[instrument(skip(self, diff), fields(
         notifier_count = self.notifiers.len(),
         added = diff.added.len(),
-        removed = diff.removed.len()
+        removed = diff.removed.len(),
+        changed = diff.changed.len(),
+        reappeared = diff.reappeared.len()
     ))]
     pub async fn notify_all(&self, diff: &ScrapeDiff) -> Vec<(&'static str, Result<()>)> {
         let start = Instant::now();
-        let notifier_names: Vec<_> = self.notifiers.iter().map(|n| n.name()).collect();
+        let notifier_names: Vec<_> = self.notifiers.iter().map(|(n, _)| n.name()).collect();
 
         info!(
             notifiers = ?notifier_names,
             changes_added = diff.added.len(),
             changes_removed = diff.removed.len(),
+            changes_changed = diff.changed.len(),
+            changes_reappeared = diff.reappeared.len(),
             "Starting notification dispatch"
         );
 
         let mut results = Vec::new();
-        for notifier in &self.notifiers {
+        for (notifier, route) in &self.notifiers {
             let notifier_start = Instant::now();
             let name = notifier.name();
 
+            let routed = route.as_ref().map(|route| route.apply(diff));
+            if routed.as_ref().is_some_and(ScrapeDiff::is_empty) {
+                debug!(notifier = name, "No changes routed to notifier, skipping");
+                continue;
+            }
+
             debug!(notifier = name, "Dispatching to notifier");
 
-            let result = notifier.notify(diff).await;
+            let result = notifier.notify(routed.as_ref().unwrap_or(diff)).await;
             let success = result.is_ok();
 
             debug!(
@@ -92,8 +121,24 @@ impl NotifierChain {
     }
 }
 
+/// Senders used for subscribers, when the provider is configured (recipients are set per subscriber)
+#[derive(Clone, Default)]
+pub struct Channels {
+    pub email: Option<EmailNotifier>,
+    pub sms: Option<SmsNotifier>,
+}
+
 impl Default for NotifierChain {
     fn default() -> Self {
         Self::new()
     }
 }
+
+/// Simple HTML escaping, for page and email text taken from scraped pages
+pub fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#x27;")
+}
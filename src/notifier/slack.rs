@@ -0,0 +1,431 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, instrument, warn};
+
+use super::{Language, Notifier, Texts};
+use crate::config::DEFAULT_SOURCE;
+use crate::models::{Course, ScrapeDiff};
+
+/// Slack rejects section blocks with longer text
+const MAX_SECTION_CHARS: usize = 3000;
+
+/// Slack rejects messages with more blocks
+const MAX_BLOCKS: usize = 50;
+
+/// Posts Block Kit messages to a Slack incoming webhook
+#[derive(Clone)]
+pub struct SlackNotifier {
+    client: reqwest::Client,
+    webhook_url: String,
+    /// Public page of each source, linked in the footer
+    page_urls: HashMap<String, String>,
+}
+
+impl SlackNotifier {
+    pub fn new(webhook_url: String, timeout: Duration) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .context("Failed to build HTTP client for Slack")?;
+        Ok(Self {
+            client,
+            webhook_url,
+            page_urls: HashMap::new(),
+        })
+    }
+
+    /// Link the footer to the page of the source a diff came from; sources without
+    /// an entry (e.g. read from a file) get no link
+    pub fn with_page_urls(mut self, page_urls: HashMap<String, String>) -> Self {
+        self.page_urls = page_urls;
+        self
+    }
+
+    fn build_message(&self, diff: &ScrapeDiff) -> Value {
+        // A channel is shared by everyone in it, so there is no per-recipient language
+        let texts = Language::default().texts();
+        let title = match diff.source {
+            Some(ref source) => format!("{} ({})", texts.title, source),
+            None => texts.title.to_string(),
+        };
+        let mut summary = format!(
+            "{}: {} {}, {} {}",
+            title,
+            diff.added.len(),
+            texts.count_new,
+            diff.removed.len(),
+            texts.count_removed
+        );
+        if !diff.reappeared.is_empty() {
+            summary.push_str(&format!(", {} {}", diff.reappeared.len(), texts.count_back));
+        }
+        if !diff.changed.is_empty() {
+            summary.push_str(&format!(", {} {}", diff.changed.len(), texts.count_changed));
+        }
+        if diff.has_watch_hits() {
+            summary = format!("{} {}", texts.watched_tag, summary);
+        }
+
+        let mut blocks = vec![json!({
+            "type": "header",
+            "text": { "type": "plain_text", "text": title, "emoji": true }
+        })];
+
+        if diff.has_watch_hits() {
+            let (watched, rest) = diff.split_watched();
+            blocks.push(section(&format!(
+                ":rotating_light: *{} ({})*",
+                texts.watched,
+                watched.total_changes()
+            )));
+            push_sections(&mut blocks, &watched, texts);
+            if !rest.is_empty() {
+                blocks.push(json!({ "type": "divider" }));
+                blocks.push(section(&format!("*{} ({})*", texts.other_changes, rest.total_changes())));
+                push_sections(&mut blocks, &rest, texts);
+            }
+        } else {
+            push_sections(&mut blocks, diff, texts);
+        }
+
+        let page_url = self.page_urls.get(diff.source.as_deref().unwrap_or(DEFAULT_SOURCE));
+        let footer = page_url.map(|url| {
+            json!({
+                "type": "context",
+                "elements": [{
+                    "type": "mrkdwn",
+                    "text": format!("<{}|{}>", escape(url), texts.footer_link)
+                }]
+            })
+        });
+        if blocks.len() >= MAX_BLOCKS {
+            warn!(blocks = blocks.len(), max_blocks = MAX_BLOCKS, "Slack message truncated");
+            blocks.truncate(MAX_BLOCKS - 2);
+            blocks.push(section("…"));
+        }
+        blocks.extend(footer);
+
+        // `text` is the fallback shown in notifications and by clients without Block Kit
+        json!({ "text": summary, "blocks": blocks })
+    }
+}
+
+/// Add the added/removed/reappeared/changed sections, each split to fit Slack's limits
+fn push_sections(blocks: &mut Vec<Value>, diff: &ScrapeDiff, texts: &Texts) {
+    if !diff.added.is_empty() {
+        let lines = diff.added.iter().map(|c| course_line(c, None, texts));
+        push_list(blocks, &format!(":new: *{} ({})*", texts.added_heading, diff.added.len()), lines);
+    }
+
+    if !diff.removed.is_empty() {
+        let lines = diff.removed.iter().map(|c| course_line(c, None, texts));
+        push_list(
+            blocks,
+            &format!(":x: *{} ({})*", texts.removed_heading, diff.removed.len()),
+            lines,
+        );
+    }
+
+    if !diff.reappeared.is_empty() {
+        let lines = diff.reappeared.iter().map(|r| {
+            let note = texts.short_reappearance_note(&r.absence(), r.previous_appearances);
+            course_line(&r.course, Some(&note), texts)
+        });
+        push_list(
+            blocks,
+            &format!(":recycle: *{} ({})*", texts.reappeared_heading, diff.reappeared.len()),
+            lines,
+        );
+    }
+
+    if !diff.changed.is_empty() {
+        let lines = diff
+            .changed
+            .iter()
+            .map(|change| format!("• {} - {}", course_link(&change.after), escape(&change.summary())));
+        push_list(
+            blocks,
+            &format!(":pencil2: *{} ({})*", texts.changed_heading, diff.changed.len()),
+            lines,
+        );
+    }
+}
+
+/// "• <url|IN1000> - Programmering (10 stp)", with an optional note after the points
+fn course_line(course: &Course, note: Option<&str>, texts: &Texts) -> String {
+    let mut line = format!(
+        "• {} - {} ({} {})",
+        course_link(course),
+        escape(&course.name),
+        course.points,
+        texts.points_short
+    );
+    if let Some(note) = note {
+        line.push_str(&format!(", {}", escape(note)));
+    }
+    line
+}
+
+/// "<url|IN1000>" for courses with an absolute link, otherwise just the code
+fn course_link(course: &Course) -> String {
+    if course.url.starts_with("https://") || course.url.starts_with("http://") {
+        format!("<{}|{}>", escape(&course.url), escape(&course.code))
+    } else {
+        escape(&course.code)
+    }
+}
+
+/// A heading followed by one line per item, in as many sections as the text needs
+fn push_list(blocks: &mut Vec<Value>, heading: &str, lines: impl Iterator<Item = String>) {
+    let mut text = heading.to_string();
+    for line in lines {
+        if text.chars().count() + 1 + line.chars().count() > MAX_SECTION_CHARS {
+            blocks.push(section(&text));
+            text = String::new();
+        }
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        text.push_str(&line);
+    }
+    blocks.push(section(&text));
+}
+
+fn section(text: &str) -> Value {
+    json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text }
+    })
+}
+
+/// Escape the characters Slack treats as markup in message text
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[async_trait]
+impl Notifier for SlackNotifier {
+    fn name(&self) -> &'static str {
+        "slack"
+    }
+
+    #[instrument(skip(self, diff), fields(
+        notifier = "slack",
+        added = diff.added.len(),
+        removed = diff.removed.len(),
+        changed = diff.changed.len(),
+        reappeared = diff.reappeared.len()
+    ))]
+    async fn notify(&self, diff: &ScrapeDiff) -> Result<()> {
+        if diff.is_empty() {
+            debug!("No changes to notify, skipping Slack");
+            return Ok(());
+        }
+
+        let start = Instant::now();
+        let message = self.build_message(diff);
+
+        info!(
+            blocks = message["blocks"].as_array().map_or(0, Vec::len),
+            added_courses = diff.added.len(),
+            removed_courses = diff.removed.len(),
+            changed_courses = diff.changed.len(),
+            reappeared_courses = diff.reappeared.len(),
+            "Posting to Slack webhook"
+        );
+
+        let response = self
+            .client
+            .post(&self.webhook_url)
+            .json(&message)
+            .send()
+            .await
+            .context("Failed to send request to Slack webhook")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            warn!(
+                status_code = status.as_u16(),
+                error = %error_text,
+                duration_ms = start.elapsed().as_millis(),
+                "Slack webhook request failed"
+            );
+            anyhow::bail!(
+                "Slack webhook error (HTTP {}): {}\n\
+                 Check that --slack-webhook-url is an active incoming webhook.",
+                status,
+                error_text
+            );
+        }
+
+        info!(
+            status_code = status.as_u16(),
+            duration_ms = start.elapsed().as_millis(),
+            "Slack notification sent"
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+    use std::collections::BTreeSet;
+    use std::sync::{Arc, Mutex};
+
+    type Received = Arc<Mutex<Vec<Value>>>;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn course(code: &str, points: f32) -> Course {
+        Course::new(
+            code.to_string(),
+            format!("Emne {} & co", code),
+            points,
+            format!("https://www.uio.no/studier/emner/{}/", code),
+            "MatNat".to_string(),
+        )
+    }
+
+    /// Local stand-in for Slack: records payloads on /hook, rejects them on /revoked
+    /// and answers /slow after the client has given up
+    async fn mock_slack() -> (String, Received) {
+        async fn hook(State(received): State<Received>, Json(body): Json<Value>) -> &'static str {
+            received.lock().unwrap().push(body);
+            "ok"
+        }
+
+        let received = Received::default();
+        let app = Router::new()
+            .route("/hook", post(hook))
+            .route("/revoked", post(|| async { (StatusCode::FORBIDDEN, "invalid_token") }))
+            .route(
+                "/slow",
+                post(|| async {
+                    tokio::time::sleep(Duration::from_secs(2)).await;
+                    "ok"
+                }),
+            )
+            .with_state(received.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{}", address), received)
+    }
+
+    fn texts_of(message: &Value) -> Vec<String> {
+        message["blocks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|block| block["text"]["text"].as_str().map(String::from))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_posts_block_kit_message() {
+        let (base, received) = mock_slack().await;
+        let notifier = SlackNotifier::new(format!("{}/hook", base), TIMEOUT).unwrap();
+        let unlinked = Course {
+            url: String::new(),
+            ..course("IN1010", 10.0)
+        };
+        let relative = Course {
+            url: "IN1020/".to_string(),
+            ..course("IN1020", 10.0)
+        };
+        let diff = ScrapeDiff::new(
+            vec![course("IN1000", 10.0), unlinked, relative],
+            vec![course("MAT1100", 5.0)],
+        )
+        .with_source("regular");
+
+        notifier.notify(&diff).await.unwrap();
+        // Nothing is posted without changes
+        notifier.notify(&ScrapeDiff::default()).await.unwrap();
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        let message = &received[0];
+        assert_eq!(message["text"], "UiO Emnevarsel (regular): 3 nye, 1 fjernet");
+        assert_eq!(message["blocks"][0]["type"], "header");
+
+        // Courses without an absolute link are listed by code only
+        let texts = texts_of(message);
+        assert_eq!(
+            texts[1],
+            ":new: *Nye ledige plasser (3)*\n\
+             • <https://www.uio.no/studier/emner/IN1000/|IN1000> - Emne IN1000 &amp; co (10 stp)\n\
+             • IN1010 - Emne IN1010 &amp; co (10 stp)\n\
+             • IN1020 - Emne IN1020 &amp; co (10 stp)"
+        );
+        assert!(texts[2].starts_with(":x: *Ikke lenger ledige plasser (1)*\n• <"));
+        assert!(texts[2].contains("|MAT1100> - Emne MAT1100 &amp; co (5 stp)"));
+    }
+
+    #[test]
+    fn test_footer_links_the_source_page() {
+        let page_urls = HashMap::from([
+            (DEFAULT_SOURCE.to_string(), "https://www.uio.no/studier/emner/ledige-plasser/".to_string()),
+            ("english".to_string(), "https://www.uio.no/english/studies/courses/vacancies/".to_string()),
+        ]);
+        let notifier = SlackNotifier::new(String::new(), TIMEOUT).unwrap().with_page_urls(page_urls);
+        let footer = |diff: &ScrapeDiff| notifier.build_message(diff)["blocks"].as_array().unwrap().last().unwrap().clone();
+
+        let diff = ScrapeDiff::new(vec![course("IN1000", 10.0)], vec![]);
+        assert_eq!(footer(&diff)["type"], "context");
+        assert!(footer(&diff)["elements"][0]["text"]
+            .as_str()
+            .unwrap()
+            .starts_with("<https://www.uio.no/studier/emner/ledige-plasser/|"));
+        let english = diff.clone().with_source("english");
+        assert!(footer(&english)["elements"][0]["text"].as_str().unwrap().contains("/english/"));
+
+        // No link for a source without a public page
+        let local = diff.with_source("local");
+        assert_eq!(footer(&local)["type"], "section");
+    }
+
+    #[tokio::test]
+    async fn test_slow_webhook_times_out() {
+        let (base, _) = mock_slack().await;
+        let notifier = SlackNotifier::new(format!("{}/slow", base), Duration::from_millis(100)).unwrap();
+        let diff = ScrapeDiff::new(vec![course("IN1000", 10.0)], vec![]);
+
+        let start = Instant::now();
+        assert!(notifier.notify(&diff).await.is_err());
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_watched_first_and_long_lists_split() {
+        let added: Vec<Course> = (0..100).map(|i| course(&format!("IN{}", 1000 + i), 10.0)).collect();
+        let diff = ScrapeDiff::new(added, vec![]).with_watched(BTreeSet::from(["IN1050".to_string()]));
+        let message = SlackNotifier::new(String::new(), TIMEOUT).unwrap().build_message(&diff);
+
+        assert!(message["text"].as_str().unwrap().starts_with("[Overvåket]"));
+        let texts = texts_of(&message);
+        assert!(texts[1].starts_with(":rotating_light: *Overvåkede emner (1)*"));
+        assert!(texts[2].contains("|IN1050>"));
+        assert!(texts.iter().all(|text| text.chars().count() <= MAX_SECTION_CHARS));
+        // 99 other courses do not fit in one section
+        assert!(texts.len() > 5);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_error_is_reported() {
+        let (base, _) = mock_slack().await;
+        let notifier = SlackNotifier::new(format!("{}/revoked", base), TIMEOUT).unwrap();
+        let diff = ScrapeDiff::new(vec![course("IN1000", 10.0)], vec![]);
+
+        let error = notifier.notify(&diff).await.unwrap_err().to_string();
+        assert!(error.contains("403"));
+        assert!(error.contains("invalid_token"));
+    }
+}
@@ -3,15 +3,17 @@ use async_trait::async_trait;
 use std::time::Instant;
 use tracing::{debug, info, instrument, warn};
 
-use super::Notifier;
+use super::{Language, Notifier, Texts};
 use crate::models::ScrapeDiff;
 
+#[derive(Clone)]
 pub struct SmsNotifier {
     client: reqwest::Client,
     account_sid: String,
     auth_token: String,
     from: String,
     to: Vec<String>,
+    language: Language,
 }
 
 impl SmsNotifier {
@@ -23,26 +25,42 @@ impl SmsNotifier {
             auth_token,
             from,
             to,
+            language: Language::default(),
+        }
+    }
+
+    /// The same sender, for other recipients and in their language
+    pub fn for_recipients(&self, to: Vec<String>, language: Language) -> Self {
+        Self {
+            to,
+            language,
+            ..self.clone()
         }
     }
 
     fn build_sms_content(&self, diff: &ScrapeDiff) -> String {
+        let texts = self.language.texts();
         let mut message = String::new();
 
-        message.push_str("UiO Emnevarsel\n");
-
-        if !diff.added.is_empty() {
-            message.push_str(&format!("\nNye ({}):\n", diff.added.len()));
-            for course in &diff.added {
-                message.push_str(&format!("• {} - {} ({} stp)\n", course.code, course.name, course.points));
-            }
+        match diff.source {
+            Some(ref source) => message.push_str(&format!("{} ({})\n", texts.title, source)),
+            None => message.push_str(&format!("{}\n", texts.title)),
         }
 
-        if !diff.removed.is_empty() {
-            message.push_str(&format!("\nFjernet ({}):\n", diff.removed.len()));
-            for course in &diff.removed {
-                message.push_str(&format!("• {} - {} ({} stp)\n", course.code, course.name, course.points));
+        if diff.has_watch_hits() {
+            let (watched, rest) = diff.split_watched();
+            message.push_str(&format!(
+                "\n!! {} ({}) !!\n",
+                texts.watched.to_uppercase(),
+                watched.total_changes()
+            ));
+            push_sections(&mut message, &watched, texts);
+            if !rest.is_empty() {
+                message.push_str(&format!("\n-- {} ({}) --\n", texts.other_changes, rest.total_changes()));
+                push_sections(&mut message, &rest, texts);
             }
+        } else {
+            push_sections(&mut message, diff, texts);
         }
 
         message
@@ -107,6 +125,50 @@ impl SmsNotifier {
     }
 }
 
+fn push_sections(message: &mut String, diff: &ScrapeDiff, texts: &Texts) {
+    if !diff.added.is_empty() {
+        message.push_str(&format!("\n{} ({}):\n", texts.sms_added, diff.added.len()));
+        for course in &diff.added {
+            message.push_str(&format!(
+                "• {} - {} ({} {})\n",
+                course.code, course.name, course.points, texts.points_short
+            ));
+        }
+    }
+
+    if !diff.removed.is_empty() {
+        message.push_str(&format!("\n{} ({}):\n", texts.sms_removed, diff.removed.len()));
+        for course in &diff.removed {
+            message.push_str(&format!(
+                "• {} - {} ({} {})\n",
+                course.code, course.name, course.points, texts.points_short
+            ));
+        }
+    }
+
+    if !diff.reappeared.is_empty() {
+        message.push_str(&format!("\n{} ({}):\n", texts.sms_reappeared, diff.reappeared.len()));
+        for reappearance in &diff.reappeared {
+            let course = &reappearance.course;
+            message.push_str(&format!(
+                "• {} - {} ({} {}), {}\n",
+                course.code,
+                course.name,
+                course.points,
+                texts.points_short,
+                texts.short_reappearance_note(&reappearance.absence(), reappearance.previous_appearances)
+            ));
+        }
+    }
+
+    if !diff.changed.is_empty() {
+        message.push_str(&format!("\n{} ({}):\n", texts.sms_changed, diff.changed.len()));
+        for change in &diff.changed {
+            message.push_str(&format!("• {} - {}\n", change.code(), change.summary()));
+        }
+    }
+}
+
 #[async_trait]
 impl Notifier for SmsNotifier {
     fn name(&self) -> &'static str {
@@ -117,7 +179,9 @@ impl Notifier for SmsNotifier {
         notifier = "sms",
         recipients = ?self.to,
         added = diff.added.len(),
-        removed = diff.removed.len()
+        removed = diff.removed.len(),
+        changed = diff.changed.len(),
+        reappeared = diff.reappeared.len()
     ))]
     async fn notify(&self, diff: &ScrapeDiff) -> Result<()> {
         if diff.is_empty() {
@@ -136,6 +200,8 @@ impl Notifier for SmsNotifier {
             body_len = body.len(),
             added_courses = diff.added.len(),
             removed_courses = diff.removed.len(),
+            changed_courses = diff.changed.len(),
+            reappeared_courses = diff.reappeared.len(),
             "Preparing to send SMS"
         );
 
@@ -178,3 +244,60 @@ impl Notifier for SmsNotifier {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Course, CourseChange, Reappearance};
+    use std::collections::BTreeSet;
+
+    fn course(code: &str) -> Course {
+        Course::new(
+            code.to_string(),
+            format!("Emne {}", code),
+            10.0,
+            format!("https://www.uio.no/studier/emner/{}/", code),
+            "MatNat".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_watched_section_comes_first() {
+        let notifier = SmsNotifier::new(String::new(), String::new(), String::new(), Vec::new());
+        let modified = Course {
+            points: 5.0,
+            ..course("IN2010")
+        };
+        let mut diff = ScrapeDiff::new(vec![course("IN1000"), course("IN1050")], vec![course("MAT1100")])
+            .with_source("regular")
+            .with_watched(BTreeSet::from(["IN1050".to_string()]));
+        diff.reappeared.push(Reappearance {
+            course: course("SOS1000"),
+            absent_seconds: 3 * 3600,
+            previous_appearances: 1,
+        });
+        diff.changed.push(CourseChange::between(&course("IN2010"), &modified).unwrap());
+
+        let message = notifier.build_sms_content(&diff);
+        assert!(message.starts_with(
+            "UiO Emnevarsel (regular)\n\n!! OVERVÅKEDE EMNER (1) !!\n\nNye (1):\n• IN1050 - Emne IN1050 (10 stp)\n"
+        ));
+
+        let position = |text: &str| message.find(text).unwrap_or_else(|| panic!("missing {}", text));
+        let order = [
+            "IN1050",
+            "-- Andre endringer (4) --",
+            "Nye (1):\n• IN1000",
+            "Fjernet (1):\n• MAT1100",
+            "Tilbake (1):\n• SOS1000",
+            "Endret (1):\n• IN2010 - points: 10 → 5",
+        ];
+        for pair in order.windows(2) {
+            assert!(position(pair[0]) < position(pair[1]), "{} before {}", pair[0], pair[1]);
+        }
+
+        // Without watch hits the sections start right after the title
+        let message = notifier.build_sms_content(&ScrapeDiff::new(vec![course("IN1000")], vec![]));
+        assert_eq!(message, "UiO Emnevarsel\n\nNye (1):\n• IN1000 - Emne IN1000 (10 stp)\n");
+    }
+}
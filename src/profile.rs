@@ -0,0 +1,182 @@
+use anyhow::{anyhow, Context, Result};
+use scraper::Selector;
+use serde::Deserialize;
+
+/// Selectors, column names and skip rules used to parse a course listing page.
+/// Fields left out of a `[profiles.NAME]` table keep their built-in defaults.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ParserProfile {
+    /// Main content area; the first match is parsed
+    pub content_selector: String,
+    /// Faculty section headings
+    pub heading_selector: String,
+    /// Course tables
+    pub table_selector: String,
+    /// Rows within a course table
+    pub row_selector: String,
+    /// Cells within a row, in column order
+    pub cell_selector: String,
+    /// Course link within a cell
+    pub link_selector: String,
+    /// Headings whose id contains any of these are not faculties (they end the listing)
+    pub skip_heading_ids: Vec<String>,
+    pub columns: ColumnNames,
+}
+
+/// Header texts (case-insensitive) that identify each known column
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColumnNames {
+    pub code: Vec<String>,
+    pub name: Vec<String>,
+    pub points: Vec<String>,
+}
+
+impl Default for ParserProfile {
+    /// Layout of the UiO Vortex "ledige plasser" page
+    fn default() -> Self {
+        Self {
+            content_selector: "#vrtx-content, main, article, .vrtx-content, body".to_string(),
+            heading_selector: "h2[id]".to_string(),
+            table_selector: "table".to_string(),
+            row_selector: "tr".to_string(),
+            cell_selector: "th, td".to_string(),
+            link_selector: "a".to_string(),
+            skip_heading_ids: vec!["sporsmal".to_string(), "kontakt".to_string()],
+            columns: ColumnNames::default(),
+        }
+    }
+}
+
+impl Default for ColumnNames {
+    /// Norwegian and English header variants
+    fn default() -> Self {
+        let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect();
+        Self {
+            code: names(&["emne", "emnekode", "kode", "course", "course code", "code", "subject"]),
+            name: names(&["emnenavn", "navn", "tittel", "course name", "name", "title"]),
+            points: names(&[
+                "studiepoeng",
+                "sp",
+                "poeng",
+                "credits",
+                "ects",
+                "ects credits",
+                "points",
+                "study points",
+                "credit points",
+            ]),
+        }
+    }
+}
+
+impl ParserProfile {
+    /// Parse all selectors, failing on the first invalid one
+    pub fn compile(&self) -> Result<CompiledProfile> {
+        let heading = parse_selector("heading_selector", &self.heading_selector)?;
+        let table = parse_selector("table_selector", &self.table_selector)?;
+
+        Ok(CompiledProfile {
+            content: parse_selector("content_selector", &self.content_selector)?,
+            // Headings and tables are walked together in document order
+            section: parse_selector(
+                "heading_selector/table_selector",
+                &format!("{}, {}", self.heading_selector, self.table_selector),
+            )?,
+            heading,
+            table,
+            row: parse_selector("row_selector", &self.row_selector)?,
+            cell: parse_selector("cell_selector", &self.cell_selector)?,
+            link: parse_selector("link_selector", &self.link_selector)?,
+            skip_heading_ids: self.skip_heading_ids.clone(),
+            columns: ColumnNames {
+                code: normalize_names(&self.columns.code),
+                name: normalize_names(&self.columns.name),
+                points: normalize_names(&self.columns.points),
+            },
+        })
+    }
+}
+
+/// A profile with its selectors parsed, ready for the scraper
+#[derive(Debug)]
+pub struct CompiledProfile {
+    pub content: Selector,
+    pub section: Selector,
+    pub heading: Selector,
+    pub table: Selector,
+    pub row: Selector,
+    pub cell: Selector,
+    pub link: Selector,
+    pub skip_heading_ids: Vec<String>,
+    /// Lowercased, whitespace-collapsed header names
+    pub columns: ColumnNames,
+}
+
+impl CompiledProfile {
+    /// Whether a heading id marks a non-faculty section
+    pub fn skips_heading(&self, id: &str) -> bool {
+        self.skip_heading_ids.iter().any(|pattern| id.contains(pattern.as_str()))
+    }
+}
+
+impl Default for CompiledProfile {
+    fn default() -> Self {
+        ParserProfile::default()
+            .compile()
+            .expect("Built-in parser profile must compile")
+    }
+}
+
+fn parse_selector(field: &str, selector: &str) -> Result<Selector> {
+    Selector::parse(selector)
+        .map_err(|e| anyhow!("{}", e))
+        .with_context(|| format!("Invalid {} '{}'", field, selector))
+}
+
+fn normalize_names(names: &[String]) -> Vec<String> {
+    names
+        .iter()
+        .map(|name| {
+            let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+            name.trim_end_matches(':').to_lowercase()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_profile_keeps_defaults() {
+        let profile: ParserProfile = toml::from_str(
+            r#"
+            heading_selector = "h3.faculty"
+            skip_heading_ids = []
+
+            [columns]
+            points = ["Credits (ECTS)"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(profile.table_selector, "table");
+        assert_eq!(profile.columns.code, ColumnNames::default().code);
+
+        let compiled = profile.compile().unwrap();
+        assert_eq!(compiled.columns.points, vec!["credits (ects)"]);
+        assert!(!compiled.skips_heading("kontakt"));
+    }
+
+    #[test]
+    fn test_invalid_selector_is_rejected() {
+        let profile = ParserProfile {
+            table_selector: "table[".to_string(),
+            ..ParserProfile::default()
+        };
+        let err = profile.compile().unwrap_err();
+        assert!(format!("{:#}", err).contains("table_selector"));
+    }
+}
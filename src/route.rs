@@ -0,0 +1,250 @@
+use anyhow::{anyhow, bail, Context, Result};
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::filter::FilterExpr;
+use crate::models::{Course, ScrapeDiff};
+
+/// Kind of change a routing rule can select
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EventKind {
+    Added,
+    Removed,
+    Modified,
+    Reappeared,
+    /// Any change to a watchlist course
+    Watched,
+}
+
+impl EventKind {
+    pub const ALL: [EventKind; 5] = [
+        EventKind::Added,
+        EventKind::Removed,
+        EventKind::Modified,
+        EventKind::Reappeared,
+        EventKind::Watched,
+    ];
+
+    pub fn parse(text: &str) -> Result<Self> {
+        match text.trim().to_lowercase().as_str() {
+            "added" | "new" => Ok(EventKind::Added),
+            "removed" => Ok(EventKind::Removed),
+            "modified" | "changed" => Ok(EventKind::Modified),
+            "reappeared" | "back" => Ok(EventKind::Reappeared),
+            "watched" | "watchlist" => Ok(EventKind::Watched),
+            other => bail!(
+                "unknown event type '{}' (use added, removed, modified, reappeared, watched or *)",
+                other
+            ),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::Added => "added",
+            EventKind::Removed => "removed",
+            EventKind::Modified => "modified",
+            EventKind::Reappeared => "reappeared",
+            EventKind::Watched => "watched",
+        }
+    }
+}
+
+/// Which changes one notifier receives, e.g. "added,reappeared where points <= 10"
+#[derive(Debug, Clone)]
+pub struct Route {
+    kinds: BTreeSet<EventKind>,
+    /// Courses must also match this (watchlist hits selected by `watched` bypass it)
+    filter: Option<FilterExpr>,
+}
+
+impl Route {
+    /// Comma-separated event types (or "*"), optionally followed by "where <expression>"
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        let (kinds, filter) = match spec.find(" where ") {
+            Some(pos) => (&spec[..pos], Some(spec[pos + " where ".len()..].trim())),
+            None => (spec, None),
+        };
+
+        let mut parsed = BTreeSet::new();
+        for kind in kinds.split(',') {
+            if kind.trim() == "*" {
+                parsed.extend(EventKind::ALL);
+            } else {
+                parsed.insert(EventKind::parse(kind)?);
+            }
+        }
+
+        let filter = match filter {
+            Some(expr) => Some(FilterExpr::parse(expr).map_err(|e| anyhow!("invalid filter: {}", e))?),
+            None => None,
+        };
+
+        Ok(Self { kinds: parsed, filter })
+    }
+
+    /// Only watchlist hits (what --urgent-channels asks for)
+    pub fn watched_only() -> Self {
+        Self {
+            kinds: BTreeSet::from([EventKind::Watched]),
+            filter: None,
+        }
+    }
+
+    fn includes(&self, kind: EventKind) -> bool {
+        self.kinds.contains(&kind)
+    }
+
+    fn selects(&self, kind: EventKind, course: &Course) -> bool {
+        self.includes(kind) && self.filter.as_ref().is_none_or(|f| f.matches(course))
+    }
+
+    /// The part of `diff` this route lets through
+    pub fn apply(&self, diff: &ScrapeDiff) -> ScrapeDiff {
+        let watched = |code: &str| self.includes(EventKind::Watched) && diff.watched.contains(code);
+
+        let added: Vec<Course> = diff
+            .added
+            .iter()
+            .filter(|c| watched(&c.code) || self.selects(EventKind::Added, c))
+            .cloned()
+            .collect();
+        let removed: Vec<Course> = diff
+            .removed
+            .iter()
+            .filter(|c| watched(&c.code) || self.selects(EventKind::Removed, c))
+            .cloned()
+            .collect();
+        let changed = diff
+            .changed
+            .iter()
+            .filter(|c| {
+                watched(c.code())
+                    || self.selects(EventKind::Modified, &c.before)
+                    || self.selects(EventKind::Modified, &c.after)
+            })
+            .cloned()
+            .collect();
+        let reappeared = diff
+            .reappeared
+            .iter()
+            .filter(|r| watched(&r.course.code) || self.selects(EventKind::Reappeared, &r.course))
+            .cloned()
+            .collect();
+
+        let mut routed = ScrapeDiff::new(added, removed)
+            .with_changed(changed)
+            .with_reappeared(reappeared);
+        // Keep the highlight only for watchlist courses that are still in the diff
+        let watched_codes = diff
+            .watched
+            .iter()
+            .filter(|code| routed.contains_code(code))
+            .cloned()
+            .collect();
+        routed = routed.with_watched(watched_codes);
+        if let Some(ref source) = diff.source {
+            routed = routed.with_source(source.clone());
+        }
+        routed
+    }
+}
+
+impl fmt::Display for Route {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.kinds.len() == EventKind::ALL.len() {
+            f.write_str("*")?;
+        } else {
+            let kinds: Vec<&str> = self.kinds.iter().map(EventKind::as_str).collect();
+            f.write_str(&kinds.join(","))?;
+        }
+        if let Some(ref filter) = self.filter {
+            write!(f, " where {}", filter)?;
+        }
+        Ok(())
+    }
+}
+
+/// A routing rule from the configuration: "sms=added,reappeared where points <= 10"
+#[derive(Debug, Clone)]
+pub struct RouteRule {
+    pub notifier: String,
+    pub route: Route,
+}
+
+impl RouteRule {
+    pub fn parse(rule: &str) -> Result<Self> {
+        let (notifier, spec) = rule
+            .split_once('=')
+            .with_context(|| format!("invalid route '{}': expected NOTIFIER=EVENTS [where EXPRESSION]", rule.trim()))?;
+        let route = Route::parse(spec).with_context(|| format!("invalid route '{}'", rule.trim()))?;
+        Ok(Self {
+            notifier: notifier.trim().to_lowercase(),
+            route,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CourseChange, Reappearance};
+
+    fn course(code: &str, points: f32) -> Course {
+        Course::new(code.to_string(), code.to_string(), points, String::new(), String::new())
+    }
+
+    fn diff() -> ScrapeDiff {
+        let mut renamed = course("IN2010", 10.0);
+        renamed.name = "Algoritmer".to_string();
+        ScrapeDiff::new(
+            vec![course("IN1000", 10.0), course("MAT1100", 5.0)],
+            vec![course("SOS1000", 5.0)],
+        )
+        .with_changed(vec![CourseChange::between(&course("IN2010", 10.0), &renamed).unwrap()])
+        .with_reappeared(vec![Reappearance {
+            course: course("EXPHIL03", 10.0),
+            absent_seconds: 3600,
+            previous_appearances: 1,
+        }])
+        .with_watched(BTreeSet::from(["SOS1000".to_string()]))
+    }
+
+    fn codes(diff: &ScrapeDiff) -> Vec<String> {
+        let mut codes: Vec<String> = diff.added.iter().map(|c| c.code.clone()).collect();
+        codes.extend(diff.removed.iter().map(|c| c.code.clone()));
+        codes.extend(diff.changed.iter().map(|c| c.code().to_string()));
+        codes.extend(diff.reappeared.iter().map(|r| r.course.code.clone()));
+        codes
+    }
+
+    #[test]
+    fn test_routes_select_event_types() {
+        let seats = RouteRule::parse("SMS = added, reappeared").unwrap();
+        assert_eq!(seats.notifier, "sms");
+        let routed = seats.route.apply(&diff());
+        assert_eq!(codes(&routed), vec!["IN1000", "MAT1100", "EXPHIL03"]);
+        assert!(!routed.has_watch_hits());
+
+        let all = Route::parse("*").unwrap();
+        assert_eq!(codes(&all.apply(&diff())).len(), 5);
+        assert_eq!(all.to_string(), "*");
+
+        let urgent = Route::watched_only().apply(&diff());
+        assert_eq!(codes(&urgent), vec!["SOS1000"]);
+        assert!(urgent.has_watch_hits());
+    }
+
+    #[test]
+    fn test_route_filter_and_watchlist() {
+        let route = Route::parse("added,watched where points < 10").unwrap();
+        assert_eq!(route.to_string(), "added,watched where points < 10");
+        // The 5 point removal is a watchlist hit, so it passes regardless of the event filter
+        assert_eq!(codes(&route.apply(&diff())), vec!["MAT1100", "SOS1000"]);
+
+        assert!(RouteRule::parse("sms").is_err());
+        assert!(RouteRule::parse("sms=opened").is_err());
+        assert!(RouteRule::parse("sms=added where points <").is_err());
+    }
+}
@@ -0,0 +1,655 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Datelike, Days, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::config::validate_interval;
+
+/// Time zone cron expressions and active-hours windows are evaluated in
+pub const TIME_ZONE: Tz = chrono_tz::Europe::Oslo;
+
+const WEEKDAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+/// When the start loop scrapes a source
+#[derive(Debug, Clone)]
+pub enum Schedule {
+    /// Same interval around the clock
+    Interval(Duration),
+    /// At the times matched by a cron expression
+    Cron {
+        expression: String,
+        schedule: Box<cron::Schedule>,
+    },
+    /// Interval by weekday and time of day; `fallback` applies outside all windows
+    Windows {
+        windows: Vec<ActiveWindow>,
+        fallback: Duration,
+    },
+}
+
+/// One active-hours window, e.g. "mon-fri 08:00-16:00=30s"
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveWindow {
+    /// Weekdays the window starts on, Monday first
+    days: [bool; 7],
+    start: NaiveTime,
+    /// End of the window; at or before `start` the window runs past midnight
+    end: NaiveTime,
+    interval: Duration,
+    spec: String,
+}
+
+impl Schedule {
+    /// Parse a cron expression: 5 fields (minute precision) or 6-7 fields (with seconds/year)
+    pub fn parse_cron(expression: &str) -> Result<Self> {
+        let expression = expression.trim();
+        let fields = expression.split_whitespace().count();
+        let full = match fields {
+            5 => format!("0 {}", expression),
+            6 | 7 => expression.to_string(),
+            _ => bail!(
+                "Invalid cron expression '{}': expected 5 fields (minute hour day month weekday)",
+                expression
+            ),
+        };
+        let schedule = cron::Schedule::from_str(&full)
+            .with_context(|| format!("Invalid cron expression '{}'", expression))?;
+        if schedule.upcoming(TIME_ZONE).next().is_none() {
+            bail!("Cron expression '{}' never matches", expression);
+        }
+
+        Ok(Schedule::Cron {
+            expression: expression.to_string(),
+            schedule: Box::new(schedule),
+        })
+    }
+
+    /// Parse ';'-separated active-hours windows, e.g. "mon-fri 08:00-16:00=30s; 07:00-23:00=5m".
+    /// The first matching window wins; `fallback` is used outside all of them.
+    pub fn parse_windows(spec: &str, fallback: Duration) -> Result<Self> {
+        let windows = spec
+            .split([';', '\n'])
+            .map(str::trim)
+            .filter(|window| !window.is_empty())
+            .map(|window| {
+                ActiveWindow::parse(window)
+                    .with_context(|| format!("Invalid active-hours window '{}'", window))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if windows.is_empty() {
+            bail!("Active hours must contain at least one window");
+        }
+
+        Ok(Schedule::Windows { windows, fallback })
+    }
+
+    /// Time of the first run after `after`, or None if the schedule has no further runs
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Schedule::Interval(interval) => Some(after + *interval),
+            Schedule::Cron { schedule, .. } => schedule
+                .after(&after.with_timezone(&TIME_ZONE))
+                .next()
+                .map(|next| next.with_timezone(&Utc)),
+            Schedule::Windows { windows, fallback } => {
+                let next = after + self.interval_at(after).unwrap_or(*fallback);
+                // Don't sleep through the start of a faster window or past the end of the current one
+                let local = after.with_timezone(&TIME_ZONE);
+                let window_end = windows
+                    .iter()
+                    .find(|window| window.contains(&local))
+                    .and_then(|window| window.next_end_after(after));
+                let boundary = windows
+                    .iter()
+                    .filter_map(|window| window.next_start_after(after))
+                    .chain(window_end)
+                    .filter(|boundary| *boundary < next)
+                    .min();
+                Some(boundary.unwrap_or(next))
+            }
+        }
+    }
+
+    /// Interval in effect at the given time (None for cron schedules)
+    pub fn interval_at(&self, at: DateTime<Utc>) -> Option<Duration> {
+        match self {
+            Schedule::Interval(interval) => Some(*interval),
+            Schedule::Cron { .. } => None,
+            Schedule::Windows { windows, fallback } => {
+                let local = at.with_timezone(&TIME_ZONE);
+                Some(
+                    windows
+                        .iter()
+                        .find(|window| window.contains(&local))
+                        .map_or(*fallback, |window| window.interval),
+                )
+            }
+        }
+    }
+
+    /// The next `count` runs after `after`, including calendar hot periods
+    pub fn upcoming(&self, hot: &HotPolicy, after: DateTime<Utc>, count: usize) -> Vec<DateTime<Utc>> {
+        let mut runs = Vec::with_capacity(count);
+        let mut last = after;
+        while runs.len() < count {
+            let Some(next) = hot.next_run(self, last, false) else {
+                break;
+            };
+            runs.push(next);
+            last = next;
+        }
+        runs
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Schedule::Interval(interval) => format!("every {}s", interval.as_secs()),
+            Schedule::Cron { expression, .. } => format!("cron '{}' ({})", expression, TIME_ZONE),
+            Schedule::Windows { windows, fallback } => {
+                let windows: Vec<&str> = windows.iter().map(|w| w.spec.as_str()).collect();
+                format!(
+                    "{} ({}), otherwise every {}s",
+                    windows.join("; "),
+                    TIME_ZONE,
+                    fallback.as_secs()
+                )
+            }
+        }
+    }
+}
+
+impl ActiveWindow {
+    fn parse(spec: &str) -> Result<Self> {
+        let (when, interval) = spec
+            .split_once('=')
+            .context("expected [DAYS] HH:MM-HH:MM=INTERVAL")?;
+
+        let when = when.trim();
+        let (days, hours) = match when.split_once(char::is_whitespace) {
+            Some((days, hours)) => (parse_days(days)?, hours.trim()),
+            None => ([true; 7], when),
+        };
+
+        let (start, end) = hours.split_once('-').context("expected hours as HH:MM-HH:MM")?;
+
+        Ok(Self {
+            days,
+            start: parse_time(start)?,
+            end: parse_time(end)?,
+            interval: parse_interval(interval)?,
+            spec: spec.split_whitespace().collect::<Vec<_>>().join(" "),
+        })
+    }
+
+    fn contains(&self, local: &DateTime<Tz>) -> bool {
+        window_contains(&self.days, self.start, self.end, local)
+    }
+
+    /// First start of this window after `after`, looking one week ahead
+    fn next_start_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let today = after.with_timezone(&TIME_ZONE).date_naive();
+        (0..=7)
+            .filter_map(|offset| today.checked_add_days(Days::new(offset)))
+            .filter(|date| self.days[date.weekday().num_days_from_monday() as usize])
+            .filter_map(|date| TIME_ZONE.from_local_datetime(&date.and_time(self.start)).earliest())
+            .map(|start| start.with_timezone(&Utc))
+            .find(|start| *start > after)
+    }
+
+    /// First end of this window after `after`; a window lasts at most a day
+    fn next_end_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let today = after.with_timezone(&TIME_ZONE).date_naive();
+        (0..=1)
+            .filter_map(|offset| today.checked_add_days(Days::new(offset)))
+            .filter_map(|date| TIME_ZONE.from_local_datetime(&date.and_time(self.end)).earliest())
+            .map(|end| end.with_timezone(&Utc))
+            .find(|end| *end > after)
+    }
+}
+
+/// Changes within this many hours count towards `HotPolicy::change_threshold`
+pub const CHANGE_WINDOW_HOURS: i64 = 24;
+
+/// Tighter polling while seats move a lot: during calendar periods such as the days
+/// around registration deadlines, or while recent changes reach a threshold
+#[derive(Debug, Clone, Default)]
+pub struct HotPolicy {
+    periods: Vec<HotPeriod>,
+    interval: Duration,
+    change_threshold: Option<usize>,
+}
+
+/// Inclusive date range in Europe/Oslo, every year ("08-01..08-20") or once
+/// ("2027-01-05..2027-01-20")
+#[derive(Debug, Clone, PartialEq)]
+pub struct HotPeriod {
+    start: PeriodDate,
+    end: PeriodDate,
+    spec: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PeriodDate {
+    year: Option<i32>,
+    month: u32,
+    day: u32,
+}
+
+impl HotPolicy {
+    /// `periods` is a ';'-separated list of date ranges; `change_threshold` also makes a
+    /// source hot after that many changes in the last `CHANGE_WINDOW_HOURS` hours
+    pub fn new(periods: Option<&str>, interval: Duration, change_threshold: Option<usize>) -> Result<Self> {
+        let periods = periods
+            .unwrap_or("")
+            .split([';', '\n'])
+            .map(str::trim)
+            .filter(|period| !period.is_empty())
+            .map(|period| {
+                HotPeriod::parse(period).with_context(|| format!("Invalid hot period '{}'", period))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if change_threshold == Some(0) {
+            bail!("Invalid hot change threshold: must be at least 1");
+        }
+
+        Ok(Self {
+            periods,
+            interval,
+            change_threshold,
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.periods.is_empty() || self.change_threshold.is_some()
+    }
+
+    pub fn change_threshold(&self) -> Option<usize> {
+        self.change_threshold
+    }
+
+    /// Calendar period covering the given time, if any
+    pub fn period_at(&self, at: DateTime<Utc>) -> Option<&HotPeriod> {
+        let date = at.with_timezone(&TIME_ZONE).date_naive();
+        self.periods.iter().find(|period| period.contains(date))
+    }
+
+    /// Next run after `after`: the schedule's, or sooner while hot. `learned_hot` is set
+    /// when recent changes reached the threshold. Outside periods the loop still wakes up
+    /// when the next period begins.
+    pub fn next_run(
+        &self,
+        schedule: &Schedule,
+        after: DateTime<Utc>,
+        learned_hot: bool,
+    ) -> Option<DateTime<Utc>> {
+        let scheduled = schedule.next_after(after);
+        let tightened = if learned_hot || self.period_at(after).is_some() {
+            Some(after + self.interval)
+        } else {
+            self.periods
+                .iter()
+                .filter_map(|period| period.next_start_after(after))
+                .min()
+        };
+
+        match (scheduled, tightened) {
+            (Some(scheduled), Some(tightened)) => Some(scheduled.min(tightened)),
+            (scheduled, tightened) => scheduled.or(tightened),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        if !self.is_enabled() {
+            return "disabled".to_string();
+        }
+
+        let mut triggers: Vec<String> = self.periods.iter().map(|p| p.spec.clone()).collect();
+        if let Some(threshold) = self.change_threshold {
+            triggers.push(format!(">= {} changes in {}h", threshold, CHANGE_WINDOW_HOURS));
+        }
+        format!("every {}s during {}", self.interval.as_secs(), triggers.join("; "))
+    }
+}
+
+impl HotPeriod {
+    fn parse(spec: &str) -> Result<Self> {
+        let (start, end) = spec
+            .split_once("..")
+            .context("expected START..END with dates as MM-DD or YYYY-MM-DD")?;
+        let start = PeriodDate::parse(start)?;
+        let end = PeriodDate::parse(end)?;
+
+        match (start.year, end.year) {
+            (Some(_), Some(_)) if start.date(None) > end.date(None) => {
+                bail!("period ends before it starts")
+            }
+            (Some(_), None) | (None, Some(_)) => {
+                bail!("use MM-DD for both dates (every year) or YYYY-MM-DD for both")
+            }
+            _ => {}
+        }
+
+        Ok(Self {
+            start,
+            end,
+            spec: spec.split_whitespace().collect(),
+        })
+    }
+
+    fn contains(&self, date: NaiveDate) -> bool {
+        if self.start.year.is_some() {
+            return self.start.date(None) <= Some(date) && Some(date) <= self.end.date(None);
+        }
+
+        let key = (date.month(), date.day());
+        let start = (self.start.month, self.start.day);
+        let end = (self.end.month, self.end.day);
+        if start <= end {
+            start <= key && key <= end
+        } else {
+            // Wraps around the new year ("12-15..01-10")
+            key >= start || key <= end
+        }
+    }
+
+    /// Local midnight of the next start of this period after `after`
+    fn next_start_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let year = after.with_timezone(&TIME_ZONE).year();
+        [year, year + 1]
+            .into_iter()
+            .filter_map(|year| self.start.date(Some(year)))
+            .filter_map(|date| TIME_ZONE.from_local_datetime(&date.and_time(NaiveTime::MIN)).earliest())
+            .map(|start| start.with_timezone(&Utc))
+            .find(|start| *start > after)
+    }
+}
+
+impl std::fmt::Display for HotPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.spec)
+    }
+}
+
+impl PeriodDate {
+    fn parse(text: &str) -> Result<Self> {
+        let text = text.trim();
+        let parts: Vec<&str> = text.split('-').collect();
+        let number = |part: &str| part.parse::<u32>().ok();
+        let date = match parts.as_slice() {
+            [month, day] => number(month).zip(number(day)).map(|(month, day)| Self {
+                year: None,
+                month,
+                day,
+            }),
+            [year, month, day] => match (year.parse::<i32>().ok(), number(month), number(day)) {
+                (Some(year), Some(month), Some(day)) => Some(Self {
+                    year: Some(year),
+                    month,
+                    day,
+                }),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        // Validate against a leap year so "02-29" is accepted for recurring periods
+        match date {
+            Some(date) if date.date(date.year.or(Some(2024))).is_some() => Ok(date),
+            _ => bail!("invalid date '{}' (use MM-DD or YYYY-MM-DD)", text),
+        }
+    }
+
+    /// The date in its own year, or in `year` for recurring dates
+    fn date(&self, year: Option<i32>) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(self.year.or(year)?, self.month, self.day)
+    }
+}
+
+/// Hours a subscriber does not want to be notified in, e.g. "22:00-07:00" or "sat,sun 00:00-10:00"
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuietHours {
+    days: [bool; 7],
+    start: NaiveTime,
+    end: NaiveTime,
+    spec: String,
+}
+
+impl QuietHours {
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        let (days, hours) = match spec.split_once(char::is_whitespace) {
+            Some((days, hours)) => (parse_days(days)?, hours.trim()),
+            None => ([true; 7], spec),
+        };
+        let (start, end) = hours
+            .split_once('-')
+            .context("expected quiet hours as [DAYS] HH:MM-HH:MM")?;
+
+        Ok(Self {
+            days,
+            start: parse_time(start)?,
+            end: parse_time(end)?,
+            spec: spec.split_whitespace().collect::<Vec<_>>().join(" "),
+        })
+    }
+
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        window_contains(&self.days, self.start, self.end, &at.with_timezone(&TIME_ZONE))
+    }
+}
+
+impl std::fmt::Display for QuietHours {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.spec)
+    }
+}
+
+/// Whether local time is within a daily window starting on one of `days`
+fn window_contains(days: &[bool; 7], start: NaiveTime, end: NaiveTime, local: &DateTime<Tz>) -> bool {
+    let time = local.time();
+    let today = local.weekday().num_days_from_monday() as usize;
+    let yesterday = (today + 6) % 7;
+
+    if start < end {
+        days[today] && time >= start && time < end
+    } else {
+        // Runs past midnight (or all day when start == end)
+        (days[today] && time >= start) || (days[yesterday] && time < end)
+    }
+}
+
+/// "mon-fri", "sat,sun", "mon-wed,fri" or "*"
+fn parse_days(spec: &str) -> Result<[bool; 7]> {
+    let mut days = [false; 7];
+    for part in spec.split(',') {
+        let part = part.trim().to_lowercase();
+        if part == "*" {
+            return Ok([true; 7]);
+        }
+        let (first, last) = match part.split_once('-') {
+            Some((first, last)) => (weekday_index(first)?, weekday_index(last)?),
+            None => (weekday_index(&part)?, weekday_index(&part)?),
+        };
+        // Ranges may wrap around the week ("fri-mon")
+        let mut day = first;
+        loop {
+            days[day] = true;
+            if day == last {
+                break;
+            }
+            day = (day + 1) % 7;
+        }
+    }
+    Ok(days)
+}
+
+fn weekday_index(name: &str) -> Result<usize> {
+    let name = name.trim();
+    WEEKDAYS
+        .iter()
+        .position(|day| name.get(..3).is_some_and(|prefix| day.starts_with(prefix)))
+        .with_context(|| format!("unknown weekday '{}' (use mon, tue, ..., sun)", name))
+}
+
+fn parse_time(text: &str) -> Result<NaiveTime> {
+    let text = text.trim();
+    if text == "24:00" {
+        return Ok(NaiveTime::MIN);
+    }
+    NaiveTime::parse_from_str(text, "%H:%M")
+        .with_context(|| format!("invalid time '{}' (use HH:MM)", text))
+}
+
+/// "30" or "30s", "5m", "1h"
+pub fn parse_interval(text: &str) -> Result<Duration> {
+    let text = text.trim();
+    let (number, unit) = match text.find(|c: char| !c.is_ascii_digit()) {
+        Some(pos) => text.split_at(pos),
+        None => (text, "s"),
+    };
+    let value: u64 = number
+        .parse()
+        .with_context(|| format!("invalid interval '{}' (use e.g. 30s, 5m, 1h)", text))?;
+    let secs = match unit.trim() {
+        "s" => value,
+        "m" => value * 60,
+        "h" => value * 3600,
+        _ => bail!("invalid interval '{}' (use e.g. 30s, 5m, 1h)", text),
+    };
+    validate_interval(secs)?;
+    Ok(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A UTC instant from Oslo local time
+    fn oslo(text: &str) -> DateTime<Utc> {
+        let naive = chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M").unwrap();
+        TIME_ZONE
+            .from_local_datetime(&naive)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_windows_pick_interval_by_time_and_day() {
+        let schedule = Schedule::parse_windows(
+            "mon-fri 08:00-16:00=30s; 22:00-07:00=1h",
+            Duration::from_secs(300),
+        )
+        .unwrap();
+
+        // 2026-10-14 is a Wednesday, 2026-10-17 a Saturday
+        let at = |text| schedule.interval_at(oslo(text)).unwrap().as_secs();
+        assert_eq!(at("2026-10-14 09:00"), 30);
+        assert_eq!(at("2026-10-17 09:00"), 300);
+        assert_eq!(at("2026-10-14 23:30"), 3600);
+        assert_eq!(at("2026-10-15 03:00"), 3600);
+        assert_eq!(at("2026-10-14 18:00"), 300);
+
+        // An hourly night interval still wakes up when the night window ends
+        let next = schedule.next_after(oslo("2026-10-15 06:59")).unwrap();
+        assert_eq!(next, oslo("2026-10-15 07:00"));
+        let next = schedule.next_after(oslo("2026-10-15 07:00")).unwrap();
+        assert_eq!(next, oslo("2026-10-15 07:05"));
+        let next = schedule.next_after(oslo("2026-10-15 07:58")).unwrap();
+        assert_eq!(next, oslo("2026-10-15 08:00"));
+    }
+
+    #[test]
+    fn test_cron_in_oslo_time() {
+        let schedule = Schedule::parse_cron("*/15 8-9 * * Mon-Fri").unwrap();
+        let runs = schedule.upcoming(&HotPolicy::default(), oslo("2026-10-16 09:50"), 3);
+        // Friday 09:50 -> 10:00 is outside the hours, so the next runs are on Monday
+        assert_eq!(
+            runs,
+            vec![
+                oslo("2026-10-19 08:00"),
+                oslo("2026-10-19 08:15"),
+                oslo("2026-10-19 08:30")
+            ]
+        );
+
+        assert!(Schedule::parse_cron("every minute").is_err());
+        assert!(Schedule::parse_cron("61 * * * *").is_err());
+    }
+
+    #[test]
+    fn test_invalid_windows() {
+        let fallback = Duration::from_secs(60);
+        assert!(Schedule::parse_windows("mon-fri 08:00-16:00", fallback).is_err());
+        assert!(Schedule::parse_windows("funday 08:00-16:00=30s", fallback).is_err());
+        assert!(Schedule::parse_windows("08:00-25:00=30s", fallback).is_err());
+        // Faster than the 10 second minimum
+        assert!(Schedule::parse_windows("08:00-16:00=5s", fallback).is_err());
+        assert!(Schedule::parse_windows(" ; ", fallback).is_err());
+    }
+
+    #[test]
+    fn test_hot_periods_tighten_the_schedule() {
+        let hot = HotPolicy::new(
+            Some("08-10..08-20; 12-20..01-05; 2027-01-10..2027-01-12"),
+            Duration::from_secs(20),
+            None,
+        )
+        .unwrap();
+        let relaxed = Schedule::Interval(Duration::from_secs(600));
+
+        assert!(hot.period_at(oslo("2026-08-15 12:00")).is_some());
+        assert!(hot.period_at(oslo("2026-08-21 00:30")).is_none());
+        assert!(hot.period_at(oslo("2027-01-02 12:00")).is_some());
+        assert_eq!(
+            hot.period_at(oslo("2027-01-11 12:00")).unwrap().to_string(),
+            "2027-01-10..2027-01-12"
+        );
+        assert!(hot.period_at(oslo("2028-01-11 12:00")).is_none());
+
+        // Hot: the hot interval wins; relaxed: the schedule's
+        let at = oslo("2026-08-15 12:00");
+        assert_eq!(hot.next_run(&relaxed, at, false), Some(oslo("2026-08-15 12:00") + Duration::from_secs(20)));
+        let at = oslo("2026-10-01 12:00");
+        assert_eq!(hot.next_run(&relaxed, at, false), Some(oslo("2026-10-01 12:10")));
+        // Learned from recent changes
+        assert_eq!(hot.next_run(&relaxed, at, true), Some(at + Duration::from_secs(20)));
+
+        // A sparse cron schedule still wakes up when a period begins
+        let nightly = Schedule::parse_cron("0 3 * * *").unwrap();
+        assert_eq!(
+            hot.next_run(&nightly, oslo("2026-08-09 12:00"), false),
+            Some(oslo("2026-08-10 00:00"))
+        );
+    }
+
+    #[test]
+    fn test_quiet_hours() {
+        let quiet = QuietHours::parse("22:00-07:00").unwrap();
+        assert!(quiet.contains(oslo("2026-10-14 23:30")));
+        assert!(quiet.contains(oslo("2026-10-15 06:59")));
+        assert!(!quiet.contains(oslo("2026-10-15 07:00")));
+
+        // 2026-10-17 is a Saturday
+        let weekend = QuietHours::parse("sat,sun  00:00-10:00").unwrap();
+        assert!(weekend.contains(oslo("2026-10-17 09:00")));
+        assert!(!weekend.contains(oslo("2026-10-16 09:00")));
+        assert_eq!(weekend.to_string(), "sat,sun 00:00-10:00");
+
+        assert!(QuietHours::parse("22-07").is_err());
+        assert!(QuietHours::parse("nights").is_err());
+    }
+
+    #[test]
+    fn test_invalid_hot_periods() {
+        let interval = Duration::from_secs(20);
+        assert!(HotPolicy::new(Some("08-10"), interval, None).is_err());
+        assert!(HotPolicy::new(Some("02-30..03-01"), interval, None).is_err());
+        assert!(HotPolicy::new(Some("2027-01-12..2027-01-10"), interval, None).is_err());
+        assert!(HotPolicy::new(Some("2027-01-10..01-12"), interval, None).is_err());
+        assert!(HotPolicy::new(None, interval, Some(0)).is_err());
+        assert!(!HotPolicy::new(Some(" "), interval, None).unwrap().is_enabled());
+    }
+}
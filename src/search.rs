@@ -0,0 +1,109 @@
+/// Word endings stripped from query words before prefix matching, longest first,
+/// so "økonomisk" finds "Økonomi" and "matematiske" finds "Matematikk"
+const SUFFIXES: &[&str] = &[
+    "iske", "ene", "ane", "isk", "lig", "er", "en", "et", "ar", "e", "a",
+];
+
+/// Query words shorter than this (after stemming) are matched as typed
+const MIN_STEM_LEN: usize = 4;
+
+/// Keyword query that ignores case and Norwegian spelling variants: "okonomi", "Økonomi"
+/// and "oekonomi" all find "Økonomi og ledelse"
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keywords {
+    query: String,
+    /// Folded and stemmed query words; all must match
+    words: Vec<String>,
+}
+
+impl Keywords {
+    /// None when the query has no words
+    pub fn parse(query: &str) -> Option<Self> {
+        let words: Vec<String> = fold_words(query).into_iter().map(|w| stem(&w)).collect();
+        if words.is_empty() {
+            return None;
+        }
+        Some(Self {
+            query: query.split_whitespace().collect::<Vec<_>>().join(" "),
+            words,
+        })
+    }
+
+    /// Whether every query word starts a word of `text`
+    pub fn matches(&self, text: &str) -> bool {
+        let text_words = fold_words(text);
+        self.words
+            .iter()
+            .all(|word| text_words.iter().any(|t| t.starts_with(word.as_str())))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.query
+    }
+}
+
+/// Lowercase and fold Norwegian letters and their ASCII spellings to one form:
+/// æ/ae -> ae, ø/oe -> o, å/aa -> a (plus common accents)
+pub fn fold(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars().flat_map(char::to_lowercase) {
+        match c {
+            'æ' | 'ä' => folded.push_str("ae"),
+            'ø' | 'ö' | 'ó' | 'ò' | 'ô' => folded.push('o'),
+            'å' | 'á' | 'à' | 'â' => folded.push('a'),
+            'é' | 'è' | 'ê' | 'ë' => folded.push('e'),
+            'ü' | 'ú' => folded.push('u'),
+            c => folded.push(c),
+        }
+    }
+    folded.replace("aa", "a").replace("oe", "o")
+}
+
+fn fold_words(text: &str) -> Vec<String> {
+    fold(text)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn stem(word: &str) -> String {
+    SUFFIXES
+        .iter()
+        .find_map(|suffix| {
+            word.strip_suffix(suffix)
+                .filter(|stem| stem.chars().count() >= MIN_STEM_LEN)
+        })
+        .unwrap_or(word)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_folds_norwegian_spellings() {
+        let keywords = Keywords::parse("okonomi").unwrap();
+        assert!(keywords.matches("Økonomi og ledelse"));
+        assert!(keywords.matches("Samfunnsøkonomi? Nei: Innføring i økonomi"));
+        assert!(Keywords::parse("Økonomi").unwrap().matches("OEKONOMI"));
+        assert!(Keywords::parse("pa norsk").unwrap().matches("Skriving på norsk"));
+        assert!(Keywords::parse("Haakon").unwrap().matches("Håkon"));
+        assert!(Keywords::parse("laering").unwrap().matches("Læring og utdanning"));
+        assert!(!keywords.matches("Samfunnsøkonomi"));
+    }
+
+    #[test]
+    fn test_prefix_and_stemming() {
+        assert!(Keywords::parse("økonomisk").unwrap().matches("Økonomi"));
+        assert!(Keywords::parse("matematiske").unwrap().matches("Matematikk 1"));
+        assert!(Keywords::parse("prog").unwrap().matches("Introduksjon til programmering"));
+        // Every word must match
+        assert!(Keywords::parse("intro prog").unwrap().matches("Introduksjon til programmering"));
+        assert!(!Keywords::parse("intro kjemi").unwrap().matches("Introduksjon til programmering"));
+        // Short words are not stemmed: "er" must not match everything
+        assert!(!Keywords::parse("ene").unwrap().matches("Matematikk"));
+        assert!(Keywords::parse("  ").is_none());
+    }
+}
@@ -0,0 +1,169 @@
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+
+use crate::config::{normalize_norwegian_phone, CourseFilter, PointsFilter};
+use crate::db::SubscriberRecord;
+use crate::filter::FilterExpr;
+use crate::notifier::{Channels, Language, NotifierChain};
+use crate::schedule::QuietHours;
+
+/// Someone notified with their own filter, channels, language and quiet hours,
+/// on top of the recipients in the configuration
+#[derive(Debug, Clone)]
+pub struct Subscriber {
+    pub id: i64,
+    pub name: String,
+    pub filter: CourseFilter,
+    pub email: Option<String>,
+    /// Normalized Norwegian phone number
+    pub phone: Option<String>,
+    pub language: Language,
+    /// Only watchlist hits are sent during these hours
+    pub quiet_hours: Option<QuietHours>,
+}
+
+impl Subscriber {
+    /// Parse and validate a stored or submitted subscriber; blank fields count as unset
+    pub fn parse(record: &SubscriberRecord) -> Result<Self> {
+        let name = record.name.trim();
+        if name.is_empty() {
+            bail!("Subscriber name is required");
+        }
+
+        let expression = match non_blank(&record.filter) {
+            Some(expr) => Some(FilterExpr::parse(expr).map_err(|e| anyhow!("Invalid filter: {}", e))?),
+            None => None,
+        };
+        let email = non_blank(&record.email).map(String::from);
+        if let Some(ref email) = email {
+            if !email.contains('@') {
+                bail!("Invalid email address '{}'", email);
+            }
+        }
+        let phone = match non_blank(&record.phone) {
+            Some(phone) => Some(
+                normalize_norwegian_phone(phone)
+                    .with_context(|| format!("Invalid Norwegian phone number '{}'", phone))?,
+            ),
+            None => None,
+        };
+        if email.is_none() && phone.is_none() {
+            bail!("Subscriber '{}' needs an email address or a phone number", name);
+        }
+        let quiet_hours = match non_blank(&record.quiet_hours) {
+            Some(spec) => Some(QuietHours::parse(spec).context("Invalid quiet hours")?),
+            None => None,
+        };
+
+        Ok(Self {
+            id: record.id,
+            name: name.to_string(),
+            filter: CourseFilter {
+                expression,
+                ..CourseFilter::from(PointsFilter::None)
+            },
+            email,
+            phone,
+            language: Language::parse(&record.language)?,
+            quiet_hours,
+        })
+    }
+
+    /// The normalized form to store
+    pub fn to_record(&self) -> SubscriberRecord {
+        SubscriberRecord {
+            id: self.id,
+            name: self.name.clone(),
+            filter: self.filter.expression.as_ref().map(|e| e.to_string()),
+            email: self.email.clone(),
+            phone: self.phone.clone(),
+            language: self.language.code().to_string(),
+            quiet_hours: self.quiet_hours.as_ref().map(|q| q.to_string()),
+        }
+    }
+
+    pub fn is_quiet(&self, at: DateTime<Utc>) -> bool {
+        self.quiet_hours.as_ref().is_some_and(|q| q.contains(at))
+    }
+
+    /// Notifiers for this subscriber's channels (skipping providers that are not configured)
+    pub fn notifiers(&self, channels: &Channels) -> NotifierChain {
+        let mut notifiers = NotifierChain::new();
+        if let (Some(email), Some(sender)) = (&self.email, &channels.email) {
+            notifiers.add(sender.for_recipients(vec![email.clone()], self.language));
+        }
+        if let (Some(phone), Some(sender)) = (&self.phone, &channels.sms) {
+            notifiers.add(sender.for_recipients(vec![phone.clone()], self.language));
+        }
+        notifiers
+    }
+}
+
+fn non_blank(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Course;
+    use crate::schedule::TIME_ZONE;
+    use chrono::TimeZone;
+
+    fn record() -> SubscriberRecord {
+        SubscriberRecord {
+            id: 7,
+            name: " Ola ".to_string(),
+            filter: Some("points <= 5 and faculty ~ matematisk".to_string()),
+            email: Some(String::new()),
+            phone: Some("412 34 567".to_string()),
+            language: "en".to_string(),
+            quiet_hours: Some("22:00-07:00".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_parse_normalizes_fields() {
+        let subscriber = Subscriber::parse(&record()).unwrap();
+        assert_eq!(subscriber.name, "Ola");
+        assert_eq!(subscriber.email, None);
+        assert_eq!(subscriber.phone.as_deref(), Some("+4741234567"));
+        assert_eq!(subscriber.language, Language::English);
+
+        let course = |points| {
+            Course::new(
+                "MAT1100".to_string(),
+                String::new(),
+                points,
+                String::new(),
+                "Det matematisk-naturvitenskapelige fakultet".to_string(),
+            )
+        };
+        assert!(subscriber.filter.matches(&course(5.0)));
+        assert!(!subscriber.filter.matches(&course(10.0)));
+
+        let night = TIME_ZONE.with_ymd_and_hms(2026, 10, 14, 23, 0, 0).unwrap().with_timezone(&Utc);
+        assert!(subscriber.is_quiet(night));
+
+        let stored = subscriber.to_record();
+        assert_eq!(stored.name, "Ola");
+        assert_eq!(stored.email, None);
+        assert_eq!(Subscriber::parse(&stored).unwrap().to_record(), stored);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_subscribers() {
+        let invalid = |change: fn(&mut SubscriberRecord)| {
+            let mut record = record();
+            change(&mut record);
+            Subscriber::parse(&record).is_err()
+        };
+        assert!(invalid(|r| r.name = "  ".to_string()));
+        assert!(invalid(|r| r.phone = None));
+        assert!(invalid(|r| r.phone = Some("123".to_string())));
+        assert!(invalid(|r| r.email = Some("not-an-address".to_string())));
+        assert!(invalid(|r| r.filter = Some("points <=".to_string())));
+        assert!(invalid(|r| r.language = "de".to_string()));
+        assert!(invalid(|r| r.quiet_hours = Some("late".to_string())));
+    }
+}
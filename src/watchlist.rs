@@ -0,0 +1,154 @@
+use anyhow::{bail, Result};
+
+use crate::models::normalize_course_code;
+
+/// Course codes that are always notified, regardless of the course filter
+#[derive(Debug, Clone, Default)]
+pub struct Watchlist {
+    patterns: Vec<WatchPattern>,
+}
+
+/// A course code, or a code pattern with `*` (any run of characters) and `?` (one character)
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchPattern {
+    /// Normalized form, e.g. "IN1*"
+    pattern: String,
+}
+
+impl WatchPattern {
+    pub fn parse(pattern: &str) -> Result<Self> {
+        let mut pattern = normalize_course_code(pattern);
+        // "**" means the same as "*"
+        while pattern.contains("**") {
+            pattern = pattern.replace("**", "*");
+        }
+        if pattern.is_empty() {
+            bail!("Empty watchlist pattern");
+        }
+        if let Some(c) = pattern
+            .chars()
+            .find(|c| !(c.is_alphanumeric() || matches!(c, '-' | '_' | '*' | '?')))
+        {
+            bail!(
+                "Invalid watchlist pattern '{}': unexpected '{}' (use course codes with * and ?)",
+                pattern,
+                c
+            );
+        }
+        Ok(Self { pattern })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn matches(&self, code: &str) -> bool {
+        glob_matches(
+            &self.pattern.chars().collect::<Vec<_>>(),
+            &normalize_course_code(code).chars().collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl Watchlist {
+    /// Parse patterns, skipping duplicates
+    pub fn new<S: AsRef<str>>(patterns: impl IntoIterator<Item = S>) -> Result<Self> {
+        let mut watchlist = Self::default();
+        for pattern in patterns {
+            watchlist.push(WatchPattern::parse(pattern.as_ref())?);
+        }
+        Ok(watchlist)
+    }
+
+    /// This watchlist plus patterns stored in the database (invalid stored ones are skipped)
+    pub fn with_stored(&self, stored: &[String]) -> Self {
+        let mut watchlist = self.clone();
+        for pattern in stored.iter().filter_map(|p| WatchPattern::parse(p).ok()) {
+            watchlist.push(pattern);
+        }
+        watchlist
+    }
+
+    fn push(&mut self, pattern: WatchPattern) {
+        if !self.patterns.contains(&pattern) {
+            self.patterns.push(pattern);
+        }
+    }
+
+    pub fn matches(&self, code: &str) -> bool {
+        self.patterns.iter().any(|p| p.matches(code))
+    }
+
+    pub fn patterns(&self) -> impl Iterator<Item = &str> {
+        self.patterns.iter().map(WatchPattern::as_str)
+    }
+}
+
+/// Match a glob with `*` and `?` against a whole code, in O(pattern × code) time.
+/// On a mismatch after a `*`, that `*` takes one more character and matching resumes.
+fn glob_matches(pattern: &[char], code: &[char]) -> bool {
+    let (mut p, mut c) = (0, 0);
+    // Position of the last `*` and the code position it currently matches up to
+    let mut star: Option<(usize, usize)> = None;
+
+    while c < code.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, c));
+                p += 1;
+            }
+            Some(&expected) if expected == '?' || expected == code[c] => {
+                p += 1;
+                c += 1;
+            }
+            _ => match star {
+                Some((star_p, star_c)) => {
+                    star = Some((star_p, star_c + 1));
+                    p = star_p + 1;
+                    c = star_c + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&ch| ch == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_and_patterns() {
+        let watchlist = Watchlist::new(["in1000", "MAT-INF1*", "HFLESER10?1"]).unwrap();
+        assert!(watchlist.matches("IN1000"));
+        assert!(!watchlist.matches("IN10000"));
+        assert!(watchlist.matches("mat-inf1100"));
+        assert!(!watchlist.matches("MAT-INF2100"));
+        assert!(watchlist.matches("HFLESER1031"));
+        assert!(!watchlist.matches("HFLESER101"));
+    }
+
+    #[test]
+    fn test_stored_patterns_are_merged() {
+        let watchlist = Watchlist::new(["IN1000"]).unwrap();
+        let merged = watchlist.with_stored(&["in1000".to_string(), "SOS*".to_string(), "bad code!".to_string()]);
+        assert_eq!(merged.patterns().collect::<Vec<_>>(), vec!["IN1000", "SOS*"]);
+        assert!(WatchPattern::parse("IN 1000; DROP").is_err());
+    }
+
+    #[test]
+    fn test_repeated_stars() {
+        let pattern = WatchPattern::parse("**********************A").unwrap();
+        assert_eq!(pattern.as_str(), "*A");
+        assert!(!pattern.matches(&"B".repeat(200)));
+        assert!(pattern.matches("BBA"));
+
+        let watchlist = Watchlist::new(["*1*0?0*"]).unwrap();
+        assert!(watchlist.matches("IN1000"));
+        assert!(watchlist.matches("MAT-INF11000"));
+        assert!(!watchlist.matches("IN2000"));
+        assert!(!watchlist.matches("IN10"));
+    }
+}
@@ -1,15 +1,25 @@
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 use axum::{
-    extract::{Path, State},
-    response::Html,
-    routing::get,
+    extract::{Form, Path, Query, Request, State},
+    http::{header, HeaderMap, Method, StatusCode},
+    middleware::{self, Next},
+    response::{Html, IntoResponse, Redirect, Response},
+    routing::{get, post},
     Router,
 };
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
 use tower_http::validate_request::ValidateRequestHeaderLayer;
-use tracing::info;
+use tracing::{info, warn};
 
-use crate::db::{CourseDisplay, Database, RunLogEntry};
+use crate::db::{CourseDisplay, Database, RunLogEntry, RunStatus, SubscriberRecord};
+use crate::models::format_absence;
+use crate::notifier::html_escape;
+use crate::schedule::TIME_ZONE;
+use crate::search::Keywords;
+use crate::subscriber::Subscriber;
+use crate::watchlist::WatchPattern;
 
 /// Display-safe application configuration (no secrets)
 #[derive(Clone)]
@@ -20,9 +30,42 @@ pub struct AppConfig {
     pub sms_enabled: bool,
     pub sms_from: Option<String>,
     pub sms_to: Vec<String>,
+    pub slack_enabled: bool,
     pub points_filter: String,
     pub database_type: String,
-    pub scrape_url: String,
+    pub sources: Vec<SourceInfo>,
+    /// When polling is tightened, e.g. around registration deadlines
+    pub hot_polling: String,
+    /// Watchlist patterns from the command line/environment (read-only in the UI)
+    pub watchlist: Vec<String>,
+    /// Exclusion rules, e.g. "level:9000"
+    pub exclusions: Vec<String>,
+    /// Notifiers that only receive watchlist hits
+    pub urgent_channels: Vec<String>,
+    /// Routing rules as "notifier: events [where expression]"
+    pub routes: Vec<String>,
+}
+
+/// A monitored page as shown on the configuration page
+#[derive(Clone)]
+pub struct SourceInfo {
+    pub name: String,
+    pub url: String,
+    pub profile: String,
+    /// Description of when the source is scraped
+    pub schedule: String,
+    /// Updated by the source's scrape loop after every cycle
+    pub status: Arc<RwLock<PollStatus>>,
+}
+
+/// Polling plan of a scrape loop after its last cycle
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PollStatus {
+    /// Time from the last cycle's start to the next run
+    pub interval_secs: Option<u64>,
+    /// Why polling is tightened right now (None when relaxed)
+    pub hot_reason: Option<String>,
+    pub next_run: Option<DateTime<Utc>>,
 }
 
 /// Application state shared between handlers
@@ -35,15 +78,76 @@ pub struct AppState {
 pub fn create_router(db: Database, config: AppConfig) -> Router {
     let state = Arc::new(AppState { db, config });
 
+    #[allow(deprecated)]
+    let auth = ValidateRequestHeaderLayer::basic("admin", "forktree");
+
+    // Pages with forms that change stored state
+    let forms = Router::new()
+        .route("/watchlist", get(watchlist_page).post(add_watch))
+        .route("/watchlist/remove", post(remove_watch))
+        .route("/subscribers", get(subscribers_page).post(add_subscriber))
+        .route("/subscribers/remove", post(remove_subscriber))
+        .route_layer(middleware::from_fn(same_origin_only));
+
     Router::new()
         .route("/", get(dashboard))
         .route("/runs", get(run_logs))
         .route("/runs/{id}", get(run_detail))
+        .route("/snapshots/{hash}", get(snapshot))
         .route("/config", get(config_page))
-        .layer(ValidateRequestHeaderLayer::basic("admin", "forktree"))
+        .merge(forms)
+        .layer(auth)
         .with_state(state)
 }
 
+/// Reject form posts made from other sites (CSRF).
+///
+/// The browser keeps sending the basic auth credentials to this host, so a page
+/// elsewhere could otherwise submit a form here on the user's behalf. Browsers
+/// name the page a POST came from in Origin (or at least Referer), and it has to
+/// be this host.
+async fn same_origin_only(request: Request, next: Next) -> Response {
+    if matches!(*request.method(), Method::GET | Method::HEAD) || is_same_origin(request.headers()) {
+        return next.run(request).await;
+    }
+
+    warn!(
+        method = %request.method(),
+        path = %request.uri().path(),
+        origin = ?request.headers().get(header::ORIGIN),
+        referer = ?request.headers().get(header::REFERER),
+        "Rejected cross-origin form post"
+    );
+    (
+        StatusCode::FORBIDDEN,
+        Html(render_error("Forms can only be submitted from this dashboard.")),
+    )
+        .into_response()
+}
+
+/// Whether Origin (or Referer when Origin is missing) points at the Host the request was sent to
+fn is_same_origin(headers: &HeaderMap) -> bool {
+    let header_str = |name| headers.get(name).and_then(|value| value.to_str().ok());
+    let (Some(host), Some(origin)) = (
+        header_str(header::HOST),
+        header_str(header::ORIGIN).or_else(|| header_str(header::REFERER)),
+    ) else {
+        return false;
+    };
+
+    // "null" (sandboxed frames, some redirects) does not parse and is rejected
+    let Ok(origin) = reqwest::Url::parse(origin) else {
+        return false;
+    };
+    // Parse Host with the origin's scheme so default ports compare equal
+    let Ok(target) = reqwest::Url::parse(&format!("{}://{}", origin.scheme(), host)) else {
+        return false;
+    };
+    origin.host_str().is_some()
+        && origin.host_str() == target.host_str()
+        && origin.port_or_known_default() == target.port_or_known_default()
+}
+
 /// Start the web server on the given port
 pub async fn start_server(router: Router, port: u16) -> anyhow::Result<()> {
     let addr = format!("0.0.0.0:{}", port);
@@ -59,10 +163,20 @@ pub async fn start_server(router: Router, port: u16) -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Dashboard page showing current courses
-async fn dashboard(State(state): State<Arc<AppState>>) -> Html<String> {
+/// Query string of the dashboard search box
+#[derive(Deserialize)]
+struct SearchParams {
+    #[serde(default)]
+    q: String,
+}
+
+/// Dashboard page showing current courses, optionally narrowed by `?q=` keywords
+async fn dashboard(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SearchParams>,
+) -> Html<String> {
     let courses = state.db.get_courses_for_display().await.unwrap_or_default();
-    Html(render_dashboard(&courses))
+    Html(render_dashboard(&courses, Keywords::parse(&params.q).as_ref()))
 }
 
 /// Run logs list page
@@ -83,15 +197,168 @@ async fn run_detail(
     }
 }
 
+/// Raw page snapshot, served as plain text so archived markup is never executed
+async fn snapshot(State(state): State<Arc<AppState>>, Path(hash): Path<String>) -> Response {
+    match state.db.get_snapshot(&hash).await {
+        Ok(Some(html)) => {
+            ([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], html).into_response()
+        }
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Html(render_error("Snapshot not found (it may have been pruned)")),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Html(render_error(&format!("Error: {}", e))),
+        )
+            .into_response(),
+    }
+}
+
 /// Configuration page
 async fn config_page(State(state): State<Arc<AppState>>) -> Html<String> {
     Html(render_config(&state.config))
 }
 
+/// Form body of the watchlist add/remove buttons
+#[derive(Deserialize)]
+struct WatchForm {
+    pattern: String,
+}
+
+/// Watchlist page
+async fn watchlist_page(State(state): State<Arc<AppState>>) -> Response {
+    render_watchlist_response(&state, None).await
+}
+
+/// Add a pattern to the stored watchlist
+async fn add_watch(State(state): State<Arc<AppState>>, Form(form): Form<WatchForm>) -> Response {
+    let pattern = match WatchPattern::parse(&form.pattern) {
+        Ok(pattern) => pattern,
+        Err(e) => return render_watchlist_response(&state, Some(e.to_string())).await,
+    };
+    match state.db.add_watch(pattern.as_str()).await {
+        Ok(()) => Redirect::to("/watchlist").into_response(),
+        Err(e) => render_watchlist_response(&state, Some(format!("Error: {}", e))).await,
+    }
+}
+
+/// Remove a pattern from the stored watchlist
+async fn remove_watch(State(state): State<Arc<AppState>>, Form(form): Form<WatchForm>) -> Response {
+    match state.db.remove_watch(&form.pattern).await {
+        Ok(()) => Redirect::to("/watchlist").into_response(),
+        Err(e) => render_watchlist_response(&state, Some(format!("Error: {}", e))).await,
+    }
+}
+
+async fn render_watchlist_response(state: &AppState, error: Option<String>) -> Response {
+    match state.db.get_watchlist().await {
+        Ok(stored) => {
+            let status = if error.is_some() { StatusCode::BAD_REQUEST } else { StatusCode::OK };
+            (status, Html(render_watchlist(&state.config, &stored, error.as_deref()))).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Html(render_error(&format!("Error: {}", e))),
+        )
+            .into_response(),
+    }
+}
+
+/// Form body of the add subscriber form; blank fields are unset
+#[derive(Deserialize)]
+struct SubscriberForm {
+    name: String,
+    #[serde(default)]
+    filter: String,
+    #[serde(default)]
+    email: String,
+    #[serde(default)]
+    phone: String,
+    language: String,
+    #[serde(default)]
+    quiet_hours: String,
+}
+
+/// Form body of the remove subscriber button
+#[derive(Deserialize)]
+struct RemoveSubscriberForm {
+    id: i64,
+}
+
+/// Subscribers page
+async fn subscribers_page(State(state): State<Arc<AppState>>) -> Response {
+    render_subscribers_response(&state, None).await
+}
+
+/// Validate and store a new subscriber
+async fn add_subscriber(State(state): State<Arc<AppState>>, Form(form): Form<SubscriberForm>) -> Response {
+    let record = SubscriberRecord {
+        id: 0,
+        name: form.name,
+        filter: Some(form.filter),
+        email: Some(form.email),
+        phone: Some(form.phone),
+        language: form.language,
+        quiet_hours: Some(form.quiet_hours),
+    };
+    let subscriber = match Subscriber::parse(&record) {
+        Ok(subscriber) => subscriber,
+        Err(e) => return render_subscribers_response(&state, Some(format!("{:#}", e))).await,
+    };
+    match state.db.add_subscriber(&subscriber.to_record()).await {
+        Ok(_) => Redirect::to("/subscribers").into_response(),
+        Err(e) => render_subscribers_response(&state, Some(format!("Error: {:#}", e))).await,
+    }
+}
+
+/// Delete a subscriber
+async fn remove_subscriber(
+    State(state): State<Arc<AppState>>,
+    Form(form): Form<RemoveSubscriberForm>,
+) -> Response {
+    match state.db.remove_subscriber(form.id).await {
+        Ok(()) => Redirect::to("/subscribers").into_response(),
+        Err(e) => render_subscribers_response(&state, Some(format!("Error: {}", e))).await,
+    }
+}
+
+async fn render_subscribers_response(state: &AppState, error: Option<String>) -> Response {
+    match state.db.get_subscribers().await {
+        Ok(subscribers) => {
+            let status = if error.is_some() { StatusCode::BAD_REQUEST } else { StatusCode::OK };
+            (status, Html(render_subscribers(&subscribers, error.as_deref()))).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Html(render_error(&format!("Error: {}", e))),
+        )
+            .into_response(),
+    }
+}
+
 /// Render the dashboard HTML
-fn render_dashboard(courses: &[CourseDisplay]) -> String {
+fn render_dashboard(courses: &[CourseDisplay], search: Option<&Keywords>) -> String {
+    let shown: Vec<&CourseDisplay> = courses
+        .iter()
+        .filter(|c| search.is_none_or(|k| k.matches(&c.name) || k.matches(&c.code)))
+        .collect();
+    let count = match search {
+        Some(_) => format!("{} of {} total", shown.len(), courses.len()),
+        None => format!("{} total", courses.len()),
+    };
+
     let mut rows = String::new();
-    for course in courses {
+    for course in shown {
+        let returned = match course.last_absence_seconds {
+            Some(absent) if course.appearances > 1 => format!(
+                r#"<br><span class="badge badge-back" title="Available {} times">Back after {}</span>"#,
+                course.appearances,
+                format_absence(absent)
+            ),
+            _ => String::new(),
+        };
         rows.push_str(&format!(
             r#"<tr>
                 <td><a href="{}" target="_blank">{}</a></td>
@@ -99,13 +366,22 @@ fn render_dashboard(courses: &[CourseDisplay]) -> String {
                 <td>{}</td>
                 <td>{}</td>
                 <td>{}</td>
+                <td class="details">{}</td>
+                <td>{}{}</td>
             </tr>"#,
             html_escape(&course.url),
             html_escape(&course.code),
             html_escape(&course.name),
             course.points,
             html_escape(&course.faculty),
+            html_escape(&course.source),
+            course
+                .details
+                .as_ref()
+                .map(|d| html_escape(&d.summary()))
+                .unwrap_or_default(),
             format_timestamp(&course.first_seen_at),
+            returned,
         ));
     }
 
@@ -123,6 +399,11 @@ fn render_dashboard(courses: &[CourseDisplay]) -> String {
         nav a {{ margin-right: 1rem; }}
         table {{ width: 100%; }}
         .count {{ color: #606c76; font-weight: normal; }}
+        .details {{ color: #606c76; font-size: 0.9em; }}
+        .badge {{ display: inline-block; padding: 0.2rem 0.5rem; border-radius: 3px; font-size: 0.8rem; }}
+        .badge-back {{ background: #e2e3f5; color: #383d8a; }}
+        .search {{ display: flex; gap: 1rem; max-width: 600px; }}
+        .search input {{ flex: 1; }}
     </style>
 </head>
 <body>
@@ -133,7 +414,11 @@ fn render_dashboard(courses: &[CourseDisplay]) -> String {
             <a href="/runs" class="button button-clear">Run Logs</a>
         </nav>
 
-        <h2>Current Courses <span class="count">({} total)</span></h2>
+        <h2>Current Courses <span class="count">({})</span></h2>
+        <form class="search" method="get" action="/">
+            <input type="search" name="q" value="{}" placeholder="Search names, e.g. okonomi or intro prog">
+            <button type="submit" class="button-outline">Search</button>
+        </form>
         <table>
             <thead>
                 <tr>
@@ -141,6 +426,8 @@ fn render_dashboard(courses: &[CourseDisplay]) -> String {
                     <th>Name</th>
                     <th>Points</th>
                     <th>Faculty</th>
+                    <th>Source</th>
+                    <th>Details</th>
                     <th>First Seen</th>
                 </tr>
             </thead>
@@ -151,7 +438,8 @@ fn render_dashboard(courses: &[CourseDisplay]) -> String {
     </main>
 </body>
 </html>"#,
-        courses.len(),
+        count,
+        html_escape(search.map(Keywords::as_str).unwrap_or_default()),
         rows
     )
 }
@@ -176,23 +464,36 @@ fn render_run_logs(runs: &[RunLogEntry]) -> String {
             format!("-{} ({})", run.raw_removed_count, run.filtered_removed_count)
         };
 
+        let changed_display = if run.raw_changed_count == run.filtered_changed_count {
+            format!("~{}", run.raw_changed_count)
+        } else {
+            format!("~{} ({})", run.raw_changed_count, run.filtered_changed_count)
+        };
+
         rows.push_str(&format!(
             r#"<tr>
                 <td><a href="/runs/{}">{}</a></td>
                 <td>{}</td>
                 <td>{}</td>
+                <td>{}</td>
+                <td>{}</td>
                 <td style="color: green;">{}</td>
                 <td style="color: red;">{}</td>
-                <td>{}</td>
+                <td style="color: #cc8800;">{}</td>
+                <td>{}{}</td>
                 <td>{}ms</td>
             </tr>"#,
             run.id,
             run.id,
             format_timestamp(&run.timestamp),
+            html_escape(&run.source),
+            status_badge(run.status),
             run.total_courses_fetched,
             added_display,
             removed_display,
-            format!("{}{}", notified, first_run),
+            changed_display,
+            notified,
+            first_run,
             run.duration_ms,
         ));
     }
@@ -212,6 +513,11 @@ fn render_run_logs(runs: &[RunLogEntry]) -> String {
         table {{ width: 100%; }}
         .count {{ color: #606c76; font-weight: normal; }}
         .hint {{ color: #606c76; font-size: 0.85em; margin-bottom: 1rem; }}
+        .badge {{ display: inline-block; padding: 0.2rem 0.5rem; border-radius: 3px; font-size: 0.9rem; }}
+        .badge-success {{ background: #d4edda; color: #155724; }}
+        .badge-disabled {{ background: #f5f5f5; color: #606c76; }}
+        .badge-danger {{ background: #f8d7da; color: #721c24; }}
+        .badge-warning {{ background: #fff3cd; color: #856404; }}
     </style>
 </head>
 <body>
@@ -223,15 +529,18 @@ fn render_run_logs(runs: &[RunLogEntry]) -> String {
         </nav>
 
         <h2>Run Logs <span class="count">({} shown)</span></h2>
-        <p class="hint">Added/Removed show raw changes. Numbers in parentheses show filtered changes (what triggers notifications).</p>
+        <p class="hint">Added/Removed/Modified show raw changes. Numbers in parentheses show filtered changes (what triggers notifications).</p>
         <table>
             <thead>
                 <tr>
                     <th>ID</th>
                     <th>Timestamp</th>
+                    <th>Source</th>
+                    <th>Status</th>
                     <th>Fetched</th>
                     <th>Added</th>
                     <th>Removed</th>
+                    <th>Modified</th>
                     <th>Notified</th>
                     <th>Duration</th>
                 </tr>
@@ -286,6 +595,89 @@ fn render_run_detail(run: &RunLogEntry) -> String {
             .join("\n")
     };
 
+    let changed_list = if run.changed_courses.is_empty() {
+        "<li>None</li>".to_string()
+    } else {
+        run.changed_courses
+            .iter()
+            .map(|change| {
+                let fields = change
+                    .fields
+                    .iter()
+                    .map(|f| {
+                        format!(
+                            "{}: <s>{}</s> &rarr; {}",
+                            html_escape(&f.field),
+                            html_escape(&f.before),
+                            html_escape(&f.after)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    r#"<li><a href="{}" target="_blank">{}</a> - {}</li>"#,
+                    html_escape(&change.after.url),
+                    html_escape(change.code()),
+                    fields
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let reappeared_list = if run.reappeared_courses.is_empty() {
+        "<li>None</li>".to_string()
+    } else {
+        run.reappeared_courses
+            .iter()
+            .map(|r| {
+                format!(
+                    r#"<li><a href="{}" target="_blank">{}</a> - {} ({} pts), back after {}, available {} time(s) before</li>"#,
+                    html_escape(&r.course.url),
+                    html_escape(&r.course.code),
+                    html_escape(&r.course.name),
+                    r.course.points,
+                    r.absence(),
+                    r.previous_appearances
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let excluded = if run.excluded_courses.is_empty() {
+        "None".to_string()
+    } else {
+        format!(
+            "{} ({})",
+            run.excluded_courses.len(),
+            html_escape(&run.excluded_courses.join(", "))
+        )
+    };
+
+    let error = match &run.error {
+        Some(e) => format!(
+            "<code>{}</code> ({}) {}",
+            html_escape(&e.kind),
+            if e.transient { "transient" } else { "permanent" },
+            html_escape(&e.message)
+        ),
+        None => "None".to_string(),
+    };
+
+    let snapshot = match (&run.content_hash, run.snapshot_available) {
+        (Some(hash), true) => format!(
+            r#"<a href="/snapshots/{}" target="_blank">View raw page</a> <code>{}</code>"#,
+            html_escape(hash),
+            html_escape(&hash.chars().take(12).collect::<String>())
+        ),
+        (Some(hash), false) => format!(
+            "Not archived <code>{}</code>",
+            html_escape(&hash.chars().take(12).collect::<String>())
+        ),
+        (None, _) => "Not recorded".to_string(),
+    };
+
     format!(
         r#"<!DOCTYPE html>
 <html lang="en">
@@ -303,10 +695,15 @@ fn render_run_detail(run: &RunLogEntry) -> String {
         .badge {{ display: inline-block; padding: 0.2rem 0.5rem; border-radius: 3px; font-size: 0.9rem; }}
         .badge-success {{ background: #d4edda; color: #155724; }}
         .badge-info {{ background: #cce5ff; color: #004085; }}
+        .badge-disabled {{ background: #f5f5f5; color: #606c76; }}
+        .badge-danger {{ background: #f8d7da; color: #721c24; }}
+        .badge-warning {{ background: #fff3cd; color: #856404; }}
         .lists {{ display: grid; grid-template-columns: 1fr 1fr; gap: 2rem; margin-top: 2rem; }}
         .lists h4 {{ margin-bottom: 0.5rem; }}
         .added {{ color: green; }}
         .removed {{ color: red; }}
+        .changed {{ color: #cc8800; }}
+        .reappeared {{ color: #383d8a; }}
         .hint {{ color: #606c76; font-size: 0.85em; margin-top: 1rem; }}
     </style>
 </head>
@@ -324,6 +721,15 @@ fn render_run_detail(run: &RunLogEntry) -> String {
             <dt>Timestamp</dt>
             <dd>{}</dd>
 
+            <dt>Source</dt>
+            <dd>{}</dd>
+
+            <dt>Status</dt>
+            <dd>{}</dd>
+
+            <dt>Error</dt>
+            <dd>{}</dd>
+
             <dt>Duration</dt>
             <dd>{}ms</dd>
 
@@ -334,16 +740,22 @@ fn render_run_detail(run: &RunLogEntry) -> String {
             <dd>{}</dd>
 
             <dt>Raw Changes</dt>
-            <dd>+{} / -{}</dd>
+            <dd>+{} / -{} / ~{}</dd>
 
             <dt>Filtered Changes</dt>
-            <dd>+{} / -{}</dd>
+            <dd>+{} / -{} / ~{}</dd>
+
+            <dt>Excluded</dt>
+            <dd>{}</dd>
 
             <dt>Notification Sent</dt>
             <dd>{}</dd>
 
             <dt>First Run</dt>
             <dd>{}</dd>
+
+            <dt>Page Snapshot</dt>
+            <dd>{}</dd>
         </dl>
 
         <div class="lists">
@@ -356,6 +768,16 @@ fn render_run_detail(run: &RunLogEntry) -> String {
                 <ul>{}</ul>
             </div>
         </div>
+        <div class="lists">
+            <div>
+                <h4 class="changed">Modified Courses (~{})</h4>
+                <ul>{}</ul>
+            </div>
+            <div>
+                <h4 class="reappeared">Available Again ({})</h4>
+                <ul>{}</ul>
+            </div>
+        </div>
         <p class="hint">Note: Course lists show raw changes. Older runs may have empty lists due to a previous bug.</p>
 
         <p><a href="/runs">&larr; Back to Run Logs</a></p>
@@ -365,13 +787,19 @@ fn render_run_detail(run: &RunLogEntry) -> String {
         run.id,
         run.id,
         format_timestamp(&run.timestamp),
+        html_escape(&run.source),
+        status_badge(run.status),
+        error,
         run.duration_ms,
         html_escape(&run.filter_used),
         run.total_courses_fetched,
         run.raw_added_count,
         run.raw_removed_count,
+        run.raw_changed_count,
         run.filtered_added_count,
         run.filtered_removed_count,
+        run.filtered_changed_count,
+        excluded,
         if run.notification_sent {
             "<span class=\"badge badge-success\">Yes</span>"
         } else {
@@ -382,10 +810,15 @@ fn render_run_detail(run: &RunLogEntry) -> String {
         } else {
             "No"
         },
+        snapshot,
         run.raw_added_count,
         added_list,
         run.raw_removed_count,
         removed_list,
+        run.raw_changed_count,
+        changed_list,
+        run.reappeared_courses.len(),
+        reappeared_list,
     )
 }
 
@@ -403,6 +836,77 @@ fn render_config(config: &AppConfig) -> String {
         "<span class=\"badge badge-disabled\">Disabled</span>"
     };
 
+    // The webhook URL is a secret, so only its status is shown
+    let slack_status = if config.slack_enabled {
+        "<span class=\"badge badge-success\">Enabled</span>"
+    } else {
+        "<span class=\"badge badge-disabled\">Disabled</span>"
+    };
+
+    let sources: String = config
+        .sources
+        .iter()
+        .map(|source| {
+            let status = source.status.read().unwrap().clone();
+            let current = match (status.interval_secs, status.next_run) {
+                (Some(interval), Some(next_run)) => format!(
+                    "{}s{}, next run {}",
+                    interval,
+                    status
+                        .hot_reason
+                        .map(|reason| format!(" <span class=\"badge badge-hot\">{}</span>", html_escape(&reason)))
+                        .unwrap_or_default(),
+                    next_run
+                        .with_timezone(&TIME_ZONE)
+                        .format("%Y-%m-%d %H:%M:%S %Z")
+                ),
+                _ => "Waiting for the first run".to_string(),
+            };
+            format!(
+                r#"<dt>Source: {}</dt>
+                <dd><a href="{}" target="_blank">{}</a> (profile {}, {})</dd>
+
+                <dt>Current interval</dt>
+                <dd>{}</dd>
+"#,
+                html_escape(&source.name),
+                html_escape(&source.url),
+                html_escape(&source.url),
+                html_escape(&source.profile),
+                html_escape(&source.schedule),
+                current
+            )
+        })
+        .collect();
+
+    let watchlist = if config.watchlist.is_empty() {
+        "No configured entries".to_string()
+    } else {
+        html_escape(&config.watchlist.join(", "))
+    };
+
+    let exclusions = if config.exclusions.is_empty() {
+        "None".to_string()
+    } else {
+        config
+            .exclusions
+            .iter()
+            .map(|rule| format!("<code>{}</code>", html_escape(rule)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let routes = if config.routes.is_empty() {
+        "Every notifier receives all changes".to_string()
+    } else {
+        config
+            .routes
+            .iter()
+            .map(|route| format!("<code>{}</code>", html_escape(route)))
+            .collect::<Vec<_>>()
+            .join("<br>")
+    };
+
     let email_from = config.email_from.as_deref().unwrap_or("Not configured");
     let email_to = if config.email_to.is_empty() {
         "Not configured".to_string()
@@ -435,6 +939,7 @@ fn render_config(config: &AppConfig) -> String {
         .badge {{ display: inline-block; padding: 0.2rem 0.5rem; border-radius: 3px; font-size: 0.9rem; }}
         .badge-success {{ background: #d4edda; color: #155724; }}
         .badge-disabled {{ background: #f5f5f5; color: #606c76; }}
+        .badge-hot {{ background: #fff3cd; color: #856404; }}
         .section {{ margin-bottom: 2rem; }}
         .section h3 {{ border-bottom: 1px solid #ddd; padding-bottom: 0.5rem; }}
     </style>
@@ -446,6 +951,8 @@ fn render_config(config: &AppConfig) -> String {
             <a href="/" class="button button-clear">Courses</a>
             <a href="/runs" class="button button-clear">Run Logs</a>
             <a href="/config" class="button button-outline">Configuration</a>
+            <a href="/watchlist" class="button button-clear">Watchlist</a>
+            <a href="/subscribers" class="button button-clear">Subscribers</a>
         </nav>
 
         <h2>System Configuration</h2>
@@ -453,17 +960,33 @@ fn render_config(config: &AppConfig) -> String {
         <div class="section">
             <h3>Scraping</h3>
             <dl class="config-grid">
-                <dt>Source URL</dt>
-                <dd><a href="{}" target="_blank">{}</a></dd>
+                {}
+
+                <dt>Hot Polling</dt>
+                <dd>{}</dd>
 
                 <dt>Points Filter</dt>
                 <dd>{}</dd>
 
+                <dt>Exclusions</dt>
+                <dd>{}</dd>
+
+                <dt>Watchlist</dt>
+                <dd>{} (<a href="/watchlist">edit</a>)</dd>
+
                 <dt>Database</dt>
                 <dd>{}</dd>
             </dl>
         </div>
 
+        <div class="section">
+            <h3>Routing</h3>
+            <dl class="config-grid">
+                <dt>Rules</dt>
+                <dd>{}</dd>
+            </dl>
+        </div>
+
         <div class="section">
             <h3>Email Notifications</h3>
             <dl class="config-grid">
@@ -491,19 +1014,243 @@ fn render_config(config: &AppConfig) -> String {
                 <dd>{}</dd>
             </dl>
         </div>
+
+        <div class="section">
+            <h3>Slack Notifications</h3>
+            <dl class="config-grid">
+                <dt>Status</dt>
+                <dd>{}</dd>
+            </dl>
+        </div>
     </main>
 </body>
 </html>"#,
-        html_escape(&config.scrape_url),
-        html_escape(&config.scrape_url),
+        sources,
+        html_escape(&config.hot_polling),
         html_escape(&config.points_filter),
+        exclusions,
+        watchlist,
         html_escape(&config.database_type),
+        routes,
         email_status,
         html_escape(email_from),
         html_escape(&email_to),
         sms_status,
         html_escape(sms_from),
         html_escape(&sms_to),
+        slack_status,
+    )
+}
+
+/// Render the watchlist page: configured entries are read-only, stored ones can be removed
+fn render_watchlist(config: &AppConfig, stored: &[String], error: Option<&str>) -> String {
+    let mut rows = String::new();
+    for pattern in &config.watchlist {
+        rows.push_str(&format!(
+            r#"<tr><td><code>{}</code></td><td>Configuration</td><td></td></tr>"#,
+            html_escape(pattern)
+        ));
+    }
+    for pattern in stored {
+        rows.push_str(&format!(
+            r#"<tr>
+                <td><code>{}</code></td>
+                <td>Web UI</td>
+                <td>
+                    <form method="post" action="/watchlist/remove">
+                        <input type="hidden" name="pattern" value="{}">
+                        <button type="submit" class="button button-outline">Remove</button>
+                    </form>
+                </td>
+            </tr>"#,
+            html_escape(pattern),
+            html_escape(pattern)
+        ));
+    }
+    if rows.is_empty() {
+        rows.push_str(r#"<tr><td colspan="3">No courses watched yet</td></tr>"#);
+    }
+
+    let error = error
+        .map(|e| format!(r#"<p class="error">{}</p>"#, html_escape(e)))
+        .unwrap_or_default();
+    let urgent = if config.urgent_channels.is_empty() {
+        "Watchlist hits are sent to all notifiers, at the top of each notification.".to_string()
+    } else {
+        format!(
+            "Watchlist hits are sent to all notifiers; {} only receive(s) watchlist hits.",
+            html_escape(&config.urgent_channels.join(", "))
+        )
+    };
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Watchlist - UiOBot</title>
+    <link rel="stylesheet" href="https://cdnjs.cloudflare.com/ajax/libs/milligram/1.4.1/milligram.min.css">
+    <style>
+        body {{ padding: 2rem 0; }}
+        nav {{ margin-bottom: 2rem; }}
+        nav a {{ margin-right: 1rem; }}
+        table {{ width: 100%; }}
+        td form {{ margin: 0; }}
+        .error {{ color: #dc3545; }}
+        .hint {{ color: #606c76; }}
+    </style>
+</head>
+<body>
+    <main class="container">
+        <h1>UiOBot Dashboard</h1>
+        <nav>
+            <a href="/" class="button button-clear">Courses</a>
+            <a href="/runs" class="button button-clear">Run Logs</a>
+            <a href="/config" class="button button-clear">Configuration</a>
+            <a href="/watchlist" class="button button-outline">Watchlist</a>
+            <a href="/subscribers" class="button button-clear">Subscribers</a>
+        </nav>
+
+        <h2>Watchlist</h2>
+        <p class="hint">Watched courses are always notified, whatever the filters. {}</p>
+        {}
+        <form method="post" action="/watchlist">
+            <label for="pattern">Course code or pattern (* and ?, e.g. MAT-INF1*)</label>
+            <input type="text" id="pattern" name="pattern" required>
+            <button type="submit">Watch</button>
+        </form>
+
+        <table>
+            <thead>
+                <tr>
+                    <th>Pattern</th>
+                    <th>Added from</th>
+                    <th></th>
+                </tr>
+            </thead>
+            <tbody>
+                {}
+            </tbody>
+        </table>
+    </main>
+</body>
+</html>"#,
+        urgent, error, rows
+    )
+}
+
+/// Render the subscribers page with the add form
+fn render_subscribers(subscribers: &[SubscriberRecord], error: Option<&str>) -> String {
+    let mut rows = String::new();
+    for subscriber in subscribers {
+        let channels: Vec<&str> = [subscriber.email.as_deref(), subscriber.phone.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect();
+        rows.push_str(&format!(
+            r#"<tr>
+                <td>{}</td>
+                <td><code>{}</code></td>
+                <td>{}</td>
+                <td>{}</td>
+                <td>{}</td>
+                <td>
+                    <form method="post" action="/subscribers/remove">
+                        <input type="hidden" name="id" value="{}">
+                        <button type="submit" class="button button-outline">Remove</button>
+                    </form>
+                </td>
+            </tr>"#,
+            html_escape(&subscriber.name),
+            html_escape(subscriber.filter.as_deref().unwrap_or("all changes")),
+            html_escape(&channels.join(", ")),
+            html_escape(&subscriber.language),
+            html_escape(subscriber.quiet_hours.as_deref().unwrap_or("-")),
+            subscriber.id
+        ));
+    }
+    if rows.is_empty() {
+        rows.push_str(r#"<tr><td colspan="6">No subscribers yet</td></tr>"#);
+    }
+
+    let error = error
+        .map(|e| format!(r#"<p class="error">{}</p>"#, html_escape(e)))
+        .unwrap_or_default();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Subscribers - UiOBot</title>
+    <link rel="stylesheet" href="https://cdnjs.cloudflare.com/ajax/libs/milligram/1.4.1/milligram.min.css">
+    <style>
+        body {{ padding: 2rem 0; }}
+        nav {{ margin-bottom: 2rem; }}
+        nav a {{ margin-right: 1rem; }}
+        table {{ width: 100%; }}
+        td form {{ margin: 0; }}
+        .error {{ color: #dc3545; white-space: pre-wrap; }}
+        .hint {{ color: #606c76; }}
+    </style>
+</head>
+<body>
+    <main class="container">
+        <h1>UiOBot Dashboard</h1>
+        <nav>
+            <a href="/" class="button button-clear">Courses</a>
+            <a href="/runs" class="button button-clear">Run Logs</a>
+            <a href="/config" class="button button-clear">Configuration</a>
+            <a href="/watchlist" class="button button-clear">Watchlist</a>
+            <a href="/subscribers" class="button button-outline">Subscribers</a>
+        </nav>
+
+        <h2>Subscribers</h2>
+        <p class="hint">Each subscriber gets the changes matching their own filter, in addition to the configured recipients.
+        During quiet hours only watchlist hits are sent.</p>
+
+        <table>
+            <thead>
+                <tr>
+                    <th>Name</th>
+                    <th>Filter</th>
+                    <th>Channels</th>
+                    <th>Language</th>
+                    <th>Quiet hours</th>
+                    <th></th>
+                </tr>
+            </thead>
+            <tbody>
+                {}
+            </tbody>
+        </table>
+
+        <h3>Add subscriber</h3>
+        {}
+        <form method="post" action="/subscribers">
+            <label for="name">Name</label>
+            <input type="text" id="name" name="name" required>
+            <label for="filter">Filter (blank for all changes)</label>
+            <input type="text" id="filter" name="filter" placeholder="points &lt;= 5 and faculty ~ matematisk">
+            <label for="email">Email</label>
+            <input type="email" id="email" name="email">
+            <label for="phone">Phone (Norwegian number)</label>
+            <input type="text" id="phone" name="phone">
+            <label for="language">Language</label>
+            <select id="language" name="language">
+                <option value="no">Norsk</option>
+                <option value="en">English</option>
+            </select>
+            <label for="quiet_hours">Quiet hours (Europe/Oslo, e.g. 22:00-07:00)</label>
+            <input type="text" id="quiet_hours" name="quiet_hours">
+            <button type="submit">Add</button>
+        </form>
+    </main>
+</body>
+</html>"#,
+        rows, error
     )
 }
 
@@ -534,13 +1281,14 @@ fn render_error(message: &str) -> String {
     )
 }
 
-/// Simple HTML escaping
-fn html_escape(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&#x27;")
+/// Badge markup for a run status
+fn status_badge(status: RunStatus) -> &'static str {
+    match status {
+        RunStatus::Ok => "<span class=\"badge badge-success\">ok</span>",
+        RunStatus::Unchanged => "<span class=\"badge badge-disabled\">unchanged</span>",
+        RunStatus::Failed => "<span class=\"badge badge-danger\">failed</span>",
+        RunStatus::Anomalous => "<span class=\"badge badge-warning\">anomalous</span>",
+    }
 }
 
 /// Format timestamp for display (truncate to readable format)
@@ -552,3 +1300,99 @@ fn format_timestamp(ts: &str) -> String {
         .take(19)
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serve the dashboard on a free port, returning its base URL
+    async fn serve() -> String {
+        let db = Database::open_in_memory().await.unwrap();
+        let config = AppConfig {
+            email_enabled: false,
+            email_from: None,
+            email_to: Vec::new(),
+            sms_enabled: false,
+            sms_from: None,
+            sms_to: Vec::new(),
+            slack_enabled: false,
+            points_filter: "all courses".to_string(),
+            database_type: "memory".to_string(),
+            sources: Vec::new(),
+            hot_polling: String::new(),
+            watchlist: Vec::new(),
+            exclusions: Vec::new(),
+            urgent_channels: Vec::new(),
+            routes: Vec::new(),
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let router = create_router(db, config);
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        format!("http://{}", address)
+    }
+
+    /// POST a form with the dashboard credentials and an optional Origin header
+    async fn post_form(base: &str, path: &str, form: &[(&str, &str)], origin: Option<&str>) -> reqwest::Response {
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+        let mut request = client
+            .post(format!("{}{}", base, path))
+            .basic_auth("admin", Some("forktree"))
+            .form(form);
+        if let Some(origin) = origin {
+            request = request.header(header::ORIGIN, origin);
+        }
+        request.send().await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cross_origin_watchlist_posts_are_rejected() {
+        let base = serve().await;
+        let form = [("pattern", "IN1000")];
+
+        let response = post_form(&base, "/watchlist", &form, Some("https://evil.example")).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = post_form(&base, "/watchlist/remove", &form, Some("https://evil.example")).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        // Neither Origin nor Referer
+        let response = post_form(&base, "/watchlist", &form, None).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = post_form(&base, "/watchlist", &form, Some(&base)).await;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    }
+
+    #[tokio::test]
+    async fn test_cross_origin_subscriber_posts_are_rejected() {
+        let base = serve().await;
+        let form = [("name", "Ola"), ("email", "ola@example.org"), ("language", "no")];
+
+        let response = post_form(&base, "/subscribers", &form, Some("https://evil.example")).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = post_form(&base, "/subscribers/remove", &[("id", "1")], Some("https://evil.example")).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = post_form(&base, "/subscribers", &form, Some(&base)).await;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    }
+
+    #[test]
+    fn test_same_origin_compares_host_and_port() {
+        let headers = |host: &str, origin: (header::HeaderName, &str)| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::HOST, host.parse().unwrap());
+            headers.insert(origin.0, origin.1.parse().unwrap());
+            headers
+        };
+
+        assert!(is_same_origin(&headers("bot.example:8080", (header::ORIGIN, "http://bot.example:8080"))));
+        assert!(is_same_origin(&headers("bot.example", (header::ORIGIN, "https://bot.example"))));
+        assert!(is_same_origin(&headers("bot.example", (header::REFERER, "https://bot.example/watchlist"))));
+        assert!(!is_same_origin(&headers("bot.example:8080", (header::ORIGIN, "http://bot.example:9090"))));
+        assert!(!is_same_origin(&headers("bot.example", (header::ORIGIN, "https://bot.example.evil.org"))));
+        assert!(!is_same_origin(&headers("bot.example", (header::ORIGIN, "null"))));
+    }
+}